@@ -146,6 +146,27 @@ pub struct ActionTree {
     history: Vec<Action>,
 }
 
+/// Statistics of an [`ActionTree`], obtained by [`ActionTree::stats`].
+///
+/// Chance events are not expanded; each street is counted as if a single card were dealt.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ActionTreeStats {
+    /// Number of player nodes of each street (flop, turn, river).
+    pub num_player_nodes: [u64; 3],
+
+    /// Number of chance nodes.
+    pub num_chance_nodes: u64,
+
+    /// Number of terminal nodes (including fold nodes).
+    pub num_terminal_nodes: u64,
+
+    /// Number of nodes reached by an all-in action.
+    pub num_allin_nodes: u64,
+
+    /// Maximum number of player actions in a line.
+    pub max_depth: usize,
+}
+
 #[derive(Default)]
 #[cfg_attr(feature = "bincode", derive(Decode, Encode))]
 pub(crate) struct ActionTreeNode {
@@ -212,6 +233,44 @@ impl ActionTree {
         ret
     }
 
+    /// Returns the statistics of the action tree.
+    #[inline]
+    pub fn stats(&self) -> ActionTreeStats {
+        let mut ret = ActionTreeStats::default();
+        Self::stats_recursive(&self.root.lock(), Action::None, 0, &mut ret);
+        ret
+    }
+
+    /// Estimates the memory usage in bytes (uncompressed, compressed) of the storage of a
+    /// [`PostFlopGame`] built from this tree and `card_config`.
+    ///
+    /// The estimate is computed without building the game tree and excludes the small fixed
+    /// overhead reported by [`PostFlopGame::memory_usage`] (e.g., the node arena and hand
+    /// tables), so it is cheap enough to call whenever the bet sizes are edited.
+    ///
+    /// [`PostFlopGame`]: crate::PostFlopGame
+    /// [`PostFlopGame::memory_usage`]: crate::PostFlopGame::memory_usage
+    pub fn estimate_memory_usage(&self, card_config: &CardConfig) -> Result<(u64, u64), String> {
+        let private_cards = crate::PostFlopGame::private_cards_of(card_config, &self.config)?;
+        let num_hands = [private_cards[0].len() as u64, private_cards[1].len() as u64];
+
+        let isomorphism = card_config.isomorphism(&private_cards);
+        let (turn_coef, river_coef) =
+            card_config.chance_coefficients(&isomorphism.1, &isomorphism.4);
+        let coefs = [1, turn_coef as u64, river_coef as u64];
+
+        let mut num_elements = 0;
+        Self::num_elements_recursive(
+            &self.root.lock(),
+            Action::None,
+            &num_hands,
+            &coefs,
+            &mut num_elements,
+        );
+
+        Ok((4 * num_elements, 2 * num_elements))
+    }
+
     /// Adds a given line to the action tree.
     ///
     /// - `line` except the last action must exist in the current tree.
@@ -739,6 +798,65 @@ impl ActionTree {
         node.children.shrink_to_fit();
     }
 
+    /// Recursive function to collect the statistics of the tree.
+    fn stats_recursive(
+        node: &ActionTreeNode,
+        prev_action: Action,
+        depth: usize,
+        stats: &mut ActionTreeStats,
+    ) {
+        if let Action::AllIn(_) = prev_action {
+            stats.num_allin_nodes += 1;
+        }
+
+        if node.is_terminal() {
+            stats.num_terminal_nodes += 1;
+            stats.max_depth = stats.max_depth.max(depth);
+        } else if node.is_chance() {
+            stats.num_chance_nodes += 1;
+            Self::stats_recursive(&node.children[0].lock(), Action::Chance(0), depth, stats);
+        } else {
+            stats.num_player_nodes[node.board_state as usize] += 1;
+            for (&action, child) in node.actions.iter().zip(node.children.iter()) {
+                Self::stats_recursive(&child.lock(), action, depth + 1, stats);
+            }
+        }
+    }
+
+    /// Recursive function to count the storage elements required by the tree.
+    fn num_elements_recursive(
+        node: &ActionTreeNode,
+        prev_action: Action,
+        num_hands: &[u64; 2],
+        coefs: &[u64; 3],
+        num_elements: &mut u64,
+    ) {
+        let coef = coefs[node.board_state as usize];
+        if node.is_terminal() {
+            // do nothing
+        } else if node.is_chance() {
+            // counterfactual values are stored for the opponent of the previous player
+            let num_cfvalues = match node.player & PLAYER_MASK {
+                PLAYER_OOP => num_hands[1],
+                PLAYER_IP => num_hands[0],
+                _ => 0,
+            };
+            *num_elements += coef * num_cfvalues;
+            let child = &node.children[0].lock();
+            Self::num_elements_recursive(child, Action::Chance(0), num_hands, coefs, num_elements);
+        } else {
+            // cumulative regrets and strategy
+            let num_actions = node.actions.len() as u64;
+            *num_elements += 2 * coef * num_actions * num_hands[node.player as usize];
+            if let Action::None | Action::Chance(_) = prev_action {
+                *num_elements += coef * num_hands[PLAYER_IP as usize];
+            }
+            for (&action, child) in node.actions.iter().zip(node.children.iter()) {
+                Self::num_elements_recursive(&child.lock(), action, num_hands, coefs, num_elements);
+            }
+        }
+    }
+
     /// Recursive function to enumerate all invalid terminal nodes.
     fn invalid_terminals_recursive(
         node: &ActionTreeNode,
@@ -975,6 +1093,36 @@ impl ActionTree {
     }
 }
 
+impl Clone for ActionTree {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            config: self.config.clone(),
+            added_lines: self.added_lines.clone(),
+            removed_lines: self.removed_lines.clone(),
+            root: Box::new(MutexLike::new(self.root.lock().clone())),
+            history: self.history.clone(),
+        }
+    }
+}
+
+impl Clone for ActionTreeNode {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            player: self.player,
+            board_state: self.board_state,
+            amount: self.amount,
+            actions: self.actions.clone(),
+            children: self
+                .children
+                .iter()
+                .map(|child| MutexLike::new(child.lock().clone()))
+                .collect(),
+        }
+    }
+}
+
 impl ActionTreeNode {
     #[inline]
     fn is_terminal(&self) -> bool {
@@ -1064,7 +1212,12 @@ fn count_num_action_nodes_recursive(node: &ActionTreeNode, street: usize, count:
     }
 }
 
-fn merge_bet_actions(actions: Vec<Action>, pot: i32, offset: i32, param: f64) -> Vec<Action> {
+pub(crate) fn merge_bet_actions(
+    actions: Vec<Action>,
+    pot: i32,
+    offset: i32,
+    param: f64,
+) -> Vec<Action> {
     const EPS: f64 = 1e-12;
 
     let get_amount = |action: Action| match action {
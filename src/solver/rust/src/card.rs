@@ -1,3 +1,4 @@
+use crate::action_tree::*;
 use crate::hand::*;
 use crate::range::*;
 use std::mem;
@@ -30,6 +31,7 @@ pub const NOT_DEALT: Card = Card::MAX;
 ///     flop: flop_from_str("Td9d6h").unwrap(),
 ///     turn: card_from_str("Qc").unwrap(),
 ///     river: NOT_DEALT,
+///     deck: Deck::Standard,
 /// };
 /// ```
 #[derive(Debug, Clone)]
@@ -46,6 +48,79 @@ pub struct CardConfig {
 
     /// River card: must be in range [`0`, `52`) or `NOT_DEALT`.
     pub river: Card,
+
+    /// Deck variant: board cards must be in the deck, and hands containing cards outside the
+    /// deck are removed from the ranges.
+    pub deck: Deck,
+}
+
+/// Available deck variants.
+///
+/// The short deck is supported only by heads-up postflop games; the multiway, preflop, and
+/// push/fold solvers always use the standard deck.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "bincode", derive(Decode, Encode))]
+pub enum Deck {
+    /// Standard 52-card deck.
+    #[default]
+    Standard,
+
+    /// 36-card short deck (6+ hold'em): the 2s through 5s are removed. A flush beats a full
+    /// house, and A-6-7-8-9 is the lowest straight.
+    ShortDeck,
+}
+
+impl Deck {
+    /// Returns the number of cards in the deck.
+    #[inline]
+    pub fn num_cards(self) -> usize {
+        match self {
+            Deck::Standard => 52,
+            Deck::ShortDeck => 36,
+        }
+    }
+
+    /// Returns the mask of the cards removed from the standard deck.
+    #[inline]
+    pub fn removed_mask(self) -> u64 {
+        match self {
+            Deck::Standard => 0,
+            Deck::ShortDeck => (1 << 16) - 1,
+        }
+    }
+
+    /// Evaluates the given 7-card hand with the hand rankings of the deck.
+    #[inline]
+    pub(crate) fn evaluate(self, hand: &Hand) -> u16 {
+        match self {
+            Deck::Standard => hand.evaluate(),
+            Deck::ShortDeck => hand.evaluate_short_deck(),
+        }
+    }
+
+    /// Returns the raw value of the given 5- to 7-card hand with the straights of the deck.
+    #[inline]
+    pub(crate) fn raw_value(self, hand: &Hand) -> i32 {
+        match self {
+            Deck::Standard => hand.raw_value(),
+            Deck::ShortDeck => hand.raw_value_short_deck(),
+        }
+    }
+
+    /// Returns the category of the given hand with the straights of the deck.
+    #[inline]
+    pub(crate) fn category(self, hand: &Hand) -> u8 {
+        match self {
+            Deck::Standard => hand.category(),
+            Deck::ShortDeck => hand.category_short_deck(),
+        }
+    }
+
+    /// Returns whether the deck contains the given card.
+    #[inline]
+    pub fn contains(self, card: Card) -> bool {
+        (card as usize) < 52 && (1 << card) & self.removed_mask() == 0
+    }
 }
 
 impl Default for CardConfig {
@@ -56,6 +131,7 @@ impl Default for CardConfig {
             flop: [NOT_DEALT; 3],
             turn: NOT_DEALT,
             river: NOT_DEALT,
+            deck: Deck::Standard,
         }
     }
 }
@@ -102,7 +178,98 @@ pub(crate) fn index_to_card_pair(index: usize) -> (Card, Card) {
     (card1 as Card, card2 as Card)
 }
 
+/// Checks the board cards and returns the board state they correspond to.
+pub(crate) fn check_board_cards(
+    flop: [Card; 3],
+    turn: Card,
+    river: Card,
+) -> Result<BoardState, String> {
+    if flop.contains(&NOT_DEALT) {
+        return Err("Flop cards not initialized".to_string());
+    }
+
+    if flop.iter().any(|&c| 52 <= c) {
+        return Err(format!("Flop cards must be in [0, 52): flop = {flop:?}"));
+    }
+
+    if flop[0] == flop[1] || flop[0] == flop[2] || flop[1] == flop[2] {
+        return Err(format!("Flop cards must be unique: flop = {flop:?}"));
+    }
+
+    if turn != NOT_DEALT {
+        if 52 <= turn {
+            return Err(format!("Turn card must be in [0, 52): turn = {turn}"));
+        }
+
+        if flop.contains(&turn) {
+            return Err(format!(
+                "Turn card must be different from flop cards: turn = {turn}"
+            ));
+        }
+    }
+
+    if river != NOT_DEALT {
+        if 52 <= river {
+            return Err(format!("River card must be in [0, 52): river = {river}"));
+        }
+
+        if flop.contains(&river) {
+            return Err(format!(
+                "River card must be different from flop cards: river = {river}"
+            ));
+        }
+
+        if turn == river {
+            return Err(format!(
+                "River card must be different from turn card: river = {river}"
+            ));
+        }
+
+        if turn == NOT_DEALT {
+            return Err(format!(
+                "River card specified without turn card: river = {river}"
+            ));
+        }
+    }
+
+    Ok(match (turn != NOT_DEALT, river != NOT_DEALT) {
+        (false, _) => BoardState::Flop,
+        (true, false) => BoardState::Turn,
+        (true, true) => BoardState::River,
+    })
+}
+
 impl CardConfig {
+    /// Returns the number of turn nodes and river nodes that each action node of the respective
+    /// street is expanded into, excluding the isomorphic chances.
+    pub(crate) fn chance_coefficients(
+        &self,
+        isomorphism_card_turn: &[Card],
+        isomorphism_card_river: &[Vec<Card>; 4],
+    ) -> (usize, usize) {
+        let num_cards = self.deck.num_cards();
+        match (self.turn, self.river) {
+            (NOT_DEALT, _) => {
+                let mut river_coef = 0;
+                let flop = self.flop;
+                let flop_mask: u64 = (1 << flop[0]) | (1 << flop[1]) | (1 << flop[2]);
+                let skip_mask: u64 = isomorphism_card_turn.iter().map(|&card| 1 << card).sum();
+                let dead_mask = flop_mask | skip_mask | self.deck.removed_mask();
+                for turn in 0..52 {
+                    if (1 << turn) & dead_mask == 0 {
+                        river_coef += num_cards - 4 - isomorphism_card_river[turn & 3].len();
+                    }
+                }
+                (num_cards - 3 - isomorphism_card_turn.len(), river_coef)
+            }
+            (turn, NOT_DEALT) => (
+                1,
+                num_cards - 4 - isomorphism_card_river[turn as usize & 3].len(),
+            ),
+            _ => (0, 1),
+        }
+    }
+
     pub(crate) fn valid_indices(
         &self,
         private_cards: &PrivateCards,
@@ -118,7 +285,8 @@ impl CardConfig {
 
         let mut ret_turn = vec![Indices::default(); 52];
         for board in 0..52 {
-            if !self.flop.contains(&board)
+            if self.deck.contains(board)
+                && !self.flop.contains(&board)
                 && (self.turn == NOT_DEALT || self.turn == board)
                 && self.river == NOT_DEALT
             {
@@ -130,7 +298,9 @@ impl CardConfig {
         let mut ret_river = vec![Indices::default(); 52 * 51 / 2];
         for board1 in 0..52 {
             for board2 in board1 + 1..52 {
-                if !self.flop.contains(&board1)
+                if self.deck.contains(board1)
+                    && self.deck.contains(board2)
+                    && !self.flop.contains(&board1)
                     && !self.flop.contains(&board2)
                     && (self.turn == NOT_DEALT || board1 == self.turn || board2 == self.turn)
                     && (self.river == NOT_DEALT || board1 == self.river || board2 == self.river)
@@ -193,7 +363,9 @@ impl CardConfig {
 
         for board1 in 0..52 {
             for board2 in board1 + 1..52 {
-                if !board.contains(board1 as usize)
+                if self.deck.contains(board1)
+                    && self.deck.contains(board2)
+                    && !board.contains(board1 as usize)
                     && !board.contains(board2 as usize)
                     && (self.turn == NOT_DEALT || board1 == self.turn || board2 == self.turn)
                     && (self.river == NOT_DEALT || board1 == self.river || board2 == self.river)
@@ -223,8 +395,9 @@ impl CardConfig {
                                         None
                                     } else {
                                         let hand = board.add_card(c1).add_card(c2);
+                                        let strength = self.deck.evaluate(&hand);
                                         Some(StrengthItem {
-                                            strength: hand.evaluate() + 1, // +1 to avoid 0
+                                            strength: strength + 1, // +1 to avoid 0
                                             index: index as u16,
                                         })
                                     }
@@ -261,6 +434,7 @@ impl CardConfig {
         }
 
         let flop_mask: u64 = (1 << self.flop[0]) | (1 << self.flop[1]) | (1 << self.flop[2]);
+        let removed_mask = self.deck.removed_mask();
         let mut flop_rankset = [0; 4];
 
         for &card in &self.flop {
@@ -299,7 +473,7 @@ impl CardConfig {
             Self::isomorphism_internal(
                 &mut isomorphism_ref_turn,
                 &mut isomorphism_card_turn,
-                flop_mask,
+                flop_mask | removed_mask,
                 &isomorphic_suit,
             );
         }
@@ -311,7 +485,9 @@ impl CardConfig {
         // river isomorphism
         if self.river == NOT_DEALT {
             for turn in 0..52 {
-                if (1 << turn) & flop_mask != 0 || (self.turn != NOT_DEALT && self.turn != turn) {
+                if (1 << turn) & (flop_mask | removed_mask) != 0
+                    || (self.turn != NOT_DEALT && self.turn != turn)
+                {
                     continue;
                 }
 
@@ -344,7 +520,7 @@ impl CardConfig {
                 Self::isomorphism_internal(
                     &mut isomorphism_ref_river[turn as usize],
                     &mut isomorphism_card_river[turn as usize & 3],
-                    turn_mask,
+                    turn_mask | removed_mask,
                     &isomorphic_suit,
                 );
             }
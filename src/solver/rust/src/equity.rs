@@ -0,0 +1,555 @@
+use crate::card::*;
+use crate::hand::*;
+use crate::range::*;
+use crate::utility::*;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+/// The number of chunks the runouts are split into for parallel evaluation.
+const NUM_CHUNKS: usize = 64;
+
+/// A method for computing the equity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EquityMethod {
+    /// Enumerates all possible runouts.
+    Exact,
+
+    /// Samples the given number of runouts uniformly at random.
+    ///
+    /// The result is deterministic for the same `seed`.
+    MonteCarlo { num_samples: u64, seed: u64 },
+}
+
+/// A result of the range-vs-range equity computation.
+///
+/// Index 0 of each array is for the first range and index 1 is for the second range.
+#[derive(Debug, Clone, Default)]
+pub struct EquityResult {
+    /// The overall equity of each range, counting ties as half.
+    pub equity: [f64; 2],
+
+    /// The probability that each range wins.
+    pub win_rate: [f64; 2],
+
+    /// The probability of a tie.
+    pub tie_rate: f64,
+
+    /// The hands of each range that do not conflict with the board and dead cards.
+    pub hands: [Vec<(Card, Card)>; 2],
+
+    /// The weights of `hands`.
+    pub weights: [Vec<f32>; 2],
+
+    /// The equity of each hand against the opposing range (`NaN` if the hand has no matchups).
+    pub hand_equity: [Vec<f64>; 2],
+
+    /// The number of evaluated runouts.
+    pub num_runouts: u64,
+}
+
+/// Sums of weights of hands, both in total and per card.
+pub(crate) struct CardSums {
+    total: f64,
+    card: [f64; 52],
+}
+
+/// Accumulated win, tie, and matchup weights of each hand.
+struct Accumulator {
+    win: [Vec<f64>; 2],
+    tie: [Vec<f64>; 2],
+    total: [Vec<f64>; 2],
+    num_runouts: u64,
+}
+
+/// A simple SplitMix64 generator for sampling runouts.
+struct SplitMix64(u64);
+
+/// Computes the equity of `ranges[0]` against `ranges[1]` on a partial board of 0-5 cards.
+///
+/// Hands that conflict with the board or `dead_cards` are removed from the ranges.
+/// Card removal between the two ranges is accounted for exactly.
+///
+/// Exact enumeration evaluates every runout and is quite slow when the board has fewer than three
+/// cards; [`EquityMethod::MonteCarlo`] is recommended in that case.
+///
+/// # Examples
+/// ```
+/// use postflop_solver::*;
+///
+/// let ranges = ["AA".parse().unwrap(), "KK".parse().unwrap()];
+/// let board = cards_from_str("Td9d6h").unwrap();
+/// let result = compute_equity(&ranges, &board, &[], EquityMethod::Exact).unwrap();
+/// assert!(result.equity[0] > 0.9);
+/// ```
+pub fn compute_equity(
+    ranges: &[Range; 2],
+    board: &[Card],
+    dead_cards: &[Card],
+    method: EquityMethod,
+) -> Result<EquityResult, String> {
+    if board.len() > 5 {
+        return Err(format!("Board must have at most 5 cards: {}", board.len()));
+    }
+
+    let board_mask = cards_to_mask(board)?;
+    let dead_mask = cards_to_mask(dead_cards)?;
+
+    if board_mask.count_ones() as usize != board.len() {
+        return Err("Board cards must be unique".to_string());
+    }
+
+    if board_mask & dead_mask != 0 {
+        return Err("Dead cards must not overlap with the board".to_string());
+    }
+
+    let mut hands = [Vec::new(), Vec::new()];
+    let mut weights = [Vec::new(), Vec::new()];
+    for player in 0..2 {
+        (hands[player], weights[player]) = ranges[player].get_hands_weights(board_mask | dead_mask);
+        if hands[player].is_empty() {
+            return Err(format!("Range {} is empty after card removal", player));
+        }
+    }
+
+    // weight of the identical combo in the opposing range; it cannot be dealt against itself, but
+    // `disjoint` subtracts it twice from the tie and total sums, so it is added back once
+    let same_weight = [0, 1].map(|player| {
+        hands[player]
+            .iter()
+            .map(|&(c1, c2)| ranges[player ^ 1].get_weight_by_cards(c1, c2) as f64)
+            .collect::<Vec<_>>()
+    });
+
+    let deck = (0..52)
+        .filter(|&card| (board_mask | dead_mask) & (1 << card) == 0)
+        .collect::<Vec<Card>>();
+    let num_missing = 5 - board.len();
+
+    if deck.len() < num_missing + 4 {
+        return Err("Not enough cards left in the deck".to_string());
+    }
+
+    let evaluate = |runouts: &mut dyn Iterator<Item = [Card; 5]>| {
+        let mut acc = Accumulator::new([hands[0].len(), hands[1].len()]);
+        for runout in runouts {
+            acc.add_runout(&runout, &hands, &weights, &same_weight);
+        }
+        acc
+    };
+
+    let accumulators = evaluate_runouts(&deck, board, method, evaluate)?;
+
+    let mut acc = Accumulator::new([hands[0].len(), hands[1].len()]);
+    for other in &accumulators {
+        acc.merge(other);
+    }
+
+    let mut win_sum = [0.0; 2];
+    let mut tie_sum = 0.0;
+    let mut total_sum = 0.0;
+    let mut hand_equity = [Vec::new(), Vec::new()];
+
+    for player in 0..2 {
+        hand_equity[player] = (0..hands[player].len())
+            .map(|i| {
+                let (win, tie, total) =
+                    (acc.win[player][i], acc.tie[player][i], acc.total[player][i]);
+                if total > 0.0 {
+                    (win + 0.5 * tie) / total
+                } else {
+                    f64::NAN
+                }
+            })
+            .collect();
+
+        for (i, &weight) in weights[player].iter().enumerate() {
+            win_sum[player] += weight as f64 * acc.win[player][i];
+            if player == 0 {
+                tie_sum += weight as f64 * acc.tie[player][i];
+                total_sum += weight as f64 * acc.total[player][i];
+            }
+        }
+    }
+
+    if total_sum <= 0.0 {
+        return Err("Ranges have no valid matchups".to_string());
+    }
+
+    let win_rate = win_sum.map(|win| win / total_sum);
+    let tie_rate = tie_sum / total_sum;
+
+    Ok(EquityResult {
+        equity: win_rate.map(|win| win + 0.5 * tie_rate),
+        win_rate,
+        tie_rate,
+        hands,
+        weights,
+        hand_equity,
+        num_runouts: acc.num_runouts,
+    })
+}
+
+/// Applies `evaluate` to the completions of `board` with the cards of `deck`, which are split into
+/// chunks and processed in parallel.
+///
+/// `deck` must not contain the board cards.
+pub(crate) fn evaluate_runouts<T: Send>(
+    deck: &[Card],
+    board: &[Card],
+    method: EquityMethod,
+    evaluate: impl Fn(&mut dyn Iterator<Item = [Card; 5]>) -> T + Sync + Send,
+) -> Result<Vec<T>, String> {
+    let num_missing = 5 - board.len();
+    let mut base_board = [0; 5];
+    base_board[..board.len()].copy_from_slice(board);
+
+    let ret = match method {
+        _ if num_missing == 0 => vec![evaluate(&mut std::iter::once(base_board))],
+
+        EquityMethod::Exact => {
+            let runouts = enumerate_runouts(deck, num_missing, base_board, board.len());
+            let chunk_size = runouts.len().div_ceil(NUM_CHUNKS);
+            into_par_iter(0..NUM_CHUNKS)
+                .map(|chunk| {
+                    let start = (chunk * chunk_size).min(runouts.len());
+                    let end = ((chunk + 1) * chunk_size).min(runouts.len());
+                    evaluate(&mut runouts[start..end].iter().copied())
+                })
+                .collect::<Vec<_>>()
+        }
+
+        EquityMethod::MonteCarlo { num_samples, seed } => {
+            if num_samples == 0 {
+                return Err("Number of samples must be positive".to_string());
+            }
+
+            into_par_iter(0..NUM_CHUNKS)
+                .map(|chunk| {
+                    let chunk = chunk as u64;
+                    let chunk_samples = (chunk + 1) * num_samples / NUM_CHUNKS as u64
+                        - chunk * num_samples / NUM_CHUNKS as u64;
+
+                    let mut rng = SplitMix64(seed ^ SplitMix64(chunk).next());
+                    let mut deck = deck.to_vec();
+                    let mut runouts = (0..chunk_samples).map(|_| {
+                        let mut runout = base_board;
+                        for i in 0..num_missing {
+                            let j = i + rng.next_below(deck.len() - i);
+                            deck.swap(i, j);
+                            runout[board.len() + i] = deck[i];
+                        }
+                        runout
+                    });
+
+                    evaluate(&mut runouts)
+                })
+                .collect::<Vec<_>>()
+        }
+    };
+
+    Ok(ret)
+}
+
+/// Converts a list of cards into a bit mask.
+#[inline]
+fn cards_to_mask(cards: &[Card]) -> Result<u64, String> {
+    cards.iter().try_fold(0u64, |mask, &card| {
+        if card >= 52 {
+            Err(format!("Invalid card: {}", card))
+        } else {
+            Ok(mask | (1 << card))
+        }
+    })
+}
+
+/// Enumerates all completions of `base_board` with `num_missing` cards from `deck`.
+fn enumerate_runouts(
+    deck: &[Card],
+    num_missing: usize,
+    base_board: [Card; 5],
+    board_len: usize,
+) -> Vec<[Card; 5]> {
+    let mut ret = Vec::new();
+    let mut indices = (0..num_missing).collect::<Vec<_>>();
+
+    loop {
+        let mut runout = base_board;
+        for (i, &index) in indices.iter().enumerate() {
+            runout[board_len + i] = deck[index];
+        }
+        ret.push(runout);
+
+        // advance to the next combination in lexicographic order
+        let mut i = num_missing;
+        while i > 0 && indices[i - 1] == deck.len() - num_missing + i - 1 {
+            i -= 1;
+        }
+        if i == 0 {
+            return ret;
+        }
+        indices[i - 1] += 1;
+        for j in i..num_missing {
+            indices[j] = indices[j - 1] + 1;
+        }
+    }
+}
+
+impl CardSums {
+    #[inline]
+    pub(crate) fn new() -> Self {
+        Self {
+            total: 0.0,
+            card: [0.0; 52],
+        }
+    }
+
+    #[inline]
+    pub(crate) fn add(&mut self, (c1, c2): (Card, Card), weight: f64) {
+        self.total += weight;
+        self.card[c1 as usize] += weight;
+        self.card[c2 as usize] += weight;
+    }
+
+    /// Returns the sum of weights of hands that do not share a card with the given hand.
+    ///
+    /// The identical hand is subtracted twice, so the caller must add it back if needed.
+    #[inline]
+    pub(crate) fn disjoint(&self, (c1, c2): (Card, Card)) -> f64 {
+        self.total - self.card[c1 as usize] - self.card[c2 as usize]
+    }
+}
+
+impl Accumulator {
+    #[inline]
+    fn new(len: [usize; 2]) -> Self {
+        Self {
+            win: len.map(|n| vec![0.0; n]),
+            tie: len.map(|n| vec![0.0; n]),
+            total: len.map(|n| vec![0.0; n]),
+            num_runouts: 0,
+        }
+    }
+
+    fn merge(&mut self, other: &Self) {
+        for player in 0..2 {
+            for (dst, src) in [
+                (&mut self.win[player], &other.win[player]),
+                (&mut self.tie[player], &other.tie[player]),
+                (&mut self.total[player], &other.total[player]),
+            ] {
+                dst.iter_mut().zip(src).for_each(|(d, s)| *d += s);
+            }
+        }
+        self.num_runouts += other.num_runouts;
+    }
+
+    /// Adds the matchups of every hand on the given complete board.
+    fn add_runout(
+        &mut self,
+        runout: &[Card; 5],
+        hands: &[Vec<(Card, Card)>; 2],
+        weights: &[Vec<f32>; 2],
+        same_weight: &[Vec<f64>; 2],
+    ) {
+        let board_mask = runout.iter().fold(0u64, |mask, &card| mask | (1 << card));
+        let board_hand = runout
+            .iter()
+            .fold(Hand::new(), |hand, &card| hand.add_card(card as usize));
+
+        let strengths = [0, 1].map(|player| {
+            let mut ret = hands[player]
+                .iter()
+                .enumerate()
+                .filter(|&(_, &(c1, c2))| board_mask & ((1 << c1) | (1 << c2)) == 0)
+                .map(|(i, &(c1, c2))| {
+                    let hand = board_hand.add_card(c1 as usize).add_card(c2 as usize);
+                    (hand.evaluate(), i)
+                })
+                .collect::<Vec<_>>();
+            ret.sort_unstable();
+            ret
+        });
+
+        for player in 0..2 {
+            let opponent = player ^ 1;
+            let strength = &strengths[player];
+            let opponent_strength = &strengths[opponent];
+
+            let mut all = CardSums::new();
+            for &(_, j) in opponent_strength {
+                all.add(hands[opponent][j], weights[opponent][j] as f64);
+            }
+
+            let mut lower = CardSums::new();
+            let mut j = 0;
+            let mut i = 0;
+
+            while i < strength.len() {
+                let value = strength[i].0;
+
+                while j < opponent_strength.len() && opponent_strength[j].0 < value {
+                    let k = opponent_strength[j].1;
+                    lower.add(hands[opponent][k], weights[opponent][k] as f64);
+                    j += 1;
+                }
+
+                let mut equal = CardSums::new();
+                for &(_, k) in opponent_strength[j..]
+                    .iter()
+                    .take_while(|&&(v, _)| v == value)
+                {
+                    equal.add(hands[opponent][k], weights[opponent][k] as f64);
+                }
+
+                while i < strength.len() && strength[i].0 == value {
+                    let k = strength[i].1;
+                    let hand = hands[player][k];
+                    let same = same_weight[player][k];
+                    self.win[player][k] += lower.disjoint(hand);
+                    self.tie[player][k] += equal.disjoint(hand) + same;
+                    self.total[player][k] += all.disjoint(hand) + same;
+                    i += 1;
+                }
+            }
+        }
+
+        self.num_runouts += 1;
+    }
+}
+
+impl SplitMix64 {
+    #[inline]
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a uniformly distributed integer in `0..n`.
+    #[inline]
+    fn next_below(&mut self, n: usize) -> usize {
+        ((self.next() as u128 * n as u128) >> 64) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Computes the equity by comparing every pair of hands on every runout.
+    fn brute_force(ranges: &[Range; 2], board: &[Card]) -> [f64; 2] {
+        let board_mask = cards_to_mask(board).unwrap();
+        let (hands0, weights0) = ranges[0].get_hands_weights(board_mask);
+        let (hands1, weights1) = ranges[1].get_hands_weights(board_mask);
+        let deck = (0..52)
+            .filter(|&card| board_mask & (1 << card) == 0)
+            .collect::<Vec<Card>>();
+
+        let mut base_board = [0; 5];
+        base_board[..board.len()].copy_from_slice(board);
+        let runouts = enumerate_runouts(&deck, 5 - board.len(), base_board, board.len());
+
+        let mut sum = [0.0; 2];
+        for (&(a1, a2), &w0) in hands0.iter().zip(&weights0) {
+            for (&(b1, b2), &w1) in hands1.iter().zip(&weights1) {
+                let mask = (1u64 << a1) | (1 << a2) | (1 << b1) | (1 << b2);
+                if mask.count_ones() != 4 {
+                    continue;
+                }
+                for runout in &runouts {
+                    if runout.iter().any(|&card| mask & (1 << card) != 0) {
+                        continue;
+                    }
+                    let hand = runout
+                        .iter()
+                        .fold(Hand::new(), |hand, &card| hand.add_card(card as usize));
+                    let s0 = hand.add_card(a1 as usize).add_card(a2 as usize).evaluate();
+                    let s1 = hand.add_card(b1 as usize).add_card(b2 as usize).evaluate();
+                    let w = (w0 * w1) as f64;
+                    let (e0, e1) = match s0.cmp(&s1) {
+                        std::cmp::Ordering::Greater => (1.0, 0.0),
+                        std::cmp::Ordering::Less => (0.0, 1.0),
+                        std::cmp::Ordering::Equal => (0.5, 0.5),
+                    };
+                    sum[0] += w * e0;
+                    sum[1] += w * e1;
+                }
+            }
+        }
+
+        let total = sum[0] + sum[1];
+        sum.map(|s| s / total)
+    }
+
+    #[test]
+    fn exact_matches_brute_force() {
+        let ranges = [
+            "AA,KK,AhQh".parse().unwrap(),
+            "QQ,JJ,AKs,QhJh".parse().unwrap(),
+        ];
+        let board = cards_from_str("Td9d6h").unwrap();
+
+        let result = compute_equity(&ranges, &board, &[], EquityMethod::Exact).unwrap();
+        let expected = brute_force(&ranges, &board);
+
+        assert_eq!(result.num_runouts, 49 * 48 / 2);
+        assert!((result.equity[0] - expected[0]).abs() < 1e-9);
+        assert!((result.equity[1] - expected[1]).abs() < 1e-9);
+        assert!((result.equity[0] + result.equity[1] - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn monte_carlo_converges() {
+        let ranges = [
+            "22+,A2s+,KTo+".parse().unwrap(),
+            "55+,ATs+,KQo".parse().unwrap(),
+        ];
+        let board = cards_from_str("Ks7d2c").unwrap();
+
+        let exact = compute_equity(&ranges, &board, &[], EquityMethod::Exact).unwrap();
+        let method = EquityMethod::MonteCarlo {
+            num_samples: 2000,
+            seed: 42,
+        };
+        let sampled = compute_equity(&ranges, &board, &[], method).unwrap();
+        let sampled_again = compute_equity(&ranges, &board, &[], method).unwrap();
+
+        assert_eq!(sampled.num_runouts, 2000);
+        assert_eq!(sampled.equity, sampled_again.equity);
+        assert!((sampled.equity[0] - exact.equity[0]).abs() < 0.01);
+    }
+
+    #[test]
+    fn hand_equity_and_dead_cards() {
+        let ranges = ["AA".parse().unwrap(), "KK".parse().unwrap()];
+        let board = cards_from_str("2c3d7h8sJc").unwrap();
+        let dead_cards = cards_from_str("AsKs").unwrap();
+
+        let result = compute_equity(&ranges, &board, &dead_cards, EquityMethod::Exact).unwrap();
+
+        assert_eq!(result.num_runouts, 1);
+        assert_eq!(result.hands[0].len(), 3);
+        assert_eq!(result.hands[1].len(), 3);
+        assert_eq!(result.equity, [1.0, 0.0]);
+        assert_eq!(result.tie_rate, 0.0);
+        assert!(result.hand_equity[0].iter().all(|&e| e == 1.0));
+    }
+
+    #[test]
+    fn invalid_inputs() {
+        let ranges = ["AA".parse().unwrap(), "KK".parse().unwrap()];
+        let board = cards_from_str("2c3d7h8sJc").unwrap();
+        let conflict = cards_from_str("2c").unwrap();
+        let six_cards = cards_from_str("2c3d7h8sJcQs").unwrap();
+
+        assert!(compute_equity(&ranges, &board, &conflict, EquityMethod::Exact).is_err());
+        assert!(compute_equity(&ranges, &six_cards, &[], EquityMethod::Exact).is_err());
+
+        let ranges = ["AA".parse().unwrap(), "AsAh".parse().unwrap()];
+        let dead_cards = cards_from_str("As").unwrap();
+        assert!(compute_equity(&ranges, &[], &dead_cards, EquityMethod::Exact).is_err());
+    }
+}
@@ -9,6 +9,7 @@
 //
 // `VarIntEncoding`: https://github.com/bincode-org/bincode/blob/trunk/docs/spec.md#varintencoding
 
+use crate::action_tree::*;
 use crate::bunching::*;
 use crate::game::*;
 use crate::interface::*;
@@ -241,7 +242,8 @@ impl FileData for PostFlopGame {
     }
 
     fn is_ready_to_save(&self) -> bool {
-        self.is_solved()
+        // unsolved games can be saved only with the cumulative regrets for resuming
+        self.is_solved() || (self.is_ready() && self.target_storage_mode() == BoardState::River)
     }
 
     fn estimated_memory_usage(&self) -> u64 {
@@ -266,9 +268,10 @@ impl FileData for BunchingData {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::action_tree::*;
     use crate::card::*;
+    use crate::icm::*;
     use crate::range::*;
+    use crate::solver::*;
     use crate::utility::*;
 
     #[test]
@@ -330,6 +333,103 @@ mod tests {
         assert!((root_ev_ip - 15.0).abs() < 1e-4);
     }
 
+    #[test]
+    fn save_and_resume_solve() {
+        let card_config = CardConfig {
+            range: ["QQ+,AKs".parse().unwrap(), "JJ+,AKo".parse().unwrap()],
+            flop: flop_from_str("Td9d6h").unwrap(),
+            turn: card_from_str("Qc").unwrap(),
+            ..Default::default()
+        };
+
+        let tree_config = TreeConfig {
+            initial_state: BoardState::Turn,
+            starting_pot: 60,
+            effective_stack: 970,
+            turn_bet_sizes: [("50%", "").try_into().unwrap(), Default::default()],
+            river_bet_sizes: [("50%", "").try_into().unwrap(), Default::default()],
+            ..Default::default()
+        };
+
+        let action_tree = ActionTree::new(tree_config).unwrap();
+        let mut game = PostFlopGame::with_config(card_config, action_tree).unwrap();
+        game.allocate_memory(false);
+
+        let mut reference = PostFlopGame::with_config(
+            game.card_config().clone(),
+            ActionTree::new(game.tree_config().clone()).unwrap(),
+        )
+        .unwrap();
+        reference.allocate_memory(false);
+        let expected = solve(&mut reference, 40, 0.0, false);
+
+        // solve halfway and save
+        continue_solve(&mut game, 20, 0.0, false);
+        assert_eq!(game.num_iterations(), 20);
+        save_data_to_file(&game, "", "tmpfile-resume.flop", None).unwrap();
+
+        // load and resume
+        let mut game: PostFlopGame = load_data_from_file("tmpfile-resume.flop", None).unwrap().0;
+        std::fs::remove_file("tmpfile-resume.flop").unwrap();
+
+        assert!(!game.is_solved());
+        assert_eq!(game.num_iterations(), 20);
+
+        let exploitability = solve(&mut game, 40, 0.0, false);
+        assert_eq!(game.num_iterations(), 40);
+        assert!((exploitability - expected).abs() < 1e-4);
+    }
+
+    #[test]
+    fn save_and_load_file_tournament_payoff() {
+        let card_config = CardConfig {
+            range: ["QQ+,AKs".parse().unwrap(), "JJ+,AKo".parse().unwrap()],
+            flop: flop_from_str("Td9d6h").unwrap(),
+            turn: card_from_str("Qc").unwrap(),
+            ..Default::default()
+        };
+
+        let tree_config = TreeConfig {
+            initial_state: BoardState::Turn,
+            starting_pot: 60,
+            effective_stack: 970,
+            turn_bet_sizes: [("50%", "a").try_into().unwrap(), Default::default()],
+            river_bet_sizes: [("50%", "a").try_into().unwrap(), Default::default()],
+            ..Default::default()
+        };
+
+        let payoff = TournamentPayoff {
+            stacks: [970.0, 970.0],
+            other_stacks: vec![1000.0],
+            payouts: vec![60.0, 40.0],
+            bounties: [5.0, 5.0],
+        };
+
+        let action_tree = ActionTree::new(tree_config).unwrap();
+        let mut game = PostFlopGame::with_config(card_config, action_tree).unwrap();
+        game.set_tournament_payoff(payoff.clone()).unwrap();
+        game.allocate_memory(false);
+        let expected = solve(&mut game, 20, 0.0, false);
+
+        game.cache_normalized_weights();
+        let expected_ev = compute_average(&game.expected_values(0), game.normalized_weights(0));
+
+        // save
+        save_data_to_file(&game, "", "tmpfile-payoff.flop", None).unwrap();
+
+        // load
+        let mut game: PostFlopGame = load_data_from_file("tmpfile-payoff.flop", None).unwrap().0;
+        std::fs::remove_file("tmpfile-payoff.flop").unwrap();
+
+        assert_eq!(game.tournament_payoff(), Some(&payoff));
+        assert!(game.set_tournament_payoff(payoff).is_err());
+
+        game.cache_normalized_weights();
+        let ev = compute_average(&game.expected_values(0), game.normalized_weights(0));
+        assert!((ev - expected_ev).abs() < 1e-4);
+        assert!((compute_exploitability(&game) - expected).abs() < 1e-4);
+    }
+
     #[test]
     #[cfg(feature = "zstd")]
     fn save_and_load_file_compressed() {
@@ -0,0 +1,316 @@
+use crate::card::*;
+use crate::range::*;
+
+/// The total number of flops.
+pub const NUM_FLOPS: u32 = 22100;
+
+/// A flop with its weight in an aggregate study.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WeightedFlop {
+    /// The flop, sorted in ascending order of card IDs.
+    pub flop: [Card; 3],
+
+    /// The number of flops this flop represents.
+    pub weight: f64,
+}
+
+/// Returns the rank set of each suit of a flop.
+#[inline]
+fn flop_ranksets(flop: [Card; 3]) -> [u16; 4] {
+    let mut ranksets = [0; 4];
+    for card in flop {
+        ranksets[card as usize & 3] |= 1 << (card >> 2);
+    }
+    ranksets
+}
+
+/// Returns the canonical representative of a flop under suit isomorphism.
+///
+/// Two flops are isomorphic if their suits can be permuted so that the rank sets of all suits
+/// match, as in the suit isomorphism of [`CardConfig`]. The representative assigns clubs,
+/// diamonds, hearts, and spades to the suits in descending order of their rank sets.
+///
+/// # Examples
+/// ```
+/// use postflop_solver::*;
+///
+/// let flop = flop_from_str("AsKsQs").unwrap();
+/// assert_eq!(canonical_flop(flop), flop_from_str("AcKcQc").unwrap());
+/// ```
+pub fn canonical_flop(flop: [Card; 3]) -> [Card; 3] {
+    let mut ranksets = flop_ranksets(flop);
+    ranksets.sort_unstable_by(|a, b| b.cmp(a));
+
+    let mut ret = [0; 3];
+    let mut index = 0;
+    for (suit, &rankset) in ranksets.iter().enumerate() {
+        for rank in 0..13 {
+            if rankset & (1 << rank) != 0 {
+                ret[index] = (rank << 2) | suit as Card;
+                index += 1;
+            }
+        }
+    }
+
+    ret.sort_unstable();
+    ret
+}
+
+/// Returns the number of flops isomorphic to the given flop, including itself.
+///
+/// # Examples
+/// ```
+/// use postflop_solver::*;
+///
+/// assert_eq!(flop_weight(flop_from_str("AsKsQs").unwrap()), 4);
+/// assert_eq!(flop_weight(flop_from_str("AsKsQh").unwrap()), 12);
+/// assert_eq!(flop_weight(flop_from_str("AsKdQh").unwrap()), 24);
+/// ```
+pub fn flop_weight(flop: [Card; 3]) -> u32 {
+    let ranksets = flop_ranksets(flop);
+
+    // 24 permutations of suits divided by those that keep the rank sets
+    let mut ret = 24;
+    let mut seen = Vec::with_capacity(4);
+    for rankset in ranksets {
+        if !seen.contains(&rankset) {
+            let count = ranksets.iter().filter(|&&r| r == rankset).count() as u32;
+            ret /= (1..=count).product::<u32>();
+            seen.push(rankset);
+        }
+    }
+
+    ret
+}
+
+/// Returns all 1,755 strategically distinct flops under suit isomorphism.
+///
+/// Each flop is sorted and canonical in the sense of [`canonical_flop`].
+/// Note that suit isomorphism assumes that both ranges are suit-symmetric.
+pub fn canonical_flops() -> Vec<[Card; 3]> {
+    let mut ret = Vec::new();
+    for card1 in 0..52 {
+        for card2 in card1 + 1..52 {
+            for card3 in card2 + 1..52 {
+                let flop = [card1, card2, card3];
+                if canonical_flop(flop) == flop {
+                    ret.push(flop);
+                }
+            }
+        }
+    }
+    ret
+}
+
+/// Returns all 1,755 canonical flops weighted by the number of flops they represent.
+///
+/// The weights sum to [`NUM_FLOPS`].
+pub fn weighted_canonical_flops() -> Vec<WeightedFlop> {
+    canonical_flops()
+        .into_iter()
+        .map(|flop| WeightedFlop {
+            flop,
+            weight: flop_weight(flop) as f64,
+        })
+        .collect()
+}
+
+/// Returns a representative subset of canonical flops whose weights approximate all flops.
+///
+/// Only the flops whose cards are all in `deck` are considered. Commonly used sizes are 25, 49,
+/// 95, and 184; `size` must be between 6 and 1,755 (573 with the short deck).
+/// The flops are first split by suit pattern (monotone, two-tone, rainbow) and pairing, and each
+/// group receives a number of flops proportional to its weight (at least one).
+/// Within a group, flops are ordered by rank from the highest card, split into contiguous chunks
+/// of equal size, and the middle flop of each chunk represents the whole chunk.
+/// The result is deterministic and the weights sum to the number of flops of `deck`
+/// ([`NUM_FLOPS`] with the standard deck).
+pub fn flop_subset(size: usize, deck: Deck) -> Result<Vec<WeightedFlop>, String> {
+    let all = weighted_canonical_flops()
+        .into_iter()
+        .filter(|f| f.flop.iter().all(|&card| deck.contains(card)))
+        .collect::<Vec<_>>();
+    let total_weight = all.iter().map(|f| f.weight).sum::<f64>();
+
+    // (number of suits, number of ranks) of each group
+    let texture = |flop: [Card; 3]| {
+        let ranksets = flop_ranksets(flop);
+        let num_suits = ranksets.iter().filter(|&&r| r != 0).count();
+        let num_ranks = ranksets.iter().fold(0, |acc, &r| acc | r).count_ones();
+        (num_suits, num_ranks)
+    };
+
+    let mut groups: Vec<((usize, u32), Vec<WeightedFlop>)> = Vec::new();
+    for &weighted in &all {
+        let key = texture(weighted.flop);
+        match groups.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, flops)) => flops.push(weighted),
+            None => groups.push((key, vec![weighted])),
+        }
+    }
+
+    if size < groups.len() || size > all.len() {
+        return Err(format!(
+            "Subset size must be between {} and {}: {}",
+            groups.len(),
+            all.len(),
+            size
+        ));
+    }
+
+    // allocate the flops to the groups by the largest remainder method
+    let ideal = groups
+        .iter()
+        .map(|(_, flops)| {
+            let weight = flops.iter().map(|f| f.weight).sum::<f64>();
+            size as f64 * weight / total_weight
+        })
+        .collect::<Vec<_>>();
+    let mut counts = ideal
+        .iter()
+        .zip(&groups)
+        .map(|(&x, (_, flops))| (x as usize).clamp(1, flops.len()))
+        .collect::<Vec<_>>();
+
+    while counts.iter().sum::<usize>() < size {
+        let i = (0..groups.len())
+            .filter(|&i| counts[i] < groups[i].1.len())
+            .max_by(|&i, &j| {
+                (ideal[i] - counts[i] as f64).total_cmp(&(ideal[j] - counts[j] as f64))
+            })
+            .unwrap();
+        counts[i] += 1;
+    }
+
+    while counts.iter().sum::<usize>() > size {
+        let i = (0..groups.len())
+            .filter(|&i| counts[i] > 1)
+            .min_by(|&i, &j| {
+                (ideal[i] - counts[i] as f64).total_cmp(&(ideal[j] - counts[j] as f64))
+            })
+            .unwrap();
+        counts[i] -= 1;
+    }
+
+    let mut ret = Vec::with_capacity(size);
+
+    for ((_, mut flops), count) in groups.into_iter().zip(counts) {
+        flops.sort_by_key(|f| std::cmp::Reverse([f.flop[2] >> 2, f.flop[1] >> 2, f.flop[0] >> 2]));
+
+        for chunk in 0..count {
+            let start = chunk * flops.len() / count;
+            let end = (chunk + 1) * flops.len() / count;
+            ret.push(WeightedFlop {
+                flop: flops[(start + end) / 2].flop,
+                weight: flops[start..end].iter().map(|f| f.weight).sum(),
+            });
+        }
+    }
+
+    ret.sort_unstable_by_key(|f| f.flop);
+    Ok(ret)
+}
+
+/// Attempts to convert a flop into a string in descending order of card IDs.
+///
+/// # Examples
+/// ```
+/// use postflop_solver::*;
+///
+/// assert_eq!(flop_to_string([0, 5, 10]), Ok("4h3d2c".to_string()));
+/// ```
+#[inline]
+pub fn flop_to_string(flop: [Card; 3]) -> Result<String, String> {
+    flop.iter()
+        .rev()
+        .map(|&card| card_to_string(card))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_canonical_flops() {
+        let flops = canonical_flops();
+        assert_eq!(flops.len(), 1755);
+        assert!(flops.iter().all(|&flop| canonical_flop(flop) == flop));
+        assert!(flops.windows(2).all(|w| w[0] < w[1]));
+
+        let mut count = 0;
+        for card1 in 0..52 {
+            for card2 in card1 + 1..52 {
+                for card3 in card2 + 1..52 {
+                    let canonical = canonical_flop([card1, card2, card3]);
+                    assert!(flops.binary_search(&canonical).is_ok());
+                    count += 1;
+                }
+            }
+        }
+
+        let weights = weighted_canonical_flops();
+        assert_eq!(count, NUM_FLOPS);
+        assert_eq!(
+            weights.iter().map(|f| f.weight).sum::<f64>(),
+            NUM_FLOPS as f64
+        );
+    }
+
+    #[test]
+    fn test_flop_subset() {
+        let all = weighted_canonical_flops();
+        let share = |flops: &[WeightedFlop], filter: fn(&[Card; 3]) -> bool| {
+            let total = flops.iter().map(|f| f.weight).sum::<f64>();
+            flops
+                .iter()
+                .filter(|f| filter(&f.flop))
+                .map(|f| f.weight)
+                .sum::<f64>()
+                / total
+        };
+        let is_monotone = |flop: &[Card; 3]| flop.iter().all(|&c| c & 3 == flop[0] & 3);
+        let is_ace_high = |flop: &[Card; 3]| flop[2] >> 2 == 12;
+
+        for size in [25, 49, 95, 184] {
+            let subset = flop_subset(size, Deck::Standard).unwrap();
+            assert_eq!(subset.len(), size);
+            assert!(subset.windows(2).all(|w| w[0].flop < w[1].flop));
+            assert!(subset.iter().all(|f| canonical_flop(f.flop) == f.flop));
+            assert_eq!(
+                subset.iter().map(|f| f.weight).sum::<f64>(),
+                NUM_FLOPS as f64
+            );
+
+            let monotone = share(&all, is_monotone) - share(&subset, is_monotone);
+            let ace_high = share(&all, is_ace_high) - share(&subset, is_ace_high);
+            assert!(monotone.abs() < 0.5 / size as f64 + 0.02);
+            assert!(ace_high.abs() < 0.05);
+        }
+
+        assert_eq!(flop_subset(1755, Deck::Standard).unwrap(), all);
+        assert!(flop_subset(5, Deck::Standard).is_err());
+        assert!(flop_subset(1756, Deck::Standard).is_err());
+    }
+
+    #[test]
+    fn test_flop_subset_short_deck() {
+        let deck = Deck::ShortDeck;
+        let in_deck = |f: &WeightedFlop| f.flop.iter().all(|&card| deck.contains(card));
+        let all = weighted_canonical_flops()
+            .into_iter()
+            .filter(in_deck)
+            .collect::<Vec<_>>();
+        assert_eq!(all.len(), 573);
+
+        for size in [25, 49, 95] {
+            let subset = flop_subset(size, deck).unwrap();
+            assert_eq!(subset.len(), size);
+            assert!(subset.iter().all(in_deck));
+            assert_eq!(subset.iter().map(|f| f.weight).sum::<f64>(), 7140.0);
+        }
+
+        assert_eq!(flop_subset(573, deck).unwrap(), all);
+        assert!(flop_subset(574, deck).is_err());
+    }
+}
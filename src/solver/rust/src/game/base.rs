@@ -52,10 +52,11 @@ impl Game for PostFlopGame {
 
     #[inline]
     fn chance_factor(&self, node: &Self::Node) -> usize {
+        let num_cards = self.card_config.deck.num_cards();
         if node.turn == NOT_DEALT {
-            45 - self.bunching_num_dead_cards
+            num_cards - 7 - self.bunching_num_dead_cards
         } else {
-            44 - self.bunching_num_dead_cards
+            num_cards - 8 - self.bunching_num_dead_cards
         }
     }
 
@@ -71,6 +72,16 @@ impl Game for PostFlopGame {
         self.apply_history(&history);
     }
 
+    #[inline]
+    fn num_iterations(&self) -> u32 {
+        self.num_iterations
+    }
+
+    #[inline]
+    fn set_num_iterations(&mut self, num_iterations: u32) {
+        self.num_iterations = num_iterations;
+    }
+
     #[inline]
     fn is_ready(&self) -> bool {
         self.state == State::MemoryAllocated && self.storage_mode == BoardState::River
@@ -78,7 +89,9 @@ impl Game for PostFlopGame {
 
     #[inline]
     fn is_raked(&self) -> bool {
+        // tournament payoffs are not zero-sum either
         self.tree_config.rake_rate > 0.0 && self.tree_config.rake_cap > 0.0
+            || self.tournament_payoff.is_some()
     }
 
     #[inline]
@@ -114,6 +127,36 @@ impl Game for PostFlopGame {
     fn is_compression_enabled(&self) -> bool {
         self.is_compression_enabled
     }
+
+    #[inline]
+    fn resolve_gadget(&self) -> Option<&ResolveGadget> {
+        self.resolve_gadget.as_ref()
+    }
+
+    fn allocate_predictions(&mut self) {
+        if !self.is_ready() {
+            panic!("Game is not ready");
+        }
+
+        let storage_bytes = (4 * self.num_storage) as usize;
+        if self.storage_prediction.len() == storage_bytes {
+            return;
+        }
+
+        self.storage_prediction = vec![0; storage_bytes];
+
+        let mut counter = 0;
+        for node in &self.node_arena {
+            let mut node = node.lock();
+            if !node.is_terminal() && !node.is_chance() {
+                unsafe {
+                    let ptr = self.storage_prediction.as_mut_ptr();
+                    node.storage4 = ptr.add(counter);
+                }
+                counter += 4 * node.num_elements as usize;
+            }
+        }
+    }
 }
 
 impl PostFlopGame {
@@ -152,6 +195,7 @@ impl PostFlopGame {
         }
 
         self.card_config = card_config;
+        self.resolve_gadget = None;
         (
             self.tree_config,
             self.added_lines,
@@ -167,6 +211,7 @@ impl PostFlopGame {
 
         self.init_interpreter();
         self.reset_bunching_effect();
+        self.reset_tournament_payoff();
 
         Ok(())
     }
@@ -187,6 +232,10 @@ impl PostFlopGame {
             return Err("Bunching configuration is not ready".to_string());
         }
 
+        if self.card_config.deck != Deck::Standard {
+            return Err("Bunching effect is only supported with the standard deck".to_string());
+        }
+
         let mut flop_sorted = self.card_config.flop;
         flop_sorted.sort_unstable();
         if flop_sorted != bunching_data.flop() {
@@ -214,6 +263,39 @@ impl PostFlopGame {
         self.back_to_root();
     }
 
+    /// Sets the tournament payoff model.
+    ///
+    /// Terminal payoffs become the tournament equity (and bounties) of the final stacks instead
+    /// of chips. The game is no longer zero-sum, so the exploitability is computed as in raked
+    /// games, and [`expected_values`] returns the tournament equity at the end of the hand.
+    ///
+    /// This method must be called before allocating memory.
+    ///
+    /// [`expected_values`]: #method.expected_values
+    pub fn set_tournament_payoff(&mut self, payoff: TournamentPayoff) -> Result<(), String> {
+        if self.state <= State::Uninitialized {
+            return Err("Game is not successfully initialized".to_string());
+        } else if self.state >= State::MemoryAllocated {
+            return Err("Game has already been allocated".to_string());
+        }
+
+        self.set_tournament_payoff_internal(payoff)
+    }
+
+    /// Resets the tournament payoff model, i.e., the terminal payoffs become chips again.
+    #[inline]
+    pub fn reset_tournament_payoff(&mut self) {
+        self.tournament_payoff = None;
+        self.payoff_table = BTreeMap::new();
+        self.payoff_bias = [0.0; 2];
+    }
+
+    /// Obtains the tournament payoff model.
+    #[inline]
+    pub fn tournament_payoff(&self) -> Option<&TournamentPayoff> {
+        self.tournament_payoff.as_ref()
+    }
+
     /// Obtains the card configuration.
     #[inline]
     pub fn card_config(&self) -> &CardConfig {
@@ -268,6 +350,16 @@ impl PostFlopGame {
         (uncompressed, compressed)
     }
 
+    /// Returns the additional memory usage in bytes when a predictive CFR variant is used.
+    #[inline]
+    pub fn memory_usage_predictions(&self) -> u64 {
+        if self.state <= State::Uninitialized {
+            panic!("Game is not successfully initialized");
+        }
+
+        4 * self.num_storage
+    }
+
     /// Returns the estimated additional memory usage in bytes when the bunching effect is enabled.
     #[inline]
     pub fn memory_usage_bunching(&self) -> u64 {
@@ -356,63 +448,21 @@ impl PostFlopGame {
     /// Checks the card configuration.
     pub(crate) fn check_card_config(&mut self) -> Result<(), String> {
         let config = &self.card_config;
-        let (flop, turn, river) = (config.flop, config.turn, config.river);
         let range = &config.range;
+        let expected_state = check_board_cards(config.flop, config.turn, config.river)?;
 
-        if flop.contains(&NOT_DEALT) {
-            return Err("Flop cards not initialized".to_string());
-        }
-
-        if flop.iter().any(|&c| 52 <= c) {
-            return Err(format!("Flop cards must be in [0, 52): flop = {flop:?}"));
-        }
-
-        if flop[0] == flop[1] || flop[0] == flop[2] || flop[1] == flop[2] {
-            return Err(format!("Flop cards must be unique: flop = {flop:?}"));
-        }
-
-        if turn != NOT_DEALT {
-            if 52 <= turn {
-                return Err(format!("Turn card must be in [0, 52): turn = {turn}"));
-            }
-
-            if flop.contains(&turn) {
-                return Err(format!(
-                    "Turn card must be different from flop cards: turn = {turn}"
-                ));
-            }
-        }
-
-        if river != NOT_DEALT {
-            if 52 <= river {
-                return Err(format!("River card must be in [0, 52): river = {river}"));
-            }
-
-            if flop.contains(&river) {
-                return Err(format!(
-                    "River card must be different from flop cards: river = {river}"
-                ));
-            }
-
-            if turn == river {
-                return Err(format!(
-                    "River card must be different from turn card: river = {river}"
-                ));
-            }
-
-            if turn == NOT_DEALT {
-                return Err(format!(
-                    "River card specified without turn card: river = {river}"
-                ));
-            }
+        let [flop0, flop1, flop2] = config.flop;
+        let board = [flop0, flop1, flop2, config.turn, config.river];
+        if let Some(&card) = board
+            .iter()
+            .find(|&&c| c != NOT_DEALT && !config.deck.contains(c))
+        {
+            return Err(format!(
+                "Board card is not in the deck: card = {card}, deck = {:?}",
+                config.deck
+            ));
         }
 
-        let expected_state = match (turn != NOT_DEALT, river != NOT_DEALT) {
-            (false, _) => BoardState::Flop,
-            (true, false) => BoardState::Turn,
-            (true, true) => BoardState::River,
-        };
-
         if self.tree_config.initial_state != expected_state {
             return Err(format!(
                 "Invalid initial state of `tree_config`: expected = {:?}, actual = {:?}",
@@ -477,13 +527,31 @@ impl PostFlopGame {
             board_mask |= 1 << river;
         }
 
+        // hands containing cards outside the deck are removed as well
+        let dead_mask = board_mask | config.deck.removed_mask();
+
         for player in 0..2 {
-            let (hands, weights) = range[player].get_hands_weights(board_mask);
+            let (hands, weights) = range[player].get_hands_weights(dead_mask);
             self.initial_weights[player] = weights;
             self.private_cards[player] = hands;
         }
     }
 
+    /// Checks the card configuration against the tree configuration and returns the private hands
+    /// of both players without building the game tree.
+    pub(crate) fn private_cards_of(
+        card_config: &CardConfig,
+        tree_config: &TreeConfig,
+    ) -> Result<[Vec<(Card, Card)>; 2], String> {
+        let mut game = Self {
+            card_config: card_config.clone(),
+            tree_config: tree_config.clone(),
+            ..Default::default()
+        };
+        game.check_card_config()?;
+        Ok(game.private_cards)
+    }
+
     /// Initializes fields related to cards.
     pub(super) fn init_card_fields(&mut self) {
         for player in 0..2 {
@@ -582,28 +650,20 @@ impl PostFlopGame {
         self.storage2 = Vec::new();
         self.storage_ip = Vec::new();
         self.storage_chance = Vec::new();
+        self.storage_prediction = Vec::new();
+        self.num_iterations = 0;
+
+        if let Some(gadget) = &self.resolve_gadget {
+            gadget.reset();
+        }
     }
 
     /// Counts the number of nodes in the game tree.
     #[inline]
     fn count_num_nodes(&self) -> [u64; 3] {
-        let (turn_coef, river_coef) = match (self.card_config.turn, self.card_config.river) {
-            (NOT_DEALT, _) => {
-                let mut river_coef = 0;
-                let flop = self.card_config.flop;
-                let skip_cards = &self.isomorphism_card_turn;
-                let flop_mask: u64 = (1 << flop[0]) | (1 << flop[1]) | (1 << flop[2]);
-                let skip_mask: u64 = skip_cards.iter().map(|&card| 1 << card).sum();
-                for turn in 0..52 {
-                    if (1 << turn) & (flop_mask | skip_mask) == 0 {
-                        river_coef += 48 - self.isomorphism_card_river[turn & 3].len();
-                    }
-                }
-                (49 - self.isomorphism_card_turn.len(), river_coef)
-            }
-            (turn, NOT_DEALT) => (1, 48 - self.isomorphism_card_river[turn as usize & 3].len()),
-            _ => (0, 1),
-        };
+        let (turn_coef, river_coef) = self
+            .card_config
+            .chance_coefficients(&self.isomorphism_card_turn, &self.isomorphism_card_river);
 
         let num_action_nodes = count_num_action_nodes(&self.action_root.lock());
 
@@ -713,6 +773,7 @@ impl PostFlopGame {
         let mut node = self.node_arena[node_index].lock();
         let flop = self.card_config.flop;
         let flop_mask: u64 = (1 << flop[0]) | (1 << flop[1]) | (1 << flop[2]);
+        let removed_mask = self.card_config.deck.removed_mask();
 
         // deal turn
         if node.turn == NOT_DEALT {
@@ -721,7 +782,7 @@ impl PostFlopGame {
 
             node.children_offset = (info.turn_index - node_index) as u32;
             for card in 0..52 {
-                if (1 << card) & (flop_mask | skip_mask) == 0 {
+                if (1 << card) & (flop_mask | skip_mask | removed_mask) == 0 {
                     node.num_children += 1;
                     let mut child = node.children().last().unwrap().lock();
                     child.prev_action = Action::Chance(card);
@@ -739,7 +800,7 @@ impl PostFlopGame {
 
             node.children_offset = (info.river_index - node_index) as u32;
             for card in 0..52 {
-                if (1 << card) & (turn_mask | skip_mask) == 0 {
+                if (1 << card) & (turn_mask | skip_mask | removed_mask) == 0 {
                     node.num_children += 1;
                     let mut child = node.children().last().unwrap().lock();
                     child.prev_action = Action::Chance(card);
@@ -1185,6 +1246,83 @@ impl PostFlopGame {
         Ok(())
     }
 
+    /// Sets the tournament payoff model and builds the payoff table.
+    pub(crate) fn set_tournament_payoff_internal(
+        &mut self,
+        payoff: TournamentPayoff,
+    ) -> Result<(), String> {
+        let effective_stack = self.tree_config.effective_stack as f64;
+        if payoff.stacks.iter().any(|&stack| stack < effective_stack) {
+            return Err(format!(
+                "Stacks must be greater than or equal to the effective stack: {:?}",
+                payoff.stacks
+            ));
+        }
+
+        if payoff.payouts.is_empty() {
+            return Err("Payouts must not be empty".to_string());
+        }
+
+        if payoff
+            .bounties
+            .iter()
+            .any(|&bounty| !bounty.is_finite() || bounty < 0.0)
+        {
+            return Err(format!(
+                "Bounties must be non-negative: {:?}",
+                payoff.bounties
+            ));
+        }
+
+        let starting_pot = self.tree_config.starting_pot as f64;
+        let [oop_stack, ip_stack] = payoff.stacks;
+        let bias = payoff.values([
+            oop_stack + 0.5 * starting_pot,
+            ip_stack + 0.5 * starting_pot,
+        ])?;
+
+        let mut amounts = self
+            .node_arena
+            .iter()
+            .filter_map(|node| {
+                let node = node.lock();
+                node.is_terminal().then_some(node.amount)
+            })
+            .collect::<Vec<_>>();
+        amounts.sort_unstable();
+        amounts.dedup();
+
+        let mut payoff_table = BTreeMap::new();
+        for amount in amounts {
+            let pot = starting_pot + 2.0 * amount as f64;
+            let rake = pot * self.tree_config.rake_rate;
+            let rake = rake.min(self.tree_config.rake_cap);
+            let stacks = payoff.stacks.map(|stack| stack - amount as f64);
+
+            let mut payoffs = [[0.0; 3]; 2];
+            for winner in 0..2 {
+                let mut final_stacks = stacks;
+                final_stacks[winner] += pot - rake;
+                let values = payoff.values(final_stacks)?;
+                payoffs[winner][0] = values[winner] - bias[winner];
+                payoffs[winner ^ 1][1] = values[winner ^ 1] - bias[winner ^ 1];
+            }
+
+            let tie_stacks = stacks.map(|stack| stack + 0.5 * (pot - rake));
+            let values = payoff.values(tie_stacks)?;
+            payoffs[0][2] = values[0] - bias[0];
+            payoffs[1][2] = values[1] - bias[1];
+
+            payoff_table.insert(amount, payoffs);
+        }
+
+        self.tournament_payoff = Some(payoff);
+        self.payoff_table = payoff_table;
+        self.payoff_bias = bias;
+
+        Ok(())
+    }
+
     /// Sets the bunching effect.
     fn memory_usage_bunching_internal(&self) -> u64 {
         let mut ret = 4;
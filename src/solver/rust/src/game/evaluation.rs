@@ -12,6 +12,21 @@ fn min(x: f64, y: f64) -> f64 {
 }
 
 impl PostFlopGame {
+    /// Returns the payoffs of `player` when the player wins, loses, and ties at the terminal
+    /// `node`, relative to the half of the starting pot (or `payoff_bias` with the tournament
+    /// payoff model).
+    #[inline]
+    fn terminal_payoffs(&self, node: &PostFlopNode, player: usize) -> [f64; 3] {
+        if self.tournament_payoff.is_some() {
+            return self.payoff_table[&node.amount][player];
+        }
+
+        let pot = (self.tree_config.starting_pot + 2 * node.amount) as f64;
+        let half_pot = 0.5 * pot;
+        let rake = min(pot * self.tree_config.rake_rate, self.tree_config.rake_cap);
+        [half_pot - rake, -half_pot, -0.5 * rake]
+    }
+
     pub(super) fn evaluate_internal(
         &self,
         result: &mut [MaybeUninit<f32>],
@@ -19,11 +34,9 @@ impl PostFlopGame {
         player: usize,
         cfreach: &[f32],
     ) {
-        let pot = (self.tree_config.starting_pot + 2 * node.amount) as f64;
-        let half_pot = 0.5 * pot;
-        let rake = min(pot * self.tree_config.rake_rate, self.tree_config.rake_cap);
-        let amount_win = (half_pot - rake) / self.num_combinations;
-        let amount_lose = -half_pot / self.num_combinations;
+        let [payoff_win, payoff_lose, payoff_tie] = self.terminal_payoffs(node, player);
+        let amount_win = payoff_win / self.num_combinations;
+        let amount_lose = payoff_lose / self.num_combinations;
 
         let player_cards = &self.private_cards[player];
         let opponent_cards = &self.private_cards[player ^ 1];
@@ -92,7 +105,7 @@ impl PostFlopGame {
             }
         }
         // showdown (optimized for no rake; 2-pass)
-        else if rake == 0.0 {
+        else if payoff_tie == 0.0 {
             let pair_index = card_pair_to_index(node.turn, node.river);
             let hand_strength = &self.hand_strength[pair_index];
             let player_strength = &hand_strength[player];
@@ -151,7 +164,7 @@ impl PostFlopGame {
         }
         // showdown (raked; 3-pass)
         else {
-            let amount_tie = -0.5 * rake / self.num_combinations;
+            let amount_tie = payoff_tie / self.num_combinations;
             let same_hand_index = &self.same_hand_index[player];
 
             let pair_index = card_pair_to_index(node.turn, node.river);
@@ -259,12 +272,10 @@ impl PostFlopGame {
         player: usize,
         cfreach: &[f32],
     ) {
-        let pot = (self.tree_config.starting_pot + 2 * node.amount) as f64;
-        let half_pot = 0.5 * pot;
-        let rake = min(pot * self.tree_config.rake_rate, self.tree_config.rake_cap);
-        let amount_win = ((half_pot - rake) / self.bunching_num_combinations) as f32;
-        let amount_lose = (-half_pot / self.bunching_num_combinations) as f32;
-        let amount_tie = (-0.5 * rake / self.bunching_num_combinations) as f32;
+        let [payoff_win, payoff_lose, payoff_tie] = self.terminal_payoffs(node, player);
+        let amount_win = (payoff_win / self.bunching_num_combinations) as f32;
+        let amount_lose = (payoff_lose / self.bunching_num_combinations) as f32;
+        let amount_tie = (payoff_tie / self.bunching_num_combinations) as f32;
         let opponent_len = self.private_cards[player ^ 1].len();
 
         // someone folded
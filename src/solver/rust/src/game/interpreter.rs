@@ -133,6 +133,7 @@ impl PostFlopGame {
 
         let flop = self.card_config.flop;
         let mut board_mask: u64 = (1 << flop[0]) | (1 << flop[1]) | (1 << flop[2]);
+        board_mask |= self.card_config.deck.removed_mask();
         let mut dead_mask: u64 = 0;
 
         // no bunching
@@ -398,21 +399,7 @@ impl PostFlopGame {
             self.cfvalues_cache[player].copy_from_slice(&vec);
 
             // update the bet amounts
-            let node = self.node();
-            match node.play(action).prev_action {
-                Action::Call => {
-                    self.total_bet_amount[player] = self.total_bet_amount[player ^ 1];
-                }
-                Action::Bet(amount) | Action::Raise(amount) | Action::AllIn(amount) => {
-                    let prev_bet_amount = match node.prev_action {
-                        Action::Bet(a) | Action::Raise(a) | Action::AllIn(a) => a,
-                        _ => 0,
-                    };
-                    let to_call = self.total_bet_amount[player ^ 1] - self.total_bet_amount[player];
-                    self.total_bet_amount[player] += amount - prev_bet_amount + to_call;
-                }
-                _ => {}
-            }
+            self.total_bet_amount = self.total_bet_amount_after(action);
 
             // update the node
             let node_index = self.node_index(&self.node().play(action));
@@ -612,18 +599,21 @@ impl PostFlopGame {
 
         let tmp = if self.bunching_num_dead_cards == 0 {
             let mut tmp = vec![0.0; num_hands];
+            let num_cards = self.card_config.deck.num_cards() as f64;
             if self.river != NOT_DEALT {
                 self.equity_internal(&mut tmp, player, self.turn, self.river, 0.5);
             } else if self.turn != NOT_DEALT {
+                let amount = 0.5 / (num_cards - 8.0);
                 for river in 0..52 {
                     if self.turn != river {
-                        self.equity_internal(&mut tmp, player, self.turn, river, 0.5 / 44.0);
+                        self.equity_internal(&mut tmp, player, self.turn, river, amount);
                     }
                 }
             } else {
+                let amount = 1.0 / ((num_cards - 7.0) * (num_cards - 8.0));
                 for turn in 0..52 {
                     for river in turn + 1..52 {
-                        self.equity_internal(&mut tmp, player, turn, river, 1.0 / (45.0 * 44.0));
+                        self.equity_internal(&mut tmp, player, turn, river, amount);
                     }
                 }
             }
@@ -649,6 +639,9 @@ impl PostFlopGame {
 
     /// Returns the expected values of each private hand of the given player.
     ///
+    /// If the tournament payoff model is set, the expected value is the tournament equity at the
+    /// end of the hand (see [`set_tournament_payoff`]).
+    ///
     /// Panics if the game is not solved.
     ///
     /// After mutating the current node, you must call the [`cache_normalized_weights`] method
@@ -656,6 +649,7 @@ impl PostFlopGame {
     ///
     /// **Time complexity:** see [`expected_values_detail`].
     ///
+    /// [`set_tournament_payoff`]: #method.set_tournament_payoff
     /// [`cache_normalized_weights`]: #method.cache_normalized_weights
     /// [`expected_values_detail`]: #method.expected_values_detail
     pub fn expected_values(&self, player: usize) -> Vec<f32> {
@@ -722,12 +716,13 @@ impl PostFlopGame {
         let node = self.node();
         let num_hands = self.num_private_hands(player);
 
+        let num_cards = self.card_config.deck.num_cards();
         let mut chance_factor = 1;
         if self.card_config.turn == NOT_DEALT && self.turn != NOT_DEALT {
-            chance_factor *= 45 - self.bunching_num_dead_cards;
+            chance_factor *= num_cards - 7 - self.bunching_num_dead_cards;
         }
         if self.card_config.river == NOT_DEALT && self.river != NOT_DEALT {
-            chance_factor *= 44 - self.bunching_num_dead_cards;
+            chance_factor *= num_cards - 8 - self.bunching_num_dead_cards;
         }
 
         let num_combinations = match self.bunching_num_dead_cards {
@@ -775,9 +770,15 @@ impl PostFlopGame {
             self.cfvalues_cache[player].to_vec()
         };
 
-        let starting_pot = self.tree_config.starting_pot;
         let total_bet_amount = self.total_bet_amount();
-        let bias = (total_bet_amount[player] - total_bet_amount[player ^ 1]).max(0);
+        let bias = match self.tournament_payoff {
+            Some(_) => self.payoff_bias[player] as f32,
+            None => {
+                let excess = (total_bet_amount[player] - total_bet_amount[player ^ 1]).max(0);
+                self.tree_config.starting_pot as f32 * 0.5 + (self.node().amount + excess) as f32
+            }
+        };
+        let has_fold_value = self.tournament_payoff.is_some();
 
         ret.chunks_exact_mut(num_hands)
             .enumerate()
@@ -788,11 +789,11 @@ impl PostFlopGame {
                     .zip(self.weights[player].iter())
                     .zip(self.normalized_weights[player].iter())
                     .for_each(|((v, &w_raw), &w_normalized)| {
-                        if is_fold || w_normalized == 0.0 {
+                        if (is_fold && !has_fold_value) || w_normalized == 0.0 {
                             *v = 0.0;
                         } else {
                             *v *= normalizer * (w_raw / w_normalized);
-                            *v += starting_pot as f32 * 0.5 + (self.node().amount + bias) as f32;
+                            *v += bias;
                         }
                     });
             });
@@ -852,6 +853,48 @@ impl PostFlopGame {
         self.total_bet_amount
     }
 
+    /// Returns the total bet amount of each player (OOP, IP) after playing the given action at
+    /// the current node.
+    ///
+    /// The `action` argument is an index into [`available_actions`] of the current player node.
+    ///
+    /// [`available_actions`]: #method.available_actions
+    #[inline]
+    pub fn total_bet_amount_after(&self, action: usize) -> [i32; 2] {
+        if self.state <= State::Uninitialized {
+            panic!("Game is not successfully initialized");
+        }
+
+        if self.is_terminal_node() || self.is_chance_node() {
+            panic!("Current node is not a player node");
+        }
+
+        let node = self.node();
+        if action >= node.num_actions() {
+            panic!("Invalid action");
+        }
+
+        let player = node.player();
+        let mut ret = self.total_bet_amount;
+
+        match node.play(action).prev_action {
+            Action::Call => {
+                ret[player] = ret[player ^ 1];
+            }
+            Action::Bet(amount) | Action::Raise(amount) | Action::AllIn(amount) => {
+                let prev_bet_amount = match node.prev_action {
+                    Action::Bet(a) | Action::Raise(a) | Action::AllIn(a) => a,
+                    _ => 0,
+                };
+                let to_call = ret[player ^ 1] - ret[player];
+                ret[player] += amount - prev_bet_amount + to_call;
+            }
+            _ => {}
+        }
+
+        ret
+    }
+
     /// Locks the strategy of the current node.
     ///
     /// The `strategy` argument must be a slice of the length of `#(actions) * #(private hands)`.
@@ -992,9 +1035,66 @@ impl PostFlopGame {
         })
     }
 
+    /// Returns the histories of all locked nodes.
+    ///
+    /// Each history can be passed to the [`apply_history`] method to move to the locked node.
+    /// Chance actions are represented by the dealt card as in the [`history`] method.
+    ///
+    /// [`apply_history`]: #method.apply_history
+    /// [`history`]: #method.history
+    pub fn locked_node_histories(&self) -> Vec<Vec<usize>> {
+        if self.state < State::MemoryAllocated {
+            panic!("Memory is not allocated");
+        }
+
+        let mut ret = Vec::new();
+        if !self.locking_strategy.is_empty() {
+            let root = self.node_arena[0].lock();
+            self.locked_node_histories_recursive(&root, &mut Vec::new(), &mut ret);
+        }
+
+        ret
+    }
+
+    /// Recursively collects the histories of locked nodes.
+    fn locked_node_histories_recursive(
+        &self,
+        node: &PostFlopNode,
+        history: &mut Vec<usize>,
+        result: &mut Vec<Vec<usize>>,
+    ) {
+        if node.is_terminal() {
+            return;
+        }
+
+        if node.is_chance() {
+            // the subtree is not stored in this storage mode
+            let is_turn = node.turn == NOT_DEALT;
+            if self.storage_mode == BoardState::Flop
+                || (!is_turn && self.storage_mode == BoardState::Turn)
+            {
+                return;
+            }
+        }
+
+        if node.is_locked {
+            result.push(history.clone());
+        }
+
+        for action in 0..node.num_actions() {
+            let child = node.play(action);
+            history.push(match child.prev_action {
+                Action::Chance(card) => card as usize,
+                _ => action,
+            });
+            self.locked_node_histories_recursive(&child, history, result);
+            history.pop();
+        }
+    }
+
     /// Returns the reference to the current node.
     #[inline]
-    fn node(&self) -> MutexGuardLike<PostFlopNode> {
+    pub(super) fn node(&self) -> MutexGuardLike<PostFlopNode> {
         self.node_arena[self.node_history.last().cloned().unwrap_or(0)].lock()
     }
 
@@ -2,6 +2,7 @@ mod base;
 mod evaluation;
 mod interpreter;
 mod node;
+mod subgame;
 
 #[cfg(feature = "bincode")]
 mod serialization;
@@ -11,7 +12,9 @@ mod tests;
 
 use crate::action_tree::*;
 use crate::card::*;
+use crate::icm::*;
 use crate::mutex_like::*;
+use crate::solver::ResolveGadget;
 use std::collections::BTreeMap;
 
 #[cfg(feature = "bincode")]
@@ -79,6 +82,13 @@ pub struct PostFlopGame {
     bunching_coef_flop: [Vec<usize>; 2],
     bunching_coef_turn: [Vec<Vec<usize>>; 2],
 
+    // tournament payoff
+    // - `payoff_table`: values of win, lose, and tie of each player, indexed by the bet amount
+    // - `payoff_bias`: values of each player when the starting pot is split without rake
+    tournament_payoff: Option<TournamentPayoff>,
+    payoff_table: BTreeMap<i32, [[f64; 3]; 2]>,
+    payoff_bias: [f64; 2],
+
     // store options
     storage_mode: BoardState,
     target_storage_mode: BoardState,
@@ -88,6 +98,7 @@ pub struct PostFlopGame {
     num_storage_ip: u64,
     num_storage_chance: u64,
     misc_memory_usage: u64,
+    num_iterations: u32,
 
     // global storage
     // `storage*` are used as a global storage and are referenced by `PostFlopNode::storage*`.
@@ -97,7 +108,9 @@ pub struct PostFlopGame {
     storage2: Vec<u8>,
     storage_ip: Vec<u8>,
     storage_chance: Vec<u8>,
+    storage_prediction: Vec<u8>,
     locking_strategy: BTreeMap<usize, Vec<f32>>,
+    resolve_gadget: Option<ResolveGadget>,
 
     // result interpreter
     action_history: Vec<usize>,
@@ -136,6 +149,7 @@ pub struct PostFlopNode {
     storage1: *mut u8, // strategy
     storage2: *mut u8, // regrets or cfvalues
     storage3: *mut u8, // IP cfvalues
+    storage4: *mut u8, // previous instantaneous regrets (predictive CFR)
 }
 
 unsafe impl Send for PostFlopNode {}
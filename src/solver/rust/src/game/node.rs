@@ -96,6 +96,16 @@ impl GameNode for PostFlopNode {
         unsafe { slice::from_raw_parts_mut(self.storage1 as *mut f32, self.num_elements as usize) }
     }
 
+    #[inline]
+    fn predictions(&self) -> &[f32] {
+        unsafe { slice::from_raw_parts(self.storage4 as *const f32, self.num_elements as usize) }
+    }
+
+    #[inline]
+    fn predictions_mut(&mut self) -> &mut [f32] {
+        unsafe { slice::from_raw_parts_mut(self.storage4 as *mut f32, self.num_elements as usize) }
+    }
+
     #[inline]
     fn strategy_compressed(&self) -> &[u16] {
         unsafe { slice::from_raw_parts(self.storage1 as *const u16, self.num_elements as usize) }
@@ -220,6 +230,7 @@ impl Default for PostFlopNode {
             storage1: ptr::null_mut(),
             storage2: ptr::null_mut(),
             storage3: ptr::null_mut(),
+            storage4: ptr::null_mut(),
             num_elements: 0,
             scale1: 0.0,
             scale2: 0.0,
@@ -81,7 +81,13 @@ impl PostFlopGame {
         let num_bytes = if self.is_compression_enabled { 2 } else { 4 };
         if self.target_storage_mode == BoardState::River {
             // omit storing the counterfactual values
-            return [num_bytes * self.num_storage as usize, 0, 0, 0];
+            let num_storage = num_bytes * self.num_storage as usize;
+            if self.state == State::Solved {
+                return [num_storage, 0, 0, 0];
+            }
+
+            // keep the cumulative regrets so that the solving can be resumed
+            return [num_storage, num_storage, 0, 0];
         }
 
         let mut node_index = match self.target_storage_mode {
@@ -114,7 +120,16 @@ impl PostFlopGame {
     }
 }
 
-static VERSION_STR: &str = "2023-03-19";
+static VERSION_STR: &str = "2026-10-17.2";
+
+// files of this version are loaded without the tournament payoff
+static NO_PAYOFF_VERSION_STR: &str = "2026-10-17.1";
+
+// files of this version are loaded with the standard deck
+static NO_DECK_VERSION_STR: &str = "2026-10-17";
+
+// files of this version are loaded with the iteration count set to zero
+static LEGACY_VERSION_STR: &str = "2023-03-19";
 
 thread_local! {
     static PTR_BASE: Cell<[*const u8; 2]> = Cell::new([ptr::null(); 2]);
@@ -148,6 +163,8 @@ impl Encode for PostFlopGame {
         self.num_storage_ip.encode(encoder)?;
         self.num_storage_chance.encode(encoder)?;
         self.misc_memory_usage.encode(encoder)?;
+        self.num_iterations.encode(encoder)?;
+        self.tournament_payoff.encode(encoder)?;
         self.storage1[0..num_storage[0]].encode(encoder)?;
         self.storage2[0..num_storage[1]].encode(encoder)?;
         self.storage_ip[0..num_storage[2]].encode(encoder)?;
@@ -192,7 +209,10 @@ impl Decode for PostFlopGame {
     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
         // version check
         let version = String::decode(decoder)?;
-        if version != VERSION_STR {
+        let is_legacy = version == LEGACY_VERSION_STR;
+        let has_payoff = version == VERSION_STR;
+        let has_deck = has_payoff || version == NO_PAYOFF_VERSION_STR;
+        if !has_deck && version != NO_DECK_VERSION_STR && !is_legacy {
             return Err(DecodeError::OtherString(format!(
                 "Version mismatch: expected '{VERSION_STR}', but got '{version}'"
             )));
@@ -201,7 +221,16 @@ impl Decode for PostFlopGame {
         // game instance
         let mut game = Self {
             state: Decode::decode(decoder)?,
-            card_config: Decode::decode(decoder)?,
+            card_config: match has_deck {
+                true => Decode::decode(decoder)?,
+                false => CardConfig {
+                    range: Decode::decode(decoder)?,
+                    flop: Decode::decode(decoder)?,
+                    turn: Decode::decode(decoder)?,
+                    river: Decode::decode(decoder)?,
+                    deck: Deck::Standard,
+                },
+            },
             tree_config: Decode::decode(decoder)?,
             added_lines: Decode::decode(decoder)?,
             removed_lines: Decode::decode(decoder)?,
@@ -213,6 +242,14 @@ impl Decode for PostFlopGame {
             num_storage_ip: Decode::decode(decoder)?,
             num_storage_chance: Decode::decode(decoder)?,
             misc_memory_usage: Decode::decode(decoder)?,
+            num_iterations: match is_legacy {
+                false => Decode::decode(decoder)?,
+                true => 0,
+            },
+            tournament_payoff: match has_payoff {
+                true => Decode::decode(decoder)?,
+                false => None,
+            },
             storage1: Decode::decode(decoder)?,
             storage2: Decode::decode(decoder)?,
             storage_ip: Decode::decode(decoder)?,
@@ -224,7 +261,9 @@ impl Decode for PostFlopGame {
         game.target_storage_mode = game.storage_mode;
         if game.storage_mode == BoardState::River && game.state >= State::MemoryAllocated {
             let num_bytes = if game.is_compression_enabled { 2 } else { 4 };
-            game.storage2 = vec![0; (num_bytes * game.num_storage) as usize];
+            if game.state == State::Solved {
+                game.storage2 = vec![0; (num_bytes * game.num_storage) as usize];
+            }
             game.storage_ip = vec![0; (num_bytes * game.num_storage_ip) as usize];
             game.storage_chance = vec![0; (num_bytes * game.num_storage_chance) as usize];
         }
@@ -259,6 +298,12 @@ impl Decode for PostFlopGame {
         game.init_interpreter();
         game.back_to_root();
 
+        // rebuild the payoff table
+        if let Some(payoff) = game.tournament_payoff.take() {
+            game.set_tournament_payoff_internal(payoff)
+                .map_err(DecodeError::OtherString)?;
+        }
+
         // restore the counterfactual values
         if game.storage_mode == BoardState::River && game.state == State::Solved {
             game.state = State::MemoryAllocated;
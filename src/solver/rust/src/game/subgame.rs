@@ -0,0 +1,150 @@
+use super::*;
+use crate::range::*;
+use crate::solver::ResolveGadget;
+
+impl PostFlopGame {
+    /// Returns the tree configuration of the subgame rooted at the current node.
+    ///
+    /// The current node must be the root node or the first player node of the turn or the river.
+    /// The starting pot and the effective stack are computed from [`total_bet_amount`], and the
+    /// other options are copied from the tree configuration of this game. Bet sizes can be changed
+    /// before passing the configuration to [`subgame`].
+    ///
+    /// [`total_bet_amount`]: #method.total_bet_amount
+    /// [`subgame`]: #method.subgame
+    pub fn subgame_tree_config(&self) -> Result<TreeConfig, String> {
+        if self.state <= State::Uninitialized {
+            return Err("Game is not successfully initialized".to_string());
+        }
+
+        if self.is_terminal_node() || self.is_chance_node() {
+            return Err("Current node is not a player node".to_string());
+        }
+
+        let is_street_root =
+            self.node_history.is_empty() || matches!(self.node().prev_action, Action::Chance(_));
+        if !is_street_root {
+            return Err("Subgame must start at the beginning of a street".to_string());
+        }
+
+        let initial_state = if self.river != NOT_DEALT {
+            BoardState::River
+        } else if self.turn != NOT_DEALT {
+            BoardState::Turn
+        } else {
+            BoardState::Flop
+        };
+
+        let amount = self.total_bet_amount[0];
+        Ok(TreeConfig {
+            initial_state,
+            starting_pot: self.tree_config.starting_pot + 2 * amount,
+            effective_stack: self.tree_config.effective_stack - amount,
+            ..self.tree_config.clone()
+        })
+    }
+
+    /// Builds a new game rooted at the current node with the given action tree.
+    ///
+    /// The ranges of the new game are the reaching ranges returned by [`weights`], and the board
+    /// is the current board. The initial state, the starting pot, the effective stack, and the
+    /// rake of `action_tree` must match [`subgame_tree_config`]. The tournament payoff model is
+    /// carried over with the stacks reduced by the bets so far.
+    ///
+    /// If `gadget_player` is specified, the new game is re-solved safely: at the root, each hand
+    /// of `gadget_player` may take its expected value in this game instead of entering the
+    /// subgame (see [`ResolveGadget`]). In this case, this game must be solved without the
+    /// bunching effect. The gadget is not saved by the serialization.
+    ///
+    /// [`weights`]: #method.weights
+    /// [`subgame_tree_config`]: #method.subgame_tree_config
+    pub fn subgame(
+        &mut self,
+        action_tree: ActionTree,
+        gadget_player: Option<usize>,
+    ) -> Result<Self, String> {
+        let expected = self.subgame_tree_config()?;
+        let config = action_tree.config();
+        if config.initial_state != expected.initial_state
+            || config.starting_pot != expected.starting_pot
+            || config.effective_stack != expected.effective_stack
+            || config.rake_rate != expected.rake_rate
+            || config.rake_cap != expected.rake_cap
+        {
+            return Err(format!(
+                "Tree configuration does not match the subgame: expected {:?}, pot = {}, \
+                 stack = {}, rake = {} (cap {})",
+                expected.initial_state,
+                expected.starting_pot,
+                expected.effective_stack,
+                expected.rake_rate,
+                expected.rake_cap
+            ));
+        }
+
+        if let Some(player) = gadget_player {
+            if player > 1 {
+                return Err(format!("Invalid gadget player: {player}"));
+            }
+            if self.state != State::Solved {
+                return Err("Game is not solved".to_string());
+            }
+            if self.bunching_num_dead_cards != 0 {
+                return Err("Safe re-solving does not support the bunching effect".to_string());
+            }
+        }
+
+        let range = [
+            Range::from_hands_weights(&self.private_cards[0], &self.weights[0])?,
+            Range::from_hands_weights(&self.private_cards[1], &self.weights[1])?,
+        ];
+
+        let card_config = CardConfig {
+            range,
+            flop: self.card_config.flop,
+            turn: self.turn,
+            river: self.river,
+            deck: self.card_config.deck,
+        };
+
+        let mut game = Self::with_config(card_config, action_tree)?;
+
+        if let Some(payoff) = &self.tournament_payoff {
+            let amount = self.total_bet_amount[0] as f64;
+            game.set_tournament_payoff(TournamentPayoff {
+                stacks: payoff.stacks.map(|stack| stack - amount),
+                ..payoff.clone()
+            })?;
+        }
+
+        if let Some(player) = gadget_player {
+            self.cache_normalized_weights();
+            let expected_values = self.expected_values(player);
+            let normalized_weights = &self.normalized_weights[player];
+            let bias = match self.tournament_payoff {
+                Some(_) => self.payoff_bias[player] as f32,
+                None => 0.5 * expected.starting_pot as f32,
+            };
+
+            // converts the expected values to the counterfactual values of the new game
+            let values = game.private_cards[player]
+                .iter()
+                .map(|hand| {
+                    let index = self.private_cards[player].binary_search(hand).unwrap();
+                    let (w_raw, w_normalized) =
+                        (self.weights[player][index], normalized_weights[index]);
+                    if w_normalized > 0.0 {
+                        let value = (expected_values[index] - bias) * (w_normalized / w_raw);
+                        (value as f64 / game.num_combinations) as f32
+                    } else {
+                        0.0
+                    }
+                })
+                .collect();
+
+            game.resolve_gadget = Some(ResolveGadget::new(player, values));
+        }
+
+        Ok(game)
+    }
+}
@@ -1,4 +1,5 @@
 use super::*;
+use crate::bet_size::*;
 use crate::range::*;
 use crate::solver::*;
 use crate::utility::*;
@@ -350,6 +351,7 @@ fn one_raise_all_range_with_river() {
         flop: flop_from_str("Td9d6h").unwrap(),
         turn: card_from_str("Qc").unwrap(),
         river: card_from_str("7s").unwrap(),
+        deck: Deck::Standard,
     };
 
     let tree_config = TreeConfig {
@@ -784,6 +786,359 @@ fn remove_lines() {
     solve(&mut game, 10, 0.01, false);
 }
 
+#[test]
+fn clone_action_tree() {
+    let card_config = CardConfig {
+        range: ["TT+,AKo,AQs+".parse().unwrap(), "AA".parse().unwrap()],
+        flop: flop_from_str("2c6dTh").unwrap(),
+        turn: card_from_str("Qc").unwrap(),
+        river: card_from_str("3s").unwrap(),
+        deck: Deck::Standard,
+    };
+
+    let tree_config = TreeConfig {
+        initial_state: BoardState::River,
+        starting_pot: 60,
+        effective_stack: 970,
+        river_bet_sizes: [("50%", "").try_into().unwrap(), Default::default()],
+        ..Default::default()
+    };
+
+    let mut action_tree = ActionTree::new(tree_config).unwrap();
+    action_tree.add_line(&[Action::Bet(120)]).unwrap();
+    action_tree.play(Action::Bet(120)).unwrap();
+
+    let cloned = action_tree.clone();
+    action_tree.remove_line(&[Action::Bet(30)]).unwrap();
+    assert_eq!(cloned.history(), &[Action::Bet(120)]);
+    assert_eq!(cloned.added_lines(), action_tree.added_lines());
+    assert!(cloned.removed_lines().is_empty());
+
+    let mut game = PostFlopGame::with_config(card_config, cloned).unwrap();
+    game.allocate_memory(false);
+    assert_eq!(
+        game.available_actions(),
+        vec![Action::Check, Action::Bet(30), Action::Bet(120)]
+    );
+}
+
+#[test]
+fn action_tree_stats() {
+    let tree_config = TreeConfig {
+        initial_state: BoardState::River,
+        starting_pot: 60,
+        effective_stack: 970,
+        river_bet_sizes: [("50%", "").try_into().unwrap(), Default::default()],
+        ..Default::default()
+    };
+
+    let mut action_tree = ActionTree::new(tree_config).unwrap();
+    let stats = action_tree.stats();
+    assert_eq!(stats.num_player_nodes, [0, 0, 3]);
+    assert_eq!(stats.num_chance_nodes, 0);
+    assert_eq!(stats.num_terminal_nodes, 3);
+    assert_eq!(stats.num_allin_nodes, 0);
+    assert_eq!(stats.max_depth, 2);
+
+    action_tree.add_line(&[Action::AllIn(970)]).unwrap();
+    let stats = action_tree.stats();
+    assert_eq!(stats.num_player_nodes, [0, 0, 4]);
+    assert_eq!(stats.num_terminal_nodes, 5);
+    assert_eq!(stats.num_allin_nodes, 1);
+
+    let sizes: BetSizeOptions = ("50%, a", "2.5x").try_into().unwrap();
+    let ranges = [
+        "66+,A8s+,KTs+,QJs,AJo+,KQo",
+        "22+,A2s+,K9s+,QTs+,JTs,ATo+,KJo+",
+    ];
+    let boards = [
+        ("Td9d6h", NOT_DEALT, NOT_DEALT),
+        ("QsJs5s", NOT_DEALT, NOT_DEALT),
+        ("Td9d6h", card_from_str("Qc").unwrap(), NOT_DEALT),
+        (
+            "Td9d6h",
+            card_from_str("Qc").unwrap(),
+            card_from_str("2s").unwrap(),
+        ),
+    ];
+
+    for (flop, turn, river) in boards {
+        let card_config = CardConfig {
+            range: [ranges[0].parse().unwrap(), ranges[1].parse().unwrap()],
+            flop: flop_from_str(flop).unwrap(),
+            turn,
+            river,
+            deck: Deck::Standard,
+        };
+
+        let initial_state = match (turn, river) {
+            (NOT_DEALT, _) => BoardState::Flop,
+            (_, NOT_DEALT) => BoardState::Turn,
+            _ => BoardState::River,
+        };
+
+        let tree_config = TreeConfig {
+            initial_state,
+            starting_pot: 100,
+            effective_stack: 300,
+            flop_bet_sizes: [sizes.clone(), sizes.clone()],
+            turn_bet_sizes: [sizes.clone(), sizes.clone()],
+            river_bet_sizes: [sizes.clone(), sizes.clone()],
+            ..Default::default()
+        };
+
+        let action_tree = ActionTree::new(tree_config).unwrap();
+        let stats = action_tree.stats();
+        let street = initial_state as usize;
+        assert!(stats.num_player_nodes[..street].iter().all(|&n| n == 0));
+        assert!(stats.num_player_nodes[street..].iter().all(|&n| n > 0));
+        assert_eq!(
+            stats.num_chance_nodes > 0,
+            initial_state != BoardState::River
+        );
+
+        let estimate = action_tree.estimate_memory_usage(&card_config).unwrap();
+        let game = PostFlopGame::with_config(card_config, action_tree).unwrap();
+        let (uncompressed, compressed) = game.memory_usage();
+        assert_eq!(estimate.0, uncompressed - game.misc_memory_usage);
+        assert_eq!(estimate.1, compressed - game.misc_memory_usage);
+    }
+}
+
+#[test]
+fn solve_algorithms() {
+    let card_config = CardConfig {
+        range: [
+            "TT+,AJs+,KQs,AQo+".parse().unwrap(),
+            "88+,ATs+,KJs+,AJo+,KQo".parse().unwrap(),
+        ],
+        flop: flop_from_str("Td9d6h").unwrap(),
+        turn: card_from_str("Qc").unwrap(),
+        river: card_from_str("2s").unwrap(),
+        deck: Deck::Standard,
+    };
+
+    let tree_config = TreeConfig {
+        initial_state: BoardState::River,
+        starting_pot: 100,
+        effective_stack: 200,
+        river_bet_sizes: [
+            ("50%, 100%", "60%").try_into().unwrap(),
+            ("50%, 100%", "60%").try_into().unwrap(),
+        ],
+        ..Default::default()
+    };
+
+    let algorithms = [
+        CfrAlgorithm::Discounted,
+        CfrAlgorithm::DiscountedWith {
+            alpha: 1.5,
+            beta: -0.5,
+            gamma: 2.0,
+        },
+        CfrAlgorithm::Linear,
+        CfrAlgorithm::CfrPlus,
+        CfrAlgorithm::PredictiveCfrPlus,
+    ];
+
+    for algorithm in algorithms {
+        for enable_compression in [false, true] {
+            let action_tree = ActionTree::new(tree_config.clone()).unwrap();
+            let mut game = PostFlopGame::with_config(card_config.clone(), action_tree).unwrap();
+            game.allocate_memory(enable_compression);
+
+            let exploitability = solve_with(&mut game, algorithm, 500, 0.0, false);
+            assert!(exploitability < 0.2, "{algorithm:?} ({enable_compression})");
+        }
+    }
+}
+
+#[test]
+fn solve_with_options_history() {
+    use std::time::Duration;
+
+    let card_config = CardConfig {
+        range: [
+            "TT+,AJs+,KQs".parse().unwrap(),
+            "88+,ATs+,KJs+".parse().unwrap(),
+        ],
+        flop: flop_from_str("Td9d6h").unwrap(),
+        turn: card_from_str("Qc").unwrap(),
+        river: card_from_str("2s").unwrap(),
+        deck: Deck::Standard,
+    };
+
+    let tree_config = TreeConfig {
+        initial_state: BoardState::River,
+        starting_pot: 100,
+        effective_stack: 200,
+        river_bet_sizes: [("50%", "60%").try_into().unwrap(), Default::default()],
+        ..Default::default()
+    };
+
+    let new_game = || {
+        let action_tree = ActionTree::new(tree_config.clone()).unwrap();
+        let mut game = PostFlopGame::with_config(card_config.clone(), action_tree).unwrap();
+        game.allocate_memory(false);
+        game
+    };
+
+    // iteration limit
+    let mut game = new_game();
+    let options = SolveOptions {
+        max_num_iterations: 23,
+        check_interval: 5,
+        ..Default::default()
+    };
+    let summary = continue_solve_with_options(&mut game, &options, |_| true);
+    let iterations = summary
+        .history
+        .iter()
+        .map(|p| p.iteration)
+        .collect::<Vec<_>>();
+    assert_eq!(iterations, vec![0, 5, 10, 15, 20, 23]);
+    assert_eq!(summary.stop_reason, StopReason::MaxIterations);
+    assert_eq!(summary.num_iterations, 23);
+    assert_eq!(summary.exploitability, summary.history[5].exploitability);
+    assert!(summary
+        .history
+        .windows(2)
+        .all(|w| w[0].elapsed <= w[1].elapsed));
+    for point in &summary.history {
+        let ev = point.ev.unwrap();
+        assert!((ev[0] + ev[1]).abs() < 1e-3);
+    }
+
+    // interrupted by the callback
+    let mut game = new_game();
+    let summary = continue_solve_with_options(&mut game, &options, |p| p.iteration < 10);
+    assert_eq!(summary.stop_reason, StopReason::Interrupted);
+    assert_eq!(summary.num_iterations, 10);
+
+    // time limit
+    let mut game = new_game();
+    let options = SolveOptions {
+        max_num_iterations: u32::MAX,
+        time_limit: Some(Duration::from_millis(100)),
+        compute_ev: false,
+        ..Default::default()
+    };
+    let summary = continue_solve_with_options(&mut game, &options, |_| true);
+    let last = summary.history.last().unwrap();
+    assert_eq!(summary.stop_reason, StopReason::TimeLimit);
+    assert_eq!(last.iteration, summary.num_iterations);
+    assert!(last.elapsed >= Duration::from_millis(100));
+    assert!(last.ev.is_none());
+
+    // target exploitability
+    let mut game = new_game();
+    let options = SolveOptions {
+        target_exploitability: 0.5,
+        ..Default::default()
+    };
+    let summary = continue_solve_with_options(&mut game, &options, |_| true);
+    assert_eq!(summary.stop_reason, StopReason::TargetExploitability);
+    assert!(summary.exploitability <= 0.5);
+}
+
+#[test]
+fn subgame() {
+    let card_config = CardConfig {
+        range: [
+            "TT+,AJs+,KQs,AQo+".parse().unwrap(),
+            "88+,ATs+,KJs+,AJo+,KQo".parse().unwrap(),
+        ],
+        flop: flop_from_str("Td9d6h").unwrap(),
+        turn: card_from_str("Qc").unwrap(),
+        river: NOT_DEALT,
+        deck: Deck::Standard,
+    };
+
+    let bet_sizes: BetSizeOptions = ("50%", "60%").try_into().unwrap();
+    let tree_config = TreeConfig {
+        initial_state: BoardState::Turn,
+        starting_pot: 100,
+        effective_stack: 200,
+        turn_bet_sizes: [bet_sizes.clone(), bet_sizes.clone()],
+        river_bet_sizes: [bet_sizes.clone(), bet_sizes.clone()],
+        ..Default::default()
+    };
+
+    let action_tree = ActionTree::new(tree_config).unwrap();
+    let mut game = PostFlopGame::with_config(card_config, action_tree).unwrap();
+    game.allocate_memory(false);
+    solve(&mut game, 1000, 0.05, false);
+
+    // the subgame must start at the beginning of a street
+    game.play(1);
+    assert!(game.subgame_tree_config().is_err());
+    game.play(1);
+    assert!(game.subgame_tree_config().is_err());
+    game.play(card_from_str("2s").unwrap() as usize);
+
+    let config = game.subgame_tree_config().unwrap();
+    assert_eq!(config.initial_state, BoardState::River);
+    assert_eq!(config.starting_pot, 200);
+    assert_eq!(config.effective_stack, 150);
+
+    let mismatched = TreeConfig {
+        starting_pot: 100,
+        ..config.clone()
+    };
+    let action_tree = ActionTree::new(mismatched).unwrap();
+    assert!(game.subgame(action_tree, None).is_err());
+
+    game.cache_normalized_weights();
+    let average_ev = |game: &PostFlopGame, player: usize| {
+        compute_average(
+            &game.expected_values(player),
+            game.normalized_weights(player),
+        )
+    };
+    let half_pot = 0.5 * config.starting_pot as f32;
+
+    // unsafe re-solving with the same bet sizes reproduces the blueprint
+    let action_tree = ActionTree::new(config.clone()).unwrap();
+    let mut subgame = game.subgame(action_tree, None).unwrap();
+    assert_eq!(subgame.current_board(), game.current_board());
+    assert!(subgame.resolve_gadget.is_none());
+    subgame.allocate_memory(false);
+    solve(&mut subgame, 1000, 0.05, false);
+    subgame.cache_normalized_weights();
+    for player in 0..2 {
+        let diff = average_ev(&subgame, player) - average_ev(&game, player);
+        assert!(diff.abs() < 0.1);
+    }
+
+    // safe re-solving: the gadget player cannot gain more than the blueprint values
+    for player in 0..2 {
+        let action_tree = ActionTree::new(config.clone()).unwrap();
+        let mut subgame = game.subgame(action_tree, Some(player)).unwrap();
+        let gadget = subgame.resolve_gadget.as_ref().unwrap();
+        let weights = &subgame.initial_weights[player];
+        let blueprint_value =
+            compute_average(gadget.values(), weights) * weights.iter().sum::<f32>();
+        assert!((blueprint_value - (average_ev(&game, player) - half_pot)).abs() < 1e-2);
+
+        subgame.allocate_memory(false);
+        solve(&mut subgame, 1000, 0.0, false);
+        let best_response_value = compute_mes_ev(&subgame)[player];
+        assert!(best_response_value < blueprint_value + 0.25);
+    }
+
+    // finer bet sizes
+    let finer_sizes: BetSizeOptions = ("33%, 75%, 150%", "60%").try_into().unwrap();
+    let finer_config = TreeConfig {
+        river_bet_sizes: [finer_sizes.clone(), finer_sizes],
+        ..config
+    };
+    let action_tree = ActionTree::new(finer_config).unwrap();
+    let mut subgame = game.subgame(action_tree, Some(1)).unwrap();
+    subgame.allocate_memory(false);
+    solve(&mut subgame, 100, 0.0, false);
+    assert!(subgame.available_actions().len() > game.available_actions().len());
+}
+
 #[test]
 fn isomorphism_monotone() {
     let oop_range = "88+,A8s+,A5s-A2s:0.5,AJo+,ATo:0.75,K9s+,KQo,KJo:0.75,KTo:0.25,Q9s+,QJo:0.5,J8s+,JTo:0.25,T8s+,T7s:0.45,97s+,96s:0.45,87s,86s:0.75,85s:0.45,75s+:0.75,74s:0.45,65s:0.75,64s:0.5,63s:0.45,54s:0.75,53s:0.5,52s:0.45,43s:0.5,42s:0.45,32s:0.45";
@@ -854,6 +1209,7 @@ fn node_locking() {
         flop: flop_from_str("2s3h4d").unwrap(),
         turn: card_from_str("6c").unwrap(),
         river: card_from_str("7c").unwrap(),
+        deck: Deck::Standard,
     };
 
     let tree_config = TreeConfig {
@@ -915,6 +1271,7 @@ fn node_locking_partial() {
         flop: flop_from_str("2s3h4d").unwrap(),
         turn: card_from_str("6c").unwrap(),
         river: card_from_str("7c").unwrap(),
+        deck: Deck::Standard,
     };
 
     let tree_config = TreeConfig {
@@ -950,6 +1307,46 @@ fn node_locking_partial() {
     assert!((strategy_oop[5] - 1.0).abs() < 1e-3); // AA bet
 }
 
+#[test]
+fn locked_node_histories() {
+    let card_config = CardConfig {
+        range: ["AsAh,QsQh".parse().unwrap(), "KsKh".parse().unwrap()],
+        flop: flop_from_str("2s3h4d").unwrap(),
+        turn: card_from_str("6c").unwrap(),
+        ..Default::default()
+    };
+
+    let tree_config = TreeConfig {
+        initial_state: BoardState::Turn,
+        starting_pot: 20,
+        effective_stack: 10,
+        turn_bet_sizes: [("a", "").try_into().unwrap(), ("a", "").try_into().unwrap()],
+        river_bet_sizes: [("a", "").try_into().unwrap(), ("a", "").try_into().unwrap()],
+        ..Default::default()
+    };
+
+    let action_tree = ActionTree::new(tree_config).unwrap();
+    let mut game = PostFlopGame::with_config(card_config, action_tree).unwrap();
+
+    game.allocate_memory(false);
+    assert!(game.locked_node_histories().is_empty());
+
+    let river = card_from_str("7c").unwrap() as usize;
+    game.lock_current_strategy(&[1.0, 1.0, 0.0, 0.0]); // check
+    game.apply_history(&[0, 0, river]);
+    game.lock_current_strategy(&[0.0, 0.0, 1.0, 1.0]); // all-in
+    game.back_to_root();
+
+    let histories = game.locked_node_histories();
+    assert_eq!(histories, vec![vec![], vec![0, 0, river]]);
+
+    game.apply_history(&histories[1]);
+    assert_eq!(
+        game.current_locking_strategy(),
+        Some(vec![0.0, 0.0, 1.0, 1.0])
+    );
+}
+
 #[test]
 fn node_locking_isomorphism() {
     let card_config = CardConfig {
@@ -1248,3 +1645,163 @@ fn solve_pio_preset_raked() {
     assert!((root_ev_oop - 95.57).abs() < 0.2);
     assert!((root_ev_ip - 66.98).abs() < 0.2);
 }
+
+#[test]
+fn set_tournament_payoff() {
+    let card_config = CardConfig {
+        range: [Range::ones(); 2],
+        flop: flop_from_str("Td9d6h").unwrap(),
+        ..Default::default()
+    };
+
+    let tree_config = TreeConfig {
+        starting_pot: 60,
+        effective_stack: 970,
+        ..Default::default()
+    };
+
+    let action_tree = ActionTree::new(tree_config).unwrap();
+    let mut game = PostFlopGame::with_config(card_config, action_tree).unwrap();
+
+    let invalid_payoff = TournamentPayoff {
+        stacks: [970.0, 500.0],
+        payouts: vec![100.0],
+        ..Default::default()
+    };
+    assert!(game.set_tournament_payoff(invalid_payoff).is_err());
+
+    // winner takes all: the tournament equity is proportional to the chips
+    let payoff = TournamentPayoff {
+        stacks: [970.0, 970.0],
+        payouts: vec![100.0],
+        ..Default::default()
+    };
+    game.set_tournament_payoff(payoff).unwrap();
+
+    game.allocate_memory(false);
+    finalize(&mut game);
+
+    game.cache_normalized_weights();
+    let ev_oop = compute_average(&game.expected_values(0), game.normalized_weights(0));
+    let ev_ip = compute_average(&game.expected_values(1), game.normalized_weights(1));
+    assert!((ev_oop - 50.0).abs() < 1e-4);
+    assert!((ev_ip - 50.0).abs() < 1e-4);
+}
+
+#[test]
+fn set_tournament_payoff_bounty() {
+    let flop = flop_from_str("AcAdKh").unwrap();
+    let lose_range_str = "KK-22,K9-K2,Q8-Q2,J8-J2,T8-T2,92+,82+,72+,62+";
+
+    let card_config = CardConfig {
+        range: ["AA".parse().unwrap(), lose_range_str.parse().unwrap()],
+        flop,
+        ..Default::default()
+    };
+
+    let tree_config = TreeConfig {
+        starting_pot: 60,
+        effective_stack: 970,
+        flop_bet_sizes: [("a", "").try_into().unwrap(), Default::default()],
+        ..Default::default()
+    };
+
+    let action_tree = ActionTree::new(tree_config).unwrap();
+    let mut game = PostFlopGame::with_config(card_config, action_tree).unwrap();
+
+    let payoff = TournamentPayoff {
+        stacks: [970.0, 970.0],
+        other_stacks: vec![1000.0],
+        payouts: vec![60.0, 40.0],
+        bounties: [10.0, 10.0],
+    };
+    game.set_tournament_payoff(payoff.clone()).unwrap();
+
+    game.allocate_memory(false);
+    finalize(&mut game);
+    assert!(compute_exploitability(&game) > 0.0);
+
+    // IP folds to the all-in
+    game.play(1);
+    game.cache_normalized_weights();
+    let num_hands = game.private_cards(1).len();
+    let ev_fold = compute_average(
+        &game.expected_values_detail(1)[..num_hands],
+        game.normalized_weights(1),
+    );
+    let expected = payoff.values([1030.0, 970.0]).unwrap()[1];
+    assert!((ev_fold as f64 - expected).abs() < 1e-4);
+
+    // IP calls and is eliminated
+    game.play(1);
+    game.cache_normalized_weights();
+    let ev_oop = compute_average(&game.expected_values(0), game.normalized_weights(0));
+    let ev_ip = compute_average(&game.expected_values(1), game.normalized_weights(1));
+    assert!((ev_oop - (60.0 * 2.0 / 3.0 + 40.0 / 3.0 + 10.0)).abs() < 1e-4);
+    assert!(ev_ip.abs() < 1e-4);
+}
+
+#[test]
+fn short_deck() {
+    let card_config = CardConfig {
+        range: [Range::ones(); 2],
+        flop: flop_from_str("Td9d6h").unwrap(),
+        deck: Deck::ShortDeck,
+        ..Default::default()
+    };
+
+    let tree_config = TreeConfig {
+        starting_pot: 60,
+        effective_stack: 970,
+        ..Default::default()
+    };
+
+    let action_tree = ActionTree::new(tree_config.clone()).unwrap();
+    let mut game = PostFlopGame::with_config(card_config, action_tree).unwrap();
+    assert_eq!(game.private_cards(0).len(), 33 * 32 / 2);
+
+    game.allocate_memory(false);
+    finalize(&mut game);
+
+    game.cache_normalized_weights();
+    let equity_oop = compute_average(&game.equity(0), game.normalized_weights(0));
+    let ev_oop = compute_average(&game.expected_values(0), game.normalized_weights(0));
+    assert!((equity_oop - 0.5).abs() < 1e-5);
+    assert!((ev_oop - 30.0).abs() < 1e-4);
+
+    game.play(0);
+    game.play(0);
+    assert!(game.is_chance_node());
+    let removed_mask = Deck::ShortDeck.removed_mask();
+    assert_eq!(game.possible_cards() & removed_mask, 0);
+    assert_eq!(game.possible_cards().count_ones(), 33);
+
+    // a flush beats a full house
+    let card_config = CardConfig {
+        range: ["9h8h".parse().unwrap(), "AdKc".parse().unwrap()],
+        flop: flop_from_str("AhKhQh").unwrap(),
+        turn: card_from_str("As").unwrap(),
+        river: card_from_str("7c").unwrap(),
+        deck: Deck::ShortDeck,
+    };
+
+    let tree_config = TreeConfig {
+        initial_state: BoardState::River,
+        ..tree_config
+    };
+
+    let action_tree = ActionTree::new(tree_config.clone()).unwrap();
+    let mut game = PostFlopGame::with_config(card_config.clone(), action_tree).unwrap();
+    game.allocate_memory(false);
+    finalize(&mut game);
+    game.cache_normalized_weights();
+    assert_eq!(game.equity(0), vec![1.0]);
+
+    // board cards must be in the deck
+    let card_config = CardConfig {
+        river: card_from_str("5c").unwrap(),
+        ..card_config
+    };
+    let action_tree = ActionTree::new(tree_config).unwrap();
+    assert!(PostFlopGame::with_config(card_config, action_tree).is_err());
+}
@@ -30,6 +30,19 @@ fn find_straight(rankset: i32) -> i32 {
     }
 }
 
+#[inline]
+fn find_straight_short_deck(rankset: i32) -> i32 {
+    const WHEEL: i32 = 0b1_0000_1111_0000;
+    let is_straight = rankset & (rankset << 1) & (rankset << 2) & (rankset << 3) & (rankset << 4);
+    if is_straight != 0 {
+        keep_n_msb(is_straight, 1)
+    } else if (rankset & WHEEL) == WHEEL {
+        1 << 7
+    } else {
+        0
+    }
+}
+
 impl Hand {
     #[inline]
     pub fn new() -> Hand {
@@ -54,13 +67,70 @@ impl Hand {
         HAND_TABLE.binary_search(&self.evaluate_internal()).unwrap() as u16
     }
 
+    /// Evaluates the hand with the short-deck rankings: a flush beats a full house, and A-6-7-8-9
+    /// is the lowest straight. The hand must not contain the 2s through 5s.
+    #[inline]
+    pub fn evaluate_short_deck(&self) -> u16 {
+        let raw_value = self.evaluate_with(find_straight_short_deck);
+        let index = HAND_TABLE.binary_search(&raw_value).unwrap();
+        let index = if (FLUSH_BEGIN..FULL_HOUSE_BEGIN).contains(&index) {
+            // flushes are moved above full houses
+            index + (FOUR_OF_A_KIND_BEGIN - FULL_HOUSE_BEGIN)
+        } else if (FULL_HOUSE_BEGIN..FOUR_OF_A_KIND_BEGIN).contains(&index) {
+            index - (FULL_HOUSE_BEGIN - FLUSH_BEGIN)
+        } else {
+            index
+        };
+        index as u16
+    }
+
+    /// Returns the raw value of the hand, which is comparable between hands of 5 to 7 cards.
+    #[inline]
+    pub fn raw_value(&self) -> i32 {
+        self.evaluate_internal()
+    }
+
+    /// Returns the category of the hand (0: high card, 1: one pair, ..., 8: straight flush).
+    ///
+    /// Unlike [`evaluate`], this also accepts hands of 5 or 6 cards.
+    ///
+    /// [`evaluate`]: #method.evaluate
+    #[inline]
+    pub fn category(&self) -> u8 {
+        (self.evaluate_internal() >> 26) as u8
+    }
+
+    /// Returns the raw value of the hand with the short-deck straights (A-6-7-8-9 is the lowest).
+    ///
+    /// Unlike [`evaluate_short_deck`], a full house still ranks above a flush, which only matters
+    /// when comparing hands of different categories.
+    ///
+    /// [`evaluate_short_deck`]: #method.evaluate_short_deck
+    #[inline]
+    pub fn raw_value_short_deck(&self) -> i32 {
+        self.evaluate_with(find_straight_short_deck)
+    }
+
+    /// Returns the category of the hand with the short-deck straights (see [`category`]).
+    ///
+    /// [`category`]: #method.category
+    #[inline]
+    pub fn category_short_deck(&self) -> u8 {
+        (self.raw_value_short_deck() >> 26) as u8
+    }
+
+    #[inline]
     fn evaluate_internal(&self) -> i32 {
+        self.evaluate_with(find_straight)
+    }
+
+    fn evaluate_with(&self, find_straight: fn(i32) -> i32) -> i32 {
         let mut rankset = 0i32;
         let mut rankset_suit = [0i32; 4];
         let mut rankset_of_count = [0i32; 5];
         let mut rank_count = [0i32; 13];
 
-        for &card in &self.cards {
+        for &card in &self.cards[..self.num_cards] {
             let rank = card / 4;
             let suit = card % 4;
             rankset |= 1 << rank;
@@ -173,4 +243,42 @@ mod tests {
         assert_eq!(counter[1], 58627800); // one pair
         assert_eq!(counter[0], 23294460); // high card
     }
+
+    #[test]
+    fn test_hand_table_offsets() {
+        assert_eq!(HAND_TABLE.partition_point(|&x| x < 5 << 26), FLUSH_BEGIN);
+        assert_eq!(
+            HAND_TABLE.partition_point(|&x| x < 6 << 26),
+            FULL_HOUSE_BEGIN
+        );
+        assert_eq!(
+            HAND_TABLE.partition_point(|&x| x < 7 << 26),
+            FOUR_OF_A_KIND_BEGIN
+        );
+    }
+
+    #[test]
+    fn test_short_deck() {
+        let hand = |cards: &[usize]| cards.iter().fold(Hand::new(), |hand, &c| hand.add_card(c));
+
+        // 4 * rank + suit (6 => 4, ..., A => 12)
+        let flush = hand(&[16, 24, 32, 40, 44, 17, 21]); // 6c8cTcQcKc 6d7d
+        let full_house = hand(&[48, 49, 50, 44, 45, 16, 20]); // AAA KK 6 7
+        let straight_a6789 = hand(&[48, 17, 21, 25, 29, 40, 42]); // A6789 QQ
+        let straight_6789t = hand(&[16, 21, 25, 29, 33, 44, 46]); // 6789T KK
+        let trips = hand(&[40, 41, 42, 16, 21, 32, 49]); // QQQ 6 7 T A
+        let straight_flush = hand(&[48, 16, 20, 24, 28, 41, 42]); // Ac6c7c8c9c QQ
+
+        assert!(flush.evaluate() < full_house.evaluate());
+        assert!(flush.evaluate_short_deck() > full_house.evaluate_short_deck());
+        assert!(full_house.evaluate_short_deck() > straight_6789t.evaluate_short_deck());
+        assert!(straight_6789t.evaluate_short_deck() > straight_a6789.evaluate_short_deck());
+        assert!(straight_a6789.evaluate_short_deck() > trips.evaluate_short_deck());
+        assert!(straight_a6789.evaluate() < trips.evaluate());
+        assert!(straight_flush.evaluate_short_deck() > flush.evaluate_short_deck());
+        assert_eq!(straight_flush.category(), 5); // plain flush in the standard rankings
+        assert_eq!(straight_flush.category_short_deck(), 8);
+        assert_eq!(straight_a6789.category(), 1);
+        assert_eq!(straight_a6789.category_short_deck(), 4);
+    }
 }
@@ -0,0 +1,390 @@
+use crate::card::*;
+use crate::hand::*;
+
+/// A made-hand category of hole cards on a board.
+///
+/// Categories only count hands that use at least one hole card; for example, a pair on the board
+/// alone is classified as [`MadeHand::HighCard`] or [`MadeHand::AceHigh`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MadeHand {
+    StraightFlush,
+    Quads,
+    FullHouse,
+    Flush,
+    Straight,
+    /// Pocket pair matching a board card.
+    Set,
+    /// One hole card matching a pair on the board.
+    Trips,
+    /// Both hole cards matching different board cards.
+    TwoPair,
+    /// Pocket pair above every board card.
+    Overpair,
+    TopPair,
+    /// Pocket pair below the top board card.
+    Underpair,
+    SecondPair,
+    /// Third pair or lower.
+    LowPair,
+    AceHigh,
+    HighCard,
+}
+
+/// A drawing category of hole cards on a board with cards to come.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Draw {
+    FlushDraw,
+    /// Open-ended straight draw or double gutshot.
+    OpenEnded,
+    Gutshot,
+    /// Three cards to a flush on the flop.
+    BackdoorFlushDraw,
+    /// Both hole cards above every board card without a made pair.
+    Overcards,
+}
+
+/// A classification of hole cards on a board.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HandClass {
+    pub made_hand: MadeHand,
+    pub draws: Vec<Draw>,
+}
+
+impl MadeHand {
+    /// All categories from the strongest.
+    pub const ALL: [MadeHand; 15] = [
+        MadeHand::StraightFlush,
+        MadeHand::Quads,
+        MadeHand::FullHouse,
+        MadeHand::Flush,
+        MadeHand::Straight,
+        MadeHand::Set,
+        MadeHand::Trips,
+        MadeHand::TwoPair,
+        MadeHand::Overpair,
+        MadeHand::TopPair,
+        MadeHand::Underpair,
+        MadeHand::SecondPair,
+        MadeHand::LowPair,
+        MadeHand::AceHigh,
+        MadeHand::HighCard,
+    ];
+
+    /// Returns all categories from the strongest with the hand rankings of `deck`.
+    pub fn ranking(deck: Deck) -> [MadeHand; 15] {
+        let mut ret = Self::ALL;
+        if deck == Deck::ShortDeck {
+            // a flush beats a full house
+            ret.swap(2, 3);
+        }
+        ret
+    }
+}
+
+impl Draw {
+    /// All categories from the strongest.
+    pub const ALL: [Draw; 5] = [
+        Draw::FlushDraw,
+        Draw::OpenEnded,
+        Draw::Gutshot,
+        Draw::BackdoorFlushDraw,
+        Draw::Overcards,
+    ];
+}
+
+/// Returns the highest rank of a straight of `deck` in the given rank set, if any.
+#[inline]
+fn straight_high(rankset: u16, deck: Deck) -> Option<u8> {
+    // treat an ace as both the highest rank and the rank below the lowest of the deck
+    let low_ace = match deck {
+        Deck::Standard => 0,
+        Deck::ShortDeck => 4,
+    };
+    let rankset = ((rankset as u32) << 1) | (((rankset as u32) >> 12) << low_ace);
+    let straights = rankset & (rankset >> 1) & (rankset >> 2) & (rankset >> 3) & (rankset >> 4);
+    (straights != 0).then(|| (straights.ilog2() + 3) as u8)
+}
+
+/// Classifies hole cards on a board of 3 to 5 cards with the hand rankings of `deck`.
+///
+/// # Examples
+/// ```
+/// use postflop_solver::*;
+///
+/// let board = cards_from_str("Td9d6h").unwrap();
+/// let hole = (card_from_str("Ad").unwrap(), card_from_str("Tc").unwrap());
+/// let class = classify_hand(&board, hole, Deck::Standard).unwrap();
+/// assert_eq!(class.made_hand, MadeHand::TopPair);
+/// assert_eq!(class.draws, vec![Draw::BackdoorFlushDraw]);
+/// ```
+pub fn classify_hand(board: &[Card], hole: (Card, Card), deck: Deck) -> Result<HandClass, String> {
+    if board.len() < 3 || board.len() > 5 {
+        return Err(format!("Board must have 3 to 5 cards: {}", board.len()));
+    }
+
+    let mut mask = 0u64;
+    for &card in board.iter().chain([hole.0, hole.1].iter()) {
+        if card >= 52 {
+            return Err(format!("Invalid card: {}", card));
+        }
+        if !deck.contains(card) {
+            return Err(format!("Card is not in the deck: {}", card));
+        }
+        if mask & (1 << card) != 0 {
+            return Err("Cards must be unique".to_string());
+        }
+        mask |= 1 << card;
+    }
+
+    let mut board_count = [0u8; 13];
+    let mut board_suit = [0u8; 4];
+    let mut board_rankset = 0u16;
+    for &card in board {
+        board_count[card as usize >> 2] += 1;
+        board_suit[card as usize & 3] += 1;
+        board_rankset |= 1 << (card >> 2);
+    }
+
+    let (high, low) = (hole.0.max(hole.1), hole.0.min(hole.1));
+    let (high_rank, low_rank) = (high >> 2, low >> 2);
+    let rankset = board_rankset | (1 << high_rank) | (1 << low_rank);
+    let top_rank = 15 - board_rankset.leading_zeros() as u8;
+
+    let board_hand = board
+        .iter()
+        .fold(Hand::new(), |hand, &card| hand.add_card(card as usize));
+    let hand = board_hand.add_card(high as usize).add_card(low as usize);
+    let category = deck.category(&hand);
+
+    // whether the hole cards improve the best five cards of the board
+    let improves_board = board.len() < 5 || deck.raw_value(&hand) > deck.raw_value(&board_hand);
+
+    let mut suit_count = board_suit;
+    suit_count[high as usize & 3] += 1;
+    suit_count[low as usize & 3] += 1;
+    let flush_suit = (0..4).find(|&suit| suit_count[suit] >= 5);
+    let holds_suit = |suit: usize| high as usize & 3 == suit || low as usize & 3 == suit;
+
+    let plays_straight = match (
+        straight_high(rankset, deck),
+        straight_high(board_rankset, deck),
+    ) {
+        (Some(hand), Some(board)) => hand > board,
+        (hand, _) => hand.is_some(),
+    };
+
+    let made_hand = match category {
+        8 if improves_board => MadeHand::StraightFlush,
+        7 if board_count.iter().all(|&count| count < 4) => MadeHand::Quads,
+        6 if improves_board => MadeHand::FullHouse,
+        5 if improves_board && flush_suit.is_some_and(holds_suit) => MadeHand::Flush,
+        4 if plays_straight => MadeHand::Straight,
+        _ => classify_pair(&board_count, board_rankset, top_rank, high_rank, low_rank),
+    };
+
+    let mut draws = Vec::new();
+
+    if board.len() < 5 {
+        let is_flush = matches!(made_hand, MadeHand::StraightFlush | MadeHand::Flush);
+        if !is_flush && (0..4).any(|suit| suit_count[suit] == 4 && holds_suit(suit)) {
+            draws.push(Draw::FlushDraw);
+        }
+
+        let is_straight = matches!(
+            made_hand,
+            MadeHand::StraightFlush | MadeHand::Quads | MadeHand::FullHouse | MadeHand::Straight
+        );
+        if !is_straight {
+            let outs = (0..13)
+                .filter(|&rank| rankset & (1 << rank) == 0 && deck.contains(rank << 2))
+                .filter(|&rank| {
+                    let hand = straight_high(rankset | (1 << rank), deck);
+                    hand.is_some() && hand > straight_high(board_rankset | (1 << rank), deck)
+                })
+                .count();
+            match outs {
+                0 => {}
+                1 => draws.push(Draw::Gutshot),
+                _ => draws.push(Draw::OpenEnded),
+            }
+        }
+
+        if board.len() == 3 && !is_flush && (0..4).any(|s| suit_count[s] == 3 && holds_suit(s)) {
+            draws.push(Draw::BackdoorFlushDraw);
+        }
+
+        if matches!(made_hand, MadeHand::AceHigh | MadeHand::HighCard) && low_rank > top_rank {
+            draws.push(Draw::Overcards);
+        }
+    }
+
+    Ok(HandClass { made_hand, draws })
+}
+
+/// Classifies hands that are at most trips by their use of the hole cards.
+fn classify_pair(
+    board_count: &[u8; 13],
+    board_rankset: u16,
+    top_rank: u8,
+    high_rank: u8,
+    low_rank: u8,
+) -> MadeHand {
+    let high_count = board_count[high_rank as usize];
+    let low_count = board_count[low_rank as usize];
+
+    if high_rank == low_rank {
+        return match high_count {
+            0 if high_rank > top_rank => MadeHand::Overpair,
+            0 => MadeHand::Underpair,
+            _ => MadeHand::Set,
+        };
+    }
+
+    if high_count >= 2 || low_count >= 2 {
+        return MadeHand::Trips;
+    }
+
+    if high_count == 1 && low_count == 1 {
+        return MadeHand::TwoPair;
+    }
+
+    let paired_rank = match (high_count, low_count) {
+        (1, _) => high_rank,
+        (_, 1) => low_rank,
+        _ if high_rank == 12 => return MadeHand::AceHigh,
+        _ => return MadeHand::HighCard,
+    };
+
+    // number of distinct board ranks above the paired rank
+    match (board_rankset >> (paired_rank + 1)).count_ones() {
+        0 => MadeHand::TopPair,
+        1 => MadeHand::SecondPair,
+        _ => MadeHand::LowPair,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::range::*;
+
+    fn classify_with(board: &str, hole: &str, deck: Deck) -> HandClass {
+        let board = cards_from_str(board).unwrap();
+        let hole = cards_from_str(hole).unwrap();
+        classify_hand(&board, (hole[0], hole[1]), deck).unwrap()
+    }
+
+    fn classify(board: &str, hole: &str) -> HandClass {
+        classify_with(board, hole, Deck::Standard)
+    }
+
+    #[test]
+    fn test_straight_high() {
+        let standard = Deck::Standard;
+        assert_eq!(straight_high(0b1_0000_0000_1111, standard), Some(3));
+        assert_eq!(straight_high(0b1_1111_0000_0000, standard), Some(12));
+        assert_eq!(straight_high(0b0_0000_0011_1110, standard), Some(5));
+        assert_eq!(straight_high(0b1_1110_0000_0001, standard), None);
+        assert_eq!(straight_high(0b1_0000_1111_0000, standard), None);
+        assert_eq!(straight_high(0b1_0000_1111_0000, Deck::ShortDeck), Some(7));
+        assert_eq!(straight_high(0b1_1111_0000_0000, Deck::ShortDeck), Some(12));
+    }
+
+    #[test]
+    fn test_made_hands() {
+        let cases = [
+            ("Td9d6d", "8d7d", MadeHand::StraightFlush),
+            ("Td9d6h", "TcTh", MadeHand::Set),
+            ("TdTc6h", "Ts9s", MadeHand::Trips),
+            ("Td9d6h", "Tc9c", MadeHand::TwoPair),
+            ("Td9d6h", "QcQh", MadeHand::Overpair),
+            ("Td9d6h", "AcTc", MadeHand::TopPair),
+            ("Td9d6h", "8c8h", MadeHand::Underpair),
+            ("Td9d6h", "9c8c", MadeHand::SecondPair),
+            ("Td9d6h", "6c5c", MadeHand::LowPair),
+            ("Td9d6h", "AcKc", MadeHand::AceHigh),
+            ("Td9d6h", "KcQc", MadeHand::HighCard),
+            ("Td9d6h", "8c7c", MadeHand::Straight),
+            ("Td9d6d", "Ad2d", MadeHand::Flush),
+            ("TdTc6h6c", "Ts9s", MadeHand::FullHouse),
+            ("TdTcThTs2c", "AcKc", MadeHand::AceHigh),
+            ("TdTcTh6h6c", "AcKd", MadeHand::AceHigh),
+            ("TdTcTh6h6c", "7c7d", MadeHand::FullHouse),
+            ("9h8h7h6h5h", "AcKd", MadeHand::AceHigh),
+            ("9h8h7h6h5h", "Th2c", MadeHand::StraightFlush),
+            ("Kh9h7h5h3h", "Qc2h", MadeHand::HighCard),
+            ("Kh9h7h5h3h", "Qh2c", MadeHand::Flush),
+            ("6h7d8c9sTd", "2c3c", MadeHand::HighCard),
+            ("6h7d8c9sTd", "Jc3c", MadeHand::Straight),
+            ("TdTc6h", "AcKc", MadeHand::AceHigh),
+        ];
+
+        for (board, hole, expected) in cases {
+            assert_eq!(
+                classify(board, hole).made_hand,
+                expected,
+                "{} {}",
+                board,
+                hole
+            );
+        }
+    }
+
+    #[test]
+    fn test_draws() {
+        assert_eq!(
+            classify("Td9d6h", "AdKd").draws,
+            vec![Draw::FlushDraw, Draw::Overcards]
+        );
+        assert_eq!(classify("Td9c2h", "8s7s").draws, vec![Draw::OpenEnded]);
+        assert_eq!(
+            classify("Td9c2h", "QsJs").draws,
+            vec![Draw::OpenEnded, Draw::Overcards]
+        );
+        assert_eq!(classify("Td9c2h", "Qs8s").draws, vec![Draw::Gutshot]);
+        assert_eq!(
+            classify("Td9c2h", "Ah5h").draws,
+            vec![Draw::BackdoorFlushDraw]
+        );
+        assert_eq!(classify("5d4c2h", "As3s").made_hand, MadeHand::Straight);
+        assert_eq!(classify("Td9c2hKs", "Ah5h").draws, vec![]);
+        assert_eq!(classify("Td9c2h3s5d", "8s7s").draws, vec![]);
+    }
+
+    #[test]
+    fn test_short_deck() {
+        let short_deck = |board, hole| classify_with(board, hole, Deck::ShortDeck);
+        assert_eq!(classify("9h8d7c", "Ah6s").made_hand, MadeHand::AceHigh);
+        assert_eq!(short_deck("9h8d7c", "Ah6s").made_hand, MadeHand::Straight);
+        assert_eq!(
+            short_deck("9h8h7h", "Ah6h").made_hand,
+            MadeHand::StraightFlush
+        );
+        assert_eq!(
+            short_deck("Ah9c8d7s6h", "KcQc").made_hand,
+            MadeHand::HighCard
+        );
+        assert_eq!(classify("9h8d7c", "AsKs").draws, vec![Draw::Overcards]);
+        assert_eq!(
+            short_deck("9h8d7c", "AsKs").draws,
+            vec![Draw::Gutshot, Draw::Overcards]
+        );
+
+        let board = cards_from_str("9h8d7c").unwrap();
+        let hole = (card_from_str("As").unwrap(), card_from_str("2s").unwrap());
+        assert!(classify_hand(&board, hole, Deck::ShortDeck).is_err());
+
+        let ranking = MadeHand::ranking(Deck::ShortDeck);
+        let position = |made_hand| ranking.iter().position(|&m| m == made_hand);
+        assert!(position(MadeHand::Flush) < position(MadeHand::FullHouse));
+        assert_eq!(MadeHand::ranking(Deck::Standard), MadeHand::ALL);
+    }
+
+    #[test]
+    fn test_invalid() {
+        let board = cards_from_str("Td9c").unwrap();
+        assert!(classify_hand(&board, (0, 1), Deck::Standard).is_err());
+        let board = cards_from_str("Td9c2h").unwrap();
+        assert!(classify_hand(&board, (board[0], 1), Deck::Standard).is_err());
+    }
+}
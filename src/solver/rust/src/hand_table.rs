@@ -559,3 +559,12 @@ pub(crate) const HAND_TABLE: [i32; 4824] = [
     536870920, 536870928, 536870944, 536870976, 536871040, 536871168, 536871424, 536871936,
     536872960, 536875008,
 ];
+
+/// Index of the weakest flush in `HAND_TABLE`.
+pub(crate) const FLUSH_BEGIN: usize = 3225;
+
+/// Index of the weakest full house in `HAND_TABLE`.
+pub(crate) const FULL_HOUSE_BEGIN: usize = 4502;
+
+/// Index of the weakest four of a kind in `HAND_TABLE`.
+pub(crate) const FOUR_OF_A_KIND_BEGIN: usize = 4658;
@@ -0,0 +1,173 @@
+#[cfg(feature = "bincode")]
+use bincode::{Decode, Encode};
+
+/// The maximum number of players with chips that [`compute_icm`] accepts.
+pub const MAX_ICM_PLAYERS: usize = 20;
+
+/// Computes the expected prize of each player by the Malmuth-Harville ICM model.
+///
+/// The probability that a player finishes in the next place is proportional to the stack among
+/// the players who have not finished yet. `payouts[i]` is the prize of the `(i + 1)`-th place;
+/// places without an entry get nothing. Players with no chips finish in the last places and share
+/// their prizes equally.
+///
+/// **Time complexity:** *O*(2^*n* *n*), where *n* is the number of players with chips.
+///
+/// # Examples
+/// ```
+/// use postflop_solver::*;
+///
+/// let equity = compute_icm(&[5000.0, 3000.0, 2000.0], &[50.0, 30.0, 20.0]).unwrap();
+/// assert!((equity[0] - 38.39).abs() < 0.01);
+/// ```
+pub fn compute_icm(stacks: &[f64], payouts: &[f64]) -> Result<Vec<f64>, String> {
+    if stacks
+        .iter()
+        .any(|&stack| !stack.is_finite() || stack < 0.0)
+    {
+        return Err("Stacks must be non-negative".to_string());
+    }
+
+    if payouts
+        .iter()
+        .any(|&payout| !payout.is_finite() || payout < 0.0)
+    {
+        return Err("Payouts must be non-negative".to_string());
+    }
+
+    let alive = (0..stacks.len())
+        .filter(|&player| stacks[player] > 0.0)
+        .collect::<Vec<_>>();
+
+    if alive.is_empty() {
+        return Err("At least one player must have chips".to_string());
+    }
+
+    if alive.len() > MAX_ICM_PLAYERS {
+        return Err(format!(
+            "Too many players with chips: {} (maximum: {MAX_ICM_PLAYERS})",
+            alive.len()
+        ));
+    }
+
+    let payout = |place: usize| payouts.get(place).cloned().unwrap_or(0.0);
+    let mut ret = vec![0.0; stacks.len()];
+
+    // busted players share the last places
+    let num_busted = stacks.len() - alive.len();
+    if num_busted > 0 {
+        let share = (alive.len()..stacks.len()).map(payout).sum::<f64>() / num_busted as f64;
+        for (equity, &stack) in ret.iter_mut().zip(stacks) {
+            if stack == 0.0 {
+                *equity = share;
+            }
+        }
+    }
+
+    // prob[mask]: probability that the players in `mask` take the top places in any order
+    let num_places = payouts.len().min(alive.len());
+    let total = alive.iter().map(|&player| stacks[player]).sum::<f64>();
+    let mut prob = vec![0.0; 1 << alive.len()];
+    let mut finished_stack = vec![0.0; 1 << alive.len()];
+    prob[0] = 1.0;
+
+    for mask in 0..prob.len() {
+        if mask > 0 {
+            let lowest = mask.trailing_zeros() as usize;
+            finished_stack[mask] = finished_stack[mask & (mask - 1)] + stacks[alive[lowest]];
+        }
+
+        let place = mask.count_ones() as usize;
+        if place >= num_places || prob[mask] == 0.0 {
+            continue;
+        }
+
+        let remaining = total - finished_stack[mask];
+        for (i, &player) in alive.iter().enumerate() {
+            if mask & (1 << i) == 0 {
+                let p = prob[mask] * stacks[player] / remaining;
+                ret[player] += p * payout(place);
+                prob[mask | (1 << i)] += p;
+            }
+        }
+    }
+
+    Ok(ret)
+}
+
+/// A tournament payoff model of a postflop game, set by [`PostFlopGame::set_tournament_payoff`].
+///
+/// Terminal chip outcomes are converted into the tournament equity by [`compute_icm`], and the
+/// bounty of a player is added to the opponent's value when the player loses the whole stack.
+///
+/// [`PostFlopGame::set_tournament_payoff`]: crate::PostFlopGame::set_tournament_payoff
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "bincode", derive(Decode, Encode))]
+pub struct TournamentPayoff {
+    /// Stacks of OOP and IP at the root of the postflop game, excluding the starting pot. Must be
+    /// greater than or equal to the effective stack.
+    pub stacks: [f64; 2],
+
+    /// Stacks of the other players of the tournament.
+    pub other_stacks: Vec<f64>,
+
+    /// Payouts of the tournament from the first place.
+    pub payouts: Vec<f64>,
+
+    /// Bounties won by eliminating OOP and IP, respectively. For progressive knockouts, pass the
+    /// part of the bounty paid out immediately (usually half).
+    pub bounties: [f64; 2],
+}
+
+impl TournamentPayoff {
+    /// Returns the values of OOP and IP when the hand ends with the given stacks.
+    pub fn values(&self, stacks: [f64; 2]) -> Result<[f64; 2], String> {
+        let mut all_stacks = stacks.to_vec();
+        all_stacks.extend_from_slice(&self.other_stacks);
+        let equity = compute_icm(&all_stacks, &self.payouts)?;
+
+        let mut ret = [equity[0], equity[1]];
+        for player in 0..2 {
+            if stacks[player] == 0.0 && stacks[player ^ 1] > 0.0 {
+                ret[player ^ 1] += self.bounties[player];
+            }
+        }
+
+        Ok(ret)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn icm_three_players() {
+        let payouts = [50.0, 30.0, 20.0];
+        let equity = compute_icm(&[50.0, 30.0, 20.0], &payouts).unwrap();
+        assert!((equity[0] - 38.392857).abs() < 1e-5);
+        assert!((equity.iter().sum::<f64>() - 100.0).abs() < 1e-9);
+
+        // equal stacks
+        let equity = compute_icm(&[10.0; 3], &payouts).unwrap();
+        assert!(equity.iter().all(|&e| (e - 100.0 / 3.0).abs() < 1e-9));
+
+        // winner takes all
+        let equity = compute_icm(&[60.0, 30.0, 10.0], &[100.0]).unwrap();
+        assert!((equity[0] - 60.0).abs() < 1e-9);
+        assert!((equity[2] - 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn icm_busted_players() {
+        let equity = compute_icm(&[100.0, 0.0], &[70.0, 30.0]).unwrap();
+        assert_eq!(equity, vec![70.0, 30.0]);
+
+        let equity = compute_icm(&[0.0, 50.0, 0.0, 50.0], &[40.0, 30.0, 20.0, 10.0]).unwrap();
+        assert_eq!(equity, vec![15.0, 35.0, 15.0, 35.0]);
+
+        assert!(compute_icm(&[0.0, 0.0], &[1.0]).is_err());
+        assert!(compute_icm(&[1.0, -1.0], &[1.0]).is_err());
+        assert!(compute_icm(&[1.0; MAX_ICM_PLAYERS + 1], &[1.0]).is_err());
+    }
+}
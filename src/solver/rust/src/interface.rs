@@ -1,4 +1,5 @@
 use crate::mutex_like::*;
+use crate::solver::ResolveGadget;
 use std::mem::MaybeUninit;
 use std::ops::Range;
 
@@ -20,6 +21,28 @@ pub trait Game: Send + Sync {
     #[doc(hidden)]
     fn initial_weights(&self, player: usize) -> &[f32];
 
+    /// Returns the number of players.
+    #[doc(hidden)]
+    fn num_players(&self) -> usize {
+        2
+    }
+
+    /// Returns the initial reach probabilities of the opponents of given player.
+    ///
+    /// With more than two players, the reach probabilities of all opponents are concatenated in
+    /// ascending order of the player index.
+    #[doc(hidden)]
+    fn initial_opponent_weights(&self, player: usize) -> &[f32] {
+        self.initial_weights(player ^ 1)
+    }
+
+    /// Returns the range of `opponent`'s reach probabilities within those of the opponents of
+    /// `player`.
+    #[doc(hidden)]
+    fn opponent_reach_range(&self, _player: usize, opponent: usize) -> Range<usize> {
+        0..self.num_private_hands(opponent)
+    }
+
     /// Computes the counterfactual values of given node.
     #[doc(hidden)]
     fn evaluate(
@@ -42,6 +65,14 @@ pub trait Game: Send + Sync {
     #[doc(hidden)]
     fn set_solved(&mut self);
 
+    /// Returns the number of completed solver iterations.
+    fn num_iterations(&self) -> u32 {
+        0
+    }
+
+    /// Sets the number of completed solver iterations.
+    fn set_num_iterations(&mut self, _num_iterations: u32) {}
+
     /// Returns whether the instance is ready to be solved.
     #[doc(hidden)]
     fn is_ready(&self) -> bool {
@@ -77,6 +108,20 @@ pub trait Game: Send + Sync {
     fn is_compression_enabled(&self) -> bool {
         false
     }
+
+    /// Allocates the buffer of previous instantaneous regrets used by predictive CFR.
+    ///
+    /// The default implementation panics, i.e., the predictive CFR variants are not supported.
+    #[doc(hidden)]
+    fn allocate_predictions(&mut self) {
+        panic!("Predictive CFR is not supported by this game");
+    }
+
+    /// Returns the re-solving gadget, if any.
+    #[doc(hidden)]
+    fn resolve_gadget(&self) -> Option<&ResolveGadget> {
+        None
+    }
 }
 
 /// The trait representing a node in game tree.
@@ -161,6 +206,18 @@ pub trait GameNode: Send + Sync {
         unreachable!()
     }
 
+    /// Returns the previous instantaneous regrets (for predictive CFR).
+    #[doc(hidden)]
+    fn predictions(&self) -> &[f32] {
+        unreachable!()
+    }
+
+    /// Returns the mutable reference to the previous instantaneous regrets.
+    #[doc(hidden)]
+    fn predictions_mut(&mut self) -> &mut [f32] {
+        unreachable!()
+    }
+
     /// Returns the [`Range`] struct of actions.
     #[doc(hidden)]
     fn action_indices(&self) -> Range<usize> {
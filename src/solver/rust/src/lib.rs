@@ -64,14 +64,24 @@ mod atomic_float;
 mod bet_size;
 mod bunching;
 mod card;
+mod equity;
+mod flops;
 mod game;
 mod hand;
+mod hand_class;
 mod hand_table;
+mod icm;
 mod interface;
+mod multiway;
 mod mutex_like;
+mod pio;
+mod preflop;
+mod push_fold;
 mod range;
+mod report;
 mod sliceop;
 mod solver;
+mod tree_export;
 mod utility;
 
 #[cfg(feature = "bincode")]
@@ -81,11 +91,21 @@ pub use action_tree::*;
 pub use bet_size::*;
 pub use bunching::*;
 pub use card::*;
+pub use equity::*;
+pub use flops::*;
 pub use game::*;
+pub use hand_class::*;
+pub use icm::*;
 pub use interface::*;
+pub use multiway::*;
 pub use mutex_like::*;
+pub use pio::*;
+pub use preflop::*;
+pub use push_fold::*;
 pub use range::*;
+pub use report::*;
 pub use solver::*;
+pub use tree_export::*;
 pub use utility::*;
 
 // Node.js bindings
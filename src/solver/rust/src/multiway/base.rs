@@ -0,0 +1,642 @@
+use super::*;
+use crate::hand::*;
+use crate::interface::*;
+use crate::utility::*;
+use std::mem::{self, MaybeUninit};
+use std::ops::Range;
+
+#[derive(Clone)]
+struct BuildTreeInfo {
+    board_state: BoardState,
+    bets: [i32; 3],
+    prev_amount: i32,
+    min_raise: i32,
+    num_bets: i32,
+    num_to_act: usize,
+    stack: i32,
+}
+
+impl Game for MultiwayGame {
+    type Node = MultiwayNode;
+
+    #[inline]
+    fn root(&self) -> MutexGuardLike<'_, Self::Node> {
+        self.root.lock()
+    }
+
+    #[inline]
+    fn num_private_hands(&self, player: usize) -> usize {
+        self.private_cards[player].len()
+    }
+
+    #[inline]
+    fn initial_weights(&self, player: usize) -> &[f32] {
+        &self.initial_weights[player]
+    }
+
+    #[inline]
+    fn num_players(&self) -> usize {
+        MULTIWAY_NUM_PLAYERS
+    }
+
+    #[inline]
+    fn initial_opponent_weights(&self, player: usize) -> &[f32] {
+        &self.opponent_weights[player]
+    }
+
+    #[inline]
+    fn opponent_reach_range(&self, player: usize, opponent: usize) -> Range<usize> {
+        let [first, _] = opponents(player);
+        let offset = if opponent == first {
+            0
+        } else {
+            self.num_private_hands(first)
+        };
+        offset..offset + self.num_private_hands(opponent)
+    }
+
+    #[inline]
+    fn evaluate(
+        &self,
+        result: &mut [MaybeUninit<f32>],
+        node: &Self::Node,
+        player: usize,
+        cfreach: &[f32],
+    ) {
+        self.evaluate_internal(result, node, player, cfreach);
+    }
+
+    #[inline]
+    fn chance_factor(&self, node: &Self::Node) -> usize {
+        if node.turn == NOT_DEALT {
+            43
+        } else {
+            42
+        }
+    }
+
+    #[inline]
+    fn is_solved(&self) -> bool {
+        self.is_solved
+    }
+
+    #[inline]
+    fn set_solved(&mut self) {
+        self.is_solved = true;
+        visit_nodes_mut(&mut self.root.lock(), &mut |node| {
+            node.predictions = Vec::new();
+        });
+    }
+
+    #[inline]
+    fn num_iterations(&self) -> u32 {
+        self.num_iterations
+    }
+
+    #[inline]
+    fn set_num_iterations(&mut self, num_iterations: u32) {
+        self.num_iterations = num_iterations;
+    }
+
+    #[inline]
+    fn is_ready(&self) -> bool {
+        self.num_nodes > 0
+    }
+
+    #[inline]
+    fn is_raked(&self) -> bool {
+        self.tree_config.rake_rate > 0.0 && self.tree_config.rake_cap > 0.0
+    }
+
+    #[inline]
+    fn allocate_predictions(&mut self) {
+        visit_nodes_mut(&mut self.root.lock(), &mut |node| {
+            if node.predictions.len() != node.regrets.len() {
+                node.predictions = vec![0.0; node.regrets.len()];
+            }
+        });
+    }
+}
+
+impl MultiwayGame {
+    /// Creates a new [`MultiwayGame`] with the specified configuration and allocates the memory.
+    ///
+    /// The game tree is built from `tree_config` in the same way as [`ActionTree`], but with three
+    /// players; a betting round ends when every player who has not folded has acted and matched
+    /// the largest bet.
+    ///
+    /// [`ActionTree`]: crate::ActionTree
+    pub fn with_config(
+        card_config: MultiwayCardConfig,
+        tree_config: MultiwayTreeConfig,
+    ) -> Result<Self, String> {
+        check_tree_config(&tree_config)?;
+
+        let mut game = Self {
+            card_config,
+            tree_config,
+            ..Default::default()
+        };
+
+        game.check_card_config()?;
+        game.init_hand_strength();
+        game.build_tree();
+        game.back_to_root();
+
+        Ok(game)
+    }
+
+    /// Returns the card configuration.
+    #[inline]
+    pub fn card_config(&self) -> &MultiwayCardConfig {
+        &self.card_config
+    }
+
+    /// Returns the tree configuration.
+    #[inline]
+    pub fn tree_config(&self) -> &MultiwayTreeConfig {
+        &self.tree_config
+    }
+
+    /// Returns the list of private hands of the given player.
+    ///
+    /// Panics if `player` is not `0`, `1`, or `2`.
+    #[inline]
+    pub fn private_cards(&self, player: usize) -> &[(Card, Card)] {
+        &self.private_cards[player]
+    }
+
+    /// Returns the number of nodes of the game tree.
+    #[inline]
+    pub fn num_nodes(&self) -> u64 {
+        self.num_nodes
+    }
+
+    /// Returns the memory usage of the game tree in bytes.
+    pub fn memory_usage(&self) -> u64 {
+        mem::size_of::<MultiwayNode>() as u64 + memory_usage_recursive(&self.root.lock())
+    }
+
+    /// Checks the card configuration and initializes the fields related to the hands.
+    fn check_card_config(&mut self) -> Result<(), String> {
+        let config = &self.card_config;
+        let (flop, turn, river) = (config.flop, config.turn, config.river);
+        let expected_state = check_board_cards(flop, turn, river)?;
+
+        if self.tree_config.initial_state != expected_state {
+            return Err(format!(
+                "Invalid initial state of `tree_config`: expected = {:?}, actual = {:?}",
+                expected_state, self.tree_config.initial_state
+            ));
+        }
+
+        for (player, range) in config.range.iter().enumerate() {
+            if range.is_empty() {
+                return Err(format!("Range of player {player} is empty"));
+            }
+
+            if !range.is_valid() {
+                return Err(format!(
+                    "Range of player {player} is invalid (loaded broken data?)"
+                ));
+            }
+        }
+
+        let board_mask = self.initial_board_mask();
+        for player in 0..MULTIWAY_NUM_PLAYERS {
+            let (hands, weights) = self.card_config.range[player].get_hands_weights(board_mask);
+
+            let mut hand_index = vec![u16::MAX; 52 * 51 / 2];
+            for (index, &(c1, c2)) in hands.iter().enumerate() {
+                hand_index[card_pair_to_index(c1, c2)] = index as u16;
+            }
+
+            self.private_cards[player] = hands;
+            self.initial_weights[player] = weights;
+            self.hand_index[player] = hand_index;
+        }
+
+        for player in 0..MULTIWAY_NUM_PLAYERS {
+            self.opponent_weights[player] = opponents(player)
+                .iter()
+                .flat_map(|&opponent| self.initial_weights[opponent].iter().cloned())
+                .collect();
+        }
+
+        let mut num_combinations = 0.0;
+        self.sum_over_opponents(
+            0,
+            &self.opponent_weights[0],
+            board_mask,
+            None,
+            &[[1.0; 3]; 3],
+            |index, value| num_combinations += self.initial_weights[0][index] as f64 * value,
+        );
+
+        if num_combinations <= 0.0 {
+            return Err("Valid card assignment does not exist".to_string());
+        }
+
+        self.num_combinations = num_combinations;
+        Ok(())
+    }
+
+    /// Initializes the field `hand_strength`.
+    fn init_hand_strength(&mut self) {
+        let config = &self.card_config;
+        self.hand_strength = vec![Default::default(); 52 * 51 / 2];
+
+        let mut flop = Hand::new();
+        for &card in &config.flop {
+            flop = flop.add_card(card as usize);
+        }
+
+        for turn in 0..52 {
+            for river in turn + 1..52 {
+                if flop.contains(turn as usize)
+                    || flop.contains(river as usize)
+                    || config.turn != NOT_DEALT && config.turn != turn && config.turn != river
+                    || config.river != NOT_DEALT && config.river != turn && config.river != river
+                {
+                    continue;
+                }
+
+                let board = flop.add_card(turn as usize).add_card(river as usize);
+                let strength = &mut self.hand_strength[card_pair_to_index(turn, river)];
+
+                for (strength, cards) in strength.iter_mut().zip(&self.private_cards) {
+                    *strength = cards
+                        .iter()
+                        .map(|&(c1, c2)| {
+                            let (c1, c2) = (c1 as usize, c2 as usize);
+                            if board.contains(c1) || board.contains(c2) {
+                                0
+                            } else {
+                                board.add_card(c1).add_card(c2).evaluate() + 1 // +1 to avoid 0
+                            }
+                        })
+                        .collect();
+                }
+            }
+        }
+    }
+
+    /// Returns the mask of the board cards given by the card configuration.
+    pub(super) fn initial_board_mask(&self) -> u64 {
+        let config = &self.card_config;
+        let mut mask: u64 = config.flop.iter().fold(0, |mask, &card| mask | (1 << card));
+        if config.turn != NOT_DEALT {
+            mask |= 1 << config.turn;
+        }
+        if config.river != NOT_DEALT {
+            mask |= 1 << config.river;
+        }
+        mask
+    }
+
+    /// Builds the game tree and allocates the memory.
+    fn build_tree(&mut self) {
+        let mut root = self.root.lock();
+        *root = MultiwayNode {
+            player: 0,
+            turn: self.card_config.turn,
+            river: self.card_config.river,
+            ..Default::default()
+        };
+
+        let info = BuildTreeInfo {
+            board_state: self.tree_config.initial_state,
+            bets: [0; 3],
+            prev_amount: 0,
+            min_raise: 0,
+            num_bets: 0,
+            num_to_act: MULTIWAY_NUM_PLAYERS,
+            stack: self.tree_config.effective_stack,
+        };
+
+        let mut num_nodes = 0;
+        self.build_tree_recursive(&mut root, &info, &mut num_nodes);
+        self.num_nodes = num_nodes;
+    }
+
+    fn build_tree_recursive(
+        &self,
+        node: &mut MultiwayNode,
+        info: &BuildTreeInfo,
+        num_nodes: &mut u64,
+    ) {
+        *num_nodes += 1;
+
+        if node.is_terminal() {
+            // do nothing
+        } else if node.is_chance() {
+            let board_state = match info.board_state {
+                BoardState::Flop => BoardState::Turn,
+                BoardState::Turn => BoardState::River,
+                BoardState::River => unreachable!(),
+            };
+
+            let stack = info.stack - info.prev_amount;
+            let player = match (stack, board_state) {
+                (0, BoardState::River) => PLAYER_TERMINAL,
+                (0, _) => PLAYER_CHANCE,
+                _ => (0..3).find(|&p| node.folded & (1 << p) == 0).unwrap(),
+            };
+
+            let next_info = BuildTreeInfo {
+                board_state,
+                bets: [0; 3],
+                prev_amount: 0,
+                min_raise: 0,
+                num_bets: 0,
+                num_to_act: 3 - node.folded.count_ones() as usize,
+                stack,
+            };
+
+            let mut board_mask = self.initial_board_mask();
+            if node.turn != NOT_DEALT {
+                board_mask |= 1 << node.turn;
+            }
+
+            for card in (0..52).filter(|&card| board_mask & (1 << card) == 0) {
+                let (turn, river) = match board_state {
+                    BoardState::Turn => (card, NOT_DEALT),
+                    _ => (node.turn, card),
+                };
+
+                let mut child = MultiwayNode {
+                    prev_action: Action::Chance(card),
+                    player,
+                    turn,
+                    river,
+                    folded: node.folded,
+                    amount: node.amount,
+                    ..Default::default()
+                };
+
+                self.build_tree_recursive(&mut child, &next_info, num_nodes);
+                node.children.push(MutexLike::new(child));
+            }
+        } else {
+            let actions = self.compute_actions(node, info);
+            let player = node.player as usize;
+            let num_hands = self.private_cards[player].len();
+
+            node.strategy = vec![0.0; actions.len() * num_hands];
+            node.regrets = vec![0.0; actions.len() * num_hands];
+
+            for action in actions {
+                let mut next_info = info.clone();
+                let mut folded = node.folded;
+                let mut amount = node.amount;
+                next_info.num_to_act -= 1;
+
+                match action {
+                    Action::Fold => folded |= 1 << player,
+                    Action::Check => {}
+                    Action::Call => {
+                        amount[player] += info.prev_amount - info.bets[player];
+                        next_info.bets[player] = info.prev_amount;
+                    }
+                    Action::Bet(bet) | Action::Raise(bet) | Action::AllIn(bet) => {
+                        amount[player] += bet - info.bets[player];
+                        next_info.bets[player] = bet;
+                        next_info.prev_amount = bet;
+                        next_info.min_raise = bet - info.prev_amount;
+                        next_info.num_bets += 1;
+                        next_info.num_to_act = 2 - folded.count_ones() as usize;
+                    }
+                    _ => panic!("Unexpected action: {action:?}"),
+                }
+
+                let next_player = if folded.count_ones() == 2 {
+                    PLAYER_TERMINAL
+                } else if next_info.num_to_act == 0 {
+                    match info.board_state {
+                        BoardState::River => PLAYER_TERMINAL,
+                        _ => PLAYER_CHANCE,
+                    }
+                } else {
+                    (1..3)
+                        .map(|offset| (node.player + offset) % 3)
+                        .find(|&p| folded & (1 << p) == 0)
+                        .unwrap()
+                };
+
+                let mut child = MultiwayNode {
+                    prev_action: action,
+                    player: next_player,
+                    turn: node.turn,
+                    river: node.river,
+                    folded,
+                    amount,
+                    ..Default::default()
+                };
+
+                self.build_tree_recursive(&mut child, &next_info, num_nodes);
+                node.children.push(MutexLike::new(child));
+            }
+        }
+
+        node.children.shrink_to_fit();
+    }
+
+    /// Computes the available actions of the given player node.
+    fn compute_actions(&self, node: &MultiwayNode, info: &BuildTreeInfo) -> Vec<Action> {
+        let config = &self.tree_config;
+        let player = node.player as usize;
+        let num_active = 3 - node.folded.count_ones() as i32;
+
+        let prev_amount = info.prev_amount;
+        let to_call = prev_amount - info.bets[player];
+        let pot = config.starting_pot + node.amount.iter().sum::<i32>() + to_call;
+        let max_amount = info.stack;
+        let min_amount = (prev_amount + info.min_raise).clamp(1, max_amount);
+
+        let bet_options = match info.board_state {
+            BoardState::Flop => &config.flop_bet_sizes[player],
+            BoardState::Turn => &config.turn_bet_sizes[player],
+            BoardState::River => &config.river_bet_sizes[player],
+        };
+
+        let mut actions = Vec::new();
+
+        if prev_amount == 0 {
+            // check
+            actions.push(Action::Check);
+
+            // bet
+            for &bet_size in &bet_options.bet {
+                match bet_size {
+                    BetSize::PotRelative(ratio) => {
+                        let amount = (pot as f64 * ratio).round() as i32;
+                        actions.push(Action::Bet(amount));
+                    }
+                    BetSize::Additive(adder, _) => actions.push(Action::Bet(adder)),
+                    BetSize::AllIn => actions.push(Action::AllIn(max_amount)),
+                    _ => panic!("Unexpected bet size: {bet_size:?}"),
+                }
+            }
+
+            // all-in
+            if max_amount <= (pot as f64 * config.add_allin_threshold).round() as i32 {
+                actions.push(Action::AllIn(max_amount));
+            }
+        } else {
+            // fold
+            actions.push(Action::Fold);
+
+            // call
+            actions.push(Action::Call);
+
+            if prev_amount < max_amount {
+                // raise
+                for &bet_size in &bet_options.raise {
+                    match bet_size {
+                        BetSize::PotRelative(ratio) => {
+                            let amount = prev_amount + (pot as f64 * ratio).round() as i32;
+                            actions.push(Action::Raise(amount));
+                        }
+                        BetSize::PrevBetRelative(ratio) => {
+                            let amount = (prev_amount as f64 * ratio).round() as i32;
+                            actions.push(Action::Raise(amount));
+                        }
+                        BetSize::Additive(adder, raise_cap) => {
+                            if raise_cap == 0 || info.num_bets <= raise_cap {
+                                actions.push(Action::Raise(prev_amount + adder));
+                            }
+                        }
+                        BetSize::AllIn => actions.push(Action::AllIn(max_amount)),
+                        _ => panic!("Unexpected bet size: {bet_size:?}"),
+                    }
+                }
+
+                // all-in
+                let allin_threshold = pot as f64 * config.add_allin_threshold;
+                if max_amount <= prev_amount + allin_threshold.round() as i32 {
+                    actions.push(Action::AllIn(max_amount));
+                }
+            }
+        }
+
+        let is_above_threshold = |amount: i32| {
+            let new_pot = pot + num_active * (amount - prev_amount);
+            let threshold = (new_pot as f64 * config.force_allin_threshold).round() as i32;
+            max_amount <= amount + threshold
+        };
+
+        // clamp bet amounts
+        for action in actions.iter_mut() {
+            match *action {
+                Action::Bet(amount) => {
+                    let clamped = amount.clamp(min_amount, max_amount);
+                    if is_above_threshold(clamped) {
+                        *action = Action::AllIn(max_amount);
+                    } else if clamped != amount {
+                        *action = Action::Bet(clamped);
+                    }
+                }
+                Action::Raise(amount) => {
+                    let clamped = amount.clamp(min_amount, max_amount);
+                    if is_above_threshold(clamped) {
+                        *action = Action::AllIn(max_amount);
+                    } else if clamped != amount {
+                        *action = Action::Raise(clamped);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        // remove duplicates
+        actions.sort_unstable();
+        actions.dedup();
+
+        // merge bet actions with close amounts
+        merge_bet_actions(actions, pot, prev_amount, config.merging_threshold)
+    }
+}
+
+/// Returns the opponents of the given player in ascending order.
+#[inline]
+pub(super) fn opponents(player: usize) -> [usize; 2] {
+    match player {
+        0 => [1, 2],
+        1 => [0, 2],
+        _ => [0, 1],
+    }
+}
+
+fn check_tree_config(config: &MultiwayTreeConfig) -> Result<(), String> {
+    if config.starting_pot <= 0 {
+        return Err(format!(
+            "Starting pot must be positive: {}",
+            config.starting_pot
+        ));
+    }
+
+    if config.effective_stack <= 0 {
+        return Err(format!(
+            "Effective stack must be positive: {}",
+            config.effective_stack
+        ));
+    }
+
+    if !(0.0..=1.0).contains(&config.rake_rate) {
+        return Err(format!(
+            "Rake rate must be between 0.0 and 1.0: {}",
+            config.rake_rate
+        ));
+    }
+
+    if config.rake_cap < 0.0 {
+        return Err(format!(
+            "Rake cap must be non-negative: {}",
+            config.rake_cap
+        ));
+    }
+
+    if config.add_allin_threshold < 0.0
+        || config.force_allin_threshold < 0.0
+        || config.merging_threshold < 0.0
+    {
+        return Err("Thresholds must be non-negative".to_string());
+    }
+
+    let bet_sizes = [
+        &config.flop_bet_sizes,
+        &config.turn_bet_sizes,
+        &config.river_bet_sizes,
+    ];
+
+    for options in bet_sizes.iter().flat_map(|options| options.iter()) {
+        if options
+            .bet
+            .iter()
+            .chain(&options.raise)
+            .any(|size| matches!(size, BetSize::Geometric(..)))
+        {
+            return Err("Geometric bet sizes are not supported in multiway games".to_string());
+        }
+    }
+
+    Ok(())
+}
+
+fn visit_nodes_mut(node: &mut MultiwayNode, op: &mut impl FnMut(&mut MultiwayNode)) {
+    op(node);
+    for child in &node.children {
+        visit_nodes_mut(&mut child.lock(), op);
+    }
+}
+
+fn memory_usage_recursive(node: &MultiwayNode) -> u64 {
+    let mut ret = vec_memory_usage(&node.children)
+        + vec_memory_usage(&node.strategy)
+        + vec_memory_usage(&node.regrets)
+        + vec_memory_usage(&node.predictions);
+    for child in &node.children {
+        ret += memory_usage_recursive(&child.lock());
+    }
+    ret
+}
@@ -0,0 +1,182 @@
+use super::base::opponents;
+use super::*;
+use std::mem::MaybeUninit;
+
+#[inline]
+fn min(x: f64, y: f64) -> f64 {
+    if x < y {
+        x
+    } else {
+        y
+    }
+}
+
+impl MultiwayGame {
+    pub(super) fn evaluate_internal(
+        &self,
+        result: &mut [MaybeUninit<f32>],
+        node: &MultiwayNode,
+        player: usize,
+        cfreach: &[f32],
+    ) {
+        let config = &self.tree_config;
+        let pot = (config.starting_pot + node.amount.iter().sum::<i32>()) as f64;
+        let rake = min(pot * config.rake_rate, config.rake_cap);
+        let bias = config.starting_pot as f64 / MULTIWAY_NUM_PLAYERS as f64;
+        let amount_lose = -(node.amount[player] as f64 + bias);
+
+        let is_active = |p: usize| node.folded & (1 << p) == 0;
+        let num_active = (0..MULTIWAY_NUM_PLAYERS).filter(|&p| is_active(p)).count();
+        let is_showdown = num_active > 1 && is_active(player);
+
+        // payoff[i][j]: the first and second opponents are in the i-th and j-th categories
+        let mut payoff = [[amount_lose; 3]; 3];
+        if num_active == 1 && is_active(player) {
+            payoff = [[pot - rake + amount_lose; 3]; 3];
+        } else if is_showdown {
+            let active = opponents(player).map(is_active);
+            for (i, row) in payoff.iter_mut().enumerate() {
+                for (j, value) in row.iter_mut().enumerate() {
+                    let categories = [i, j];
+                    let count = |category: usize| {
+                        (0..2)
+                            .filter(|&k| active[k] && categories[k] == category)
+                            .count()
+                    };
+                    if count(2) == 0 {
+                        *value += (pot - rake) / (count(1) + 1) as f64;
+                    }
+                }
+            }
+        }
+
+        result.iter_mut().for_each(|v| {
+            v.write(0.0);
+        });
+
+        let result = unsafe { &mut *(result as *mut _ as *mut [f32]) };
+
+        let mut board_mask = self.initial_board_mask();
+        if node.turn != NOT_DEALT {
+            board_mask |= 1 << node.turn;
+        }
+        if node.river != NOT_DEALT {
+            board_mask |= 1 << node.river;
+        }
+
+        let strength = if is_showdown {
+            Some(&self.hand_strength[card_pair_to_index(node.turn, node.river)])
+        } else {
+            None
+        };
+
+        let num_combinations = self.num_combinations;
+        self.sum_over_opponents(
+            player,
+            cfreach,
+            board_mask,
+            strength,
+            &payoff,
+            |index, value| result[index] = (value / num_combinations) as f32,
+        );
+    }
+
+    /// Computes the sum of `cfreach[a] * cfreach[b] * payoff[category(a)][category(b)]` over all
+    /// pairs of the opponents' hands `(a, b)` that conflict with neither a hand of `player` nor
+    /// `board_mask`, and passes it to `write` with the index of the hand of `player`.
+    ///
+    /// The category of an opponent's hand is `0`, `1`, or `2` if it is weaker than, as strong as,
+    /// or stronger than the hand of `player` with respect to `strength`, and always `0` if
+    /// `strength` is `None`. Hands of `player` that conflict with `board_mask` are skipped.
+    ///
+    /// **Time complexity:** *O*(#(hands of `player`) * #(hands of the opponents)).
+    pub(super) fn sum_over_opponents(
+        &self,
+        player: usize,
+        cfreach: &[f32],
+        board_mask: u64,
+        strength: Option<&[Vec<u16>; 3]>,
+        payoff: &[[f64; 3]; 3],
+        mut write: impl FnMut(usize, f64),
+    ) {
+        let [opponent_a, opponent_b] = opponents(player);
+        let (cfreach_a, cfreach_b) = cfreach.split_at(self.private_cards[opponent_a].len());
+        let cards_b = &self.private_cards[opponent_b];
+        let hand_index_b = &self.hand_index[opponent_b];
+
+        let strength_of = |p: usize, index: usize| strength.map_or(0, |s| s[p][index]);
+        let category = |s: u16, other: u16| match other.cmp(&s) {
+            std::cmp::Ordering::Less => 0,
+            std::cmp::Ordering::Equal => 1,
+            std::cmp::Ordering::Greater => 2,
+        };
+
+        // sums of the reach probabilities of the second opponent for each category, and those
+        // containing each card
+        let mut cfreach_sum = [0.0; 3];
+        let mut cfreach_minus = [[0.0; 52]; 3];
+        let mut is_sum_computed = false;
+
+        for (index, &(c1, c2)) in self.private_cards[player].iter().enumerate() {
+            let player_mask: u64 = (1 << c1) | (1 << c2);
+            if player_mask & board_mask != 0 {
+                continue;
+            }
+
+            let s = strength_of(player, index);
+
+            if !is_sum_computed || strength.is_some() {
+                is_sum_computed = true;
+                cfreach_sum = [0.0; 3];
+                cfreach_minus = [[0.0; 52]; 3];
+                for (index_b, &(c3, c4)) in cards_b.iter().enumerate() {
+                    let cfreach_i = cfreach_b[index_b];
+                    let mask: u64 = (1 << c3) | (1 << c4);
+                    if cfreach_i != 0.0 && mask & board_mask == 0 {
+                        let k = category(s, strength_of(opponent_b, index_b));
+                        let cfreach_i = cfreach_i as f64;
+                        cfreach_sum[k] += cfreach_i;
+                        cfreach_minus[k][c3 as usize] += cfreach_i;
+                        cfreach_minus[k][c4 as usize] += cfreach_i;
+                    }
+                }
+            }
+
+            let mut value = 0.0;
+
+            for (index_a, &(c3, c4)) in self.private_cards[opponent_a].iter().enumerate() {
+                let cfreach_i = cfreach_a[index_a];
+                let mask: u64 = (1 << c3) | (1 << c4);
+                if cfreach_i == 0.0 || mask & (board_mask | player_mask) != 0 {
+                    continue;
+                }
+
+                // inclusion-exclusion principle over the four cards of `player` and the first
+                // opponent; a hand of the second opponent contains at most two of them
+                let cards = [c1, c2, c3, c4];
+                let mut sum = cfreach_sum;
+                for card in cards {
+                    for (x, minus) in sum.iter_mut().zip(&cfreach_minus) {
+                        *x -= minus[card as usize];
+                    }
+                }
+                for i in 0..4 {
+                    for j in i + 1..4 {
+                        let index_b = hand_index_b[card_pair_to_index(cards[i], cards[j])];
+                        if index_b != u16::MAX {
+                            let index_b = index_b as usize;
+                            let k = category(s, strength_of(opponent_b, index_b));
+                            sum[k] += cfreach_b[index_b] as f64;
+                        }
+                    }
+                }
+
+                let row = &payoff[category(s, strength_of(opponent_a, index_a))];
+                let payoff_sum = sum.iter().zip(row).map(|(x, y)| x * y).sum::<f64>();
+                value += cfreach_i as f64 * payoff_sum;
+            }
+
+            write(index, value);
+        }
+    }
+}
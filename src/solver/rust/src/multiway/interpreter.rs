@@ -0,0 +1,273 @@
+use super::base::opponents;
+use super::*;
+use crate::interface::*;
+use crate::utility::*;
+
+impl MultiwayGame {
+    /// Moves the current node back to the root node.
+    #[inline]
+    pub fn back_to_root(&mut self) {
+        self.action_history.clear();
+        self.turn = self.card_config.turn;
+        self.river = self.card_config.river;
+        self.weights = self.initial_weights.clone();
+    }
+
+    /// Returns the history of the current node, i.e., the arguments of [`play`].
+    ///
+    /// [`play`]: #method.play
+    #[inline]
+    pub fn history(&self) -> &[usize] {
+        &self.action_history
+    }
+
+    /// Applies the given history from the root node.
+    #[inline]
+    pub fn apply_history(&mut self, history: &[usize]) {
+        self.back_to_root();
+        for &action in history {
+            self.play(action);
+        }
+    }
+
+    /// Returns whether the current node is a terminal node.
+    #[inline]
+    pub fn is_terminal_node(&self) -> bool {
+        self.node().is_terminal()
+    }
+
+    /// Returns whether the current node is a chance node (i.e., turn/river node).
+    ///
+    /// Unlike [`PostFlopGame`], the turn/river node after all players are all-in is also a chance
+    /// node.
+    ///
+    /// [`PostFlopGame`]: crate::PostFlopGame
+    #[inline]
+    pub fn is_chance_node(&self) -> bool {
+        self.node().is_chance()
+    }
+
+    /// Returns the available actions for the current node.
+    ///
+    /// If the current node is a chance node, the dealing of each possible card is returned.
+    #[inline]
+    pub fn available_actions(&self) -> Vec<Action> {
+        self.node()
+            .children
+            .iter()
+            .map(|child| child.lock().prev_action)
+            .collect()
+    }
+
+    /// If the current node is a chance node, returns a list of cards that can be dealt as a 64-bit
+    /// integer (see [`PostFlopGame::possible_cards`]). Otherwise, `0` is returned.
+    ///
+    /// [`PostFlopGame::possible_cards`]: crate::PostFlopGame::possible_cards
+    pub fn possible_cards(&self) -> u64 {
+        self.available_actions()
+            .into_iter()
+            .fold(0, |mask, action| match action {
+                Action::Chance(card) => mask | (1 << card),
+                _ => mask,
+            })
+    }
+
+    /// Returns the current player (0, 1, or 2).
+    ///
+    /// If the current node is a terminal node or a chance node, returns an undefined value.
+    #[inline]
+    pub fn current_player(&self) -> usize {
+        self.node().player()
+    }
+
+    /// Returns whether each player has folded.
+    #[inline]
+    pub fn folded_players(&self) -> [bool; 3] {
+        let folded = self.node().folded;
+        [0, 1, 2].map(|player| folded & (1 << player) != 0)
+    }
+
+    /// Returns the current board.
+    #[inline]
+    pub fn current_board(&self) -> Vec<Card> {
+        let mut ret = self.card_config.flop.to_vec();
+        if self.turn != NOT_DEALT {
+            ret.push(self.turn);
+        }
+        if self.river != NOT_DEALT {
+            ret.push(self.river);
+        }
+        ret
+    }
+
+    /// Returns the total bet amount of each player since the root node.
+    #[inline]
+    pub fn total_bet_amount(&self) -> [i32; 3] {
+        self.node().amount
+    }
+
+    /// Plays the given action. Playing an action from a terminal node is not allowed.
+    ///
+    /// If the current node is a chance node, the `action` corresponds to the dealt card (see
+    /// [`Card`] for encoding). Otherwise, plays the `action`-th action of [`available_actions`].
+    ///
+    /// [`available_actions`]: #method.available_actions
+    pub fn play(&mut self, action: usize) {
+        if self.is_terminal_node() {
+            panic!("Terminal node is not allowed");
+        }
+
+        if self.is_chance_node() {
+            if self.possible_cards() & (1 << action) == 0 {
+                panic!("Invalid card: {action}");
+            }
+
+            let card = action as Card;
+            if self.turn == NOT_DEALT {
+                self.turn = card;
+            } else {
+                self.river = card;
+            }
+
+            for player in 0..MULTIWAY_NUM_PLAYERS {
+                let weights = &mut self.weights[player];
+                for (weight, &(c1, c2)) in weights.iter_mut().zip(&self.private_cards[player]) {
+                    if c1 == card || c2 == card {
+                        *weight = 0.0;
+                    }
+                }
+            }
+        } else {
+            let node = self.node();
+            let num_actions = node.num_actions();
+            if action >= num_actions {
+                panic!("Invalid action: {action}");
+            }
+
+            let player = node.player();
+            let num_hands = self.num_private_hands(player);
+            let strategy = normalized_strategy(node.strategy(), num_actions);
+            let row = &strategy[action * num_hands..(action + 1) * num_hands];
+
+            self.weights[player]
+                .iter_mut()
+                .zip(row)
+                .for_each(|(weight, &probability)| *weight *= probability);
+        }
+
+        self.action_history.push(action);
+    }
+
+    /// Returns the reach probabilities of each private hand of the given player at the current
+    /// node, i.e., the initial weights multiplied by the probabilities of the actions taken.
+    #[inline]
+    pub fn weights(&self, player: usize) -> &[f32] {
+        &self.weights[player]
+    }
+
+    /// Returns the average strategy of the current player.
+    ///
+    /// The probability of the `i`-th action with the `j`-th private hand is stored in the
+    /// `i * #(private hands) + j`-th element.
+    ///
+    /// Panics if the current node is a terminal node or a chance node.
+    pub fn strategy(&self) -> Vec<f32> {
+        if self.is_terminal_node() || self.is_chance_node() {
+            panic!("Terminal node and chance node are not allowed");
+        }
+
+        let node = self.node();
+        normalized_strategy(node.strategy(), node.num_actions())
+    }
+
+    /// Returns the expected value of each private hand of the current player.
+    ///
+    /// As in [`PostFlopGame::expected_values`], the expected value is the amount of chips the
+    /// player is expected to get from the pot, including the share of the starting pot and the
+    /// bets already made. Hands that cannot be combined with any hands of the opponents have the
+    /// value of `0`.
+    ///
+    /// Panics if the game is not solved, or if the current node is a terminal node or a chance
+    /// node.
+    ///
+    /// [`PostFlopGame::expected_values`]: crate::PostFlopGame::expected_values
+    pub fn expected_values(&self) -> Vec<f32> {
+        if !self.is_solved {
+            panic!("Game is not solved");
+        }
+
+        let strategy = self.strategy();
+        let node = self.node();
+        let player = node.player();
+        let num_hands = self.num_private_hands(player);
+
+        let mut chance_factor = 1.0;
+        if self.card_config.turn == NOT_DEALT && self.turn != NOT_DEALT {
+            chance_factor *= 43.0;
+        }
+        if self.card_config.river == NOT_DEALT && self.river != NOT_DEALT {
+            chance_factor *= 42.0;
+        }
+
+        let mut board_mask = self.initial_board_mask();
+        if self.turn != NOT_DEALT {
+            board_mask |= 1 << self.turn;
+        }
+        if self.river != NOT_DEALT {
+            board_mask |= 1 << self.river;
+        }
+
+        let cfreach = opponents(player)
+            .iter()
+            .flat_map(|&opponent| self.weights[opponent].iter().cloned())
+            .collect::<Vec<_>>();
+
+        let bias = self.tree_config.starting_pot as f64 / MULTIWAY_NUM_PLAYERS as f64;
+        let offset = bias + node.amount[player] as f64;
+        let normalizer = self.num_combinations * chance_factor;
+
+        let mut ret = vec![0.0; num_hands];
+        self.sum_over_opponents(
+            player,
+            &cfreach,
+            board_mask,
+            None,
+            &[[1.0; 3]; 3],
+            |index, cfreach_sum| {
+                if cfreach_sum > 0.0 {
+                    let cfvalue = strategy
+                        .iter()
+                        .skip(index)
+                        .step_by(num_hands)
+                        .zip(node.cfvalues().iter().skip(index).step_by(num_hands))
+                        .fold(0.0, |acc, (&s, &v)| acc + s as f64 * v as f64);
+                    ret[index] = (cfvalue * normalizer / cfreach_sum + offset) as f32;
+                }
+            },
+        );
+
+        ret
+    }
+
+    /// Returns the current node.
+    fn node(&self) -> MutexGuardLike<'_, MultiwayNode> {
+        let mut node: &MutexLike<MultiwayNode> = &self.root;
+        for &action in &self.action_history {
+            let guard = node.lock();
+            let index = if guard.is_chance() {
+                let card = Action::Chance(action as Card);
+                let position = guard
+                    .children
+                    .iter()
+                    .position(|c| c.lock().prev_action == card);
+                position.unwrap()
+            } else {
+                action
+            };
+            // This is safe because the children are owned by the game tree, which is not modified
+            // while `self` is borrowed.
+            node = unsafe { &*(&guard.children[index] as *const MutexLike<MultiwayNode>) };
+        }
+        node.lock()
+    }
+}
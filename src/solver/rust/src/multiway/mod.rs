@@ -0,0 +1,151 @@
+mod base;
+mod evaluation;
+mod interpreter;
+mod node;
+
+#[cfg(test)]
+mod tests;
+
+use crate::action_tree::*;
+use crate::bet_size::*;
+use crate::card::*;
+use crate::mutex_like::*;
+use crate::range::*;
+
+/// The number of players of [`MultiwayGame`].
+pub const MULTIWAY_NUM_PLAYERS: usize = 3;
+
+const PLAYER_CHANCE: u8 = 3;
+const PLAYER_TERMINAL: u8 = 4;
+
+/// A struct containing the card configuration of a three-way game.
+///
+/// The same as [`CardConfig`], but with a range of each of the three players.
+#[derive(Debug, Clone)]
+pub struct MultiwayCardConfig {
+    /// Initial range of each player, in order of action.
+    pub range: [Range; 3],
+
+    /// Flop cards: each card must be unique.
+    pub flop: [Card; 3],
+
+    /// Turn card: must be in range [`0`, `52`) or `NOT_DEALT`.
+    pub turn: Card,
+
+    /// River card: must be in range [`0`, `52`) or `NOT_DEALT`.
+    pub river: Card,
+}
+
+impl Default for MultiwayCardConfig {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            range: Default::default(),
+            flop: [NOT_DEALT; 3],
+            turn: NOT_DEALT,
+            river: NOT_DEALT,
+        }
+    }
+}
+
+/// A struct containing the game tree configuration of a three-way game.
+///
+/// The fields have the same meaning as in [`TreeConfig`], except that there are no donk sizes and
+/// that geometric bet sizes are not supported. All players start with the same effective stack,
+/// so there are never side pots.
+#[derive(Debug, Clone, Default)]
+pub struct MultiwayTreeConfig {
+    /// Initial state of the game tree (flop, turn, or river).
+    pub initial_state: BoardState,
+
+    /// Starting pot size. Must be greater than `0`.
+    pub starting_pot: i32,
+
+    /// Initial effective stack. Must be greater than `0`.
+    pub effective_stack: i32,
+
+    /// Rake rate. Must be between `0.0` and `1.0`, inclusive.
+    pub rake_rate: f64,
+
+    /// Rake cap. Must be non-negative.
+    pub rake_cap: f64,
+
+    /// Bet size options of each player for the flop.
+    pub flop_bet_sizes: [BetSizeOptions; 3],
+
+    /// Bet size options of each player for the turn.
+    pub turn_bet_sizes: [BetSizeOptions; 3],
+
+    /// Bet size options of each player for the river.
+    pub river_bet_sizes: [BetSizeOptions; 3],
+
+    /// Add all-in action if the ratio of maximum bet size to the pot is below or equal to this
+    /// value (set `0.0` to disable).
+    pub add_allin_threshold: f64,
+
+    /// Force all-in action if the SPR (stack/pot) after the calls is below or equal to this value
+    /// (set `0.0` to disable).
+    pub force_allin_threshold: f64,
+
+    /// Merge bet actions if there are bet actions with "close" values (set `0.0` to disable).
+    pub merging_threshold: f64,
+}
+
+/// A struct representing a three-way postflop game.
+///
+/// Players act in ascending order of the player index on every street, and a folded player is
+/// skipped until the end of the hand. The game implements [`Game`] and is solved with the same
+/// functions as [`PostFlopGame`], e.g., [`solve`]. Note, however, that the result is not a Nash
+/// equilibrium in general since the game is not a two-player zero-sum game; the exploitability is
+/// the average gain of each player by switching to the best response.
+///
+/// Unlike [`PostFlopGame`], the game does not merge isomorphic chances and always stores the
+/// strategy in 32-bit floating point numbers. The evaluation of each terminal node takes
+/// *O*(#(hands of the player) * #(hands of the opponents)) time.
+///
+/// [`Game`]: crate::Game
+/// [`PostFlopGame`]: crate::PostFlopGame
+/// [`solve`]: crate::solve
+#[derive(Default)]
+pub struct MultiwayGame {
+    // configurations
+    card_config: MultiwayCardConfig,
+    tree_config: MultiwayTreeConfig,
+    root: Box<MutexLike<MultiwayNode>>,
+
+    // computed from configurations
+    num_combinations: f64,
+    initial_weights: [Vec<f32>; 3],
+    opponent_weights: [Vec<f32>; 3],
+    private_cards: [Vec<(Card, Card)>; 3],
+    num_nodes: u64,
+    is_solved: bool,
+    num_iterations: u32,
+
+    // index of each card pair in `private_cards` (`u16::MAX` if not in the range)
+    hand_index: [Vec<u16>; 3],
+
+    // strength of each hand indexed by the turn and river cards (`0` if the hand overlaps)
+    hand_strength: Vec<[Vec<u16>; 3]>,
+
+    // result interpreter
+    action_history: Vec<usize>,
+    turn: Card,
+    river: Card,
+    weights: [Vec<f32>; 3],
+}
+
+/// A struct representing a node in a three-way game tree.
+#[derive(Debug, Default)]
+pub struct MultiwayNode {
+    prev_action: Action,
+    player: u8,
+    turn: Card,
+    river: Card,
+    folded: u8,
+    amount: [i32; 3],
+    children: Vec<MutexLike<MultiwayNode>>,
+    strategy: Vec<f32>,
+    regrets: Vec<f32>, // regrets or cfvalues
+    predictions: Vec<f32>,
+}
@@ -0,0 +1,194 @@
+use super::*;
+use crate::interface::*;
+use crate::solver::*;
+use crate::utility::*;
+
+fn river_card_config(ranges: [&str; 3]) -> MultiwayCardConfig {
+    MultiwayCardConfig {
+        range: ranges.map(|range| range.parse().unwrap()),
+        flop: flop_from_str("KhKd7c").unwrap(),
+        turn: card_from_str("4s").unwrap(),
+        river: card_from_str("2d").unwrap(),
+    }
+}
+
+#[test]
+fn multiway_tree() {
+    let bet_sizes: BetSizeOptions = ("50%", "a").try_into().unwrap();
+    let tree_config = MultiwayTreeConfig {
+        initial_state: BoardState::River,
+        starting_pot: 90,
+        effective_stack: 300,
+        river_bet_sizes: [bet_sizes.clone(), bet_sizes.clone(), bet_sizes],
+        ..Default::default()
+    };
+
+    let card_config = river_card_config(["AA", "QQ", "JJ"]);
+    let mut game = MultiwayGame::with_config(card_config, tree_config.clone()).unwrap();
+
+    assert_eq!(game.current_player(), 0);
+    assert_eq!(
+        game.available_actions(),
+        vec![Action::Check, Action::Bet(45)]
+    );
+
+    // all check
+    game.apply_history(&[0, 0]);
+    assert_eq!(game.current_player(), 2);
+    game.play(0);
+    assert!(game.is_terminal_node());
+
+    // check, bet, fold, call
+    game.apply_history(&[0, 1]);
+    assert_eq!(game.current_player(), 2);
+    assert_eq!(
+        game.available_actions(),
+        vec![Action::Fold, Action::Call, Action::AllIn(300)]
+    );
+    game.play(0);
+    assert_eq!(game.current_player(), 0);
+    assert_eq!(game.folded_players(), [false, false, true]);
+    game.play(1);
+    assert!(game.is_terminal_node());
+    assert_eq!(game.total_bet_amount(), [45, 45, 0]);
+
+    // bet, fold, fold
+    game.apply_history(&[1, 0, 0]);
+    assert!(game.is_terminal_node());
+    assert_eq!(game.folded_players(), [false, true, true]);
+
+    // bet, call, all-in, call
+    game.apply_history(&[1, 1, 2]);
+    assert_eq!(game.current_player(), 0);
+    assert_eq!(game.available_actions(), vec![Action::Fold, Action::Call]);
+    game.play(1);
+    assert_eq!(game.current_player(), 1);
+    game.play(0);
+    assert!(game.is_terminal_node());
+    assert_eq!(game.total_bet_amount(), [300, 45, 300]);
+
+    let tree_config = MultiwayTreeConfig {
+        river_bet_sizes: [
+            ("2e", "").try_into().unwrap(),
+            Default::default(),
+            Default::default(),
+        ],
+        ..tree_config
+    };
+    let card_config = river_card_config(["AA", "QQ", "JJ"]);
+    assert!(MultiwayGame::with_config(card_config, tree_config).is_err());
+
+    let card_config = river_card_config(["AsAh", "AsAh", "AdAc"]);
+    let tree_config = MultiwayTreeConfig {
+        initial_state: BoardState::River,
+        starting_pot: 90,
+        effective_stack: 300,
+        ..Default::default()
+    };
+    assert!(MultiwayGame::with_config(card_config, tree_config).is_err());
+}
+
+#[test]
+fn multiway_showdown() {
+    let tree_config = MultiwayTreeConfig {
+        initial_state: BoardState::River,
+        starting_pot: 90,
+        effective_stack: 300,
+        ..Default::default()
+    };
+
+    // the first player wins the whole pot
+    let card_config = river_card_config(["AsAh", "QsQh", "JsJh"]);
+    let mut game = MultiwayGame::with_config(card_config, tree_config.clone()).unwrap();
+    finalize(&mut game);
+    assert_eq!(compute_current_ev_multiway(&game), vec![60.0, -30.0, -30.0]);
+    let index = game.weights(0).iter().position(|&w| w > 0.0).unwrap();
+    assert_eq!(game.expected_values()[index], 90.0);
+
+    // the second and third players split the pot
+    let card_config = river_card_config(["3c3d", "8c8d", "8h8s"]);
+    let mut game = MultiwayGame::with_config(card_config, tree_config.clone()).unwrap();
+    finalize(&mut game);
+    assert_eq!(compute_current_ev_multiway(&game), vec![-30.0, 15.0, 15.0]);
+
+    // symmetric ranges
+    let card_config = river_card_config(["AA,QQ,88,33", "AA,QQ,88,33", "AA,QQ,88,33"]);
+    let mut game = MultiwayGame::with_config(card_config, tree_config).unwrap();
+    finalize(&mut game);
+    let ev = compute_current_ev_multiway(&game);
+    assert!(ev.iter().all(|ev| ev.abs() < 1e-4));
+    assert!(compute_exploitability(&game).abs() < 1e-4);
+}
+
+#[test]
+fn multiway_solve() {
+    let bet_sizes: BetSizeOptions = ("50%, a", "").try_into().unwrap();
+    let tree_config = MultiwayTreeConfig {
+        initial_state: BoardState::Turn,
+        starting_pot: 90,
+        effective_stack: 300,
+        turn_bet_sizes: [bet_sizes.clone(), bet_sizes.clone(), bet_sizes.clone()],
+        river_bet_sizes: [bet_sizes.clone(), bet_sizes.clone(), bet_sizes],
+        ..Default::default()
+    };
+
+    let card_config = MultiwayCardConfig {
+        range: [
+            "AA,KQs,JJ,T9s".parse().unwrap(),
+            "QQ,AKs,99,87s".parse().unwrap(),
+            "TT,AQs,88,65s".parse().unwrap(),
+        ],
+        flop: flop_from_str("Td9d6h").unwrap(),
+        turn: card_from_str("Qc").unwrap(),
+        river: NOT_DEALT,
+    };
+
+    let mut game = MultiwayGame::with_config(card_config, tree_config).unwrap();
+    assert!(game.memory_usage() > 0);
+
+    let initial = compute_exploitability(&game);
+    let exploitability = solve_with(&mut game, CfrAlgorithm::PredictiveCfrPlus, 100, 0.0, false);
+    assert!(exploitability < initial * 0.2);
+
+    // zero-sum without rake
+    let ev = compute_current_ev_multiway(&game);
+    assert!(ev.iter().sum::<f32>().abs() < 1e-3);
+
+    // the strategy is normalized and the root values match the expected values
+    let strategy = game.strategy();
+    let num_hands = game.private_cards(0).len();
+    for hand in 0..num_hands {
+        let sum = strategy.iter().skip(hand).step_by(num_hands).sum::<f32>();
+        assert!((sum - 1.0).abs() < 1e-5);
+    }
+
+    let expected_values = game.expected_values();
+    let mut ev_sum = 0.0;
+    game.sum_over_opponents(
+        0,
+        game.initial_opponent_weights(0),
+        game.initial_board_mask(),
+        None,
+        &[[1.0; 3]; 3],
+        |index, cfreach_sum| {
+            let weight = game.initial_weights(0)[index] as f64;
+            ev_sum += weight * cfreach_sum * (expected_values[index] as f64 - 30.0);
+        },
+    );
+    assert!((ev_sum / game.num_combinations - ev[0] as f64).abs() < 1e-3);
+
+    // deal a river card after everyone checks
+    game.apply_history(&[0, 0, 0]);
+    assert!(game.is_chance_node());
+    let possible_cards = game.possible_cards();
+    assert_eq!(possible_cards.count_ones(), 48);
+    let card = card_from_str("As").unwrap();
+    game.play(card as usize);
+    assert_eq!(game.current_player(), 0);
+    assert_eq!(game.current_board().len(), 5);
+    for (&weight, &(c1, c2)) in game.weights(0).iter().zip(game.private_cards(0)) {
+        if c1 == card || c2 == card {
+            assert_eq!(weight, 0.0);
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use super::*;
+
+/// A canonical flop and the number of flops it represents
+#[napi(object)]
+pub struct FlopWeight {
+    pub flop: String,
+    pub weight: f64,
+}
+
+/// Returns a representative subset of canonical flops with weights summing to 22,100
+///
+/// Typical sizes are 25, 49, 95, and 184; all 1,755 canonical flops are returned if `size` is
+/// absent. With `shortDeck`, only the flops of the 36-card deck are used and the weights sum to
+/// 7,140.
+#[napi]
+pub fn get_flop_subset(size: Option<u32>, short_deck: Option<bool>) -> Result<Vec<FlopWeight>> {
+    let deck = if short_deck.unwrap_or(false) { Deck::ShortDeck } else { Deck::Standard };
+    let flops = match size {
+        Some(size) => flop_subset(size as usize, deck).map_err(Error::from_reason)?,
+        None => weighted_canonical_flops()
+            .into_iter()
+            .filter(|weighted| weighted.flop.iter().all(|&card| deck.contains(card)))
+            .collect(),
+    };
+
+    flops
+        .iter()
+        .map(|weighted| {
+            Ok(FlopWeight {
+                flop: flop_to_string(weighted.flop).map_err(Error::from_reason)?,
+                weight: weighted.weight,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,2462 @@
+// Node.js bindings for the postflop-solver
+
+use napi::bindgen_prelude::*;
+use napi::threadsafe_function::{
+    ErrorStrategy, ThreadSafeCallContext, ThreadsafeFunction, ThreadsafeFunctionCallMode,
+};
+use napi::JsFunction;
+use napi_derive::napi;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, TryLockError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+// Import all the postflop-solver modules
+use crate::*;
+
+// Free functions of the bindings. napi registers them only outside of test builds, so they are
+// dead code there.
+#[cfg_attr(test, allow(dead_code))]
+mod flop_subset;
+#[cfg_attr(test, allow(dead_code))]
+mod pio_import;
+#[cfg_attr(test, allow(dead_code))]
+mod range_builder;
+#[cfg_attr(test, allow(dead_code))]
+mod range_equity;
+#[cfg_attr(test, allow(dead_code))]
+mod tournament;
+
+/// Bet and raise sizes of both players for one street
+///
+/// Sizes use the comma-separated format of `BetSizeOptions` (e.g. "33%,75%,a").
+/// Missing entries fall back to `GameConfig::bet_sizes` and `GameConfig::raise_sizes`.
+#[napi(object)]
+pub struct StreetBetSizes {
+    pub oop_bet: Option<String>,
+    pub oop_raise: Option<String>,
+    pub ip_bet: Option<String>,
+    pub ip_raise: Option<String>,
+}
+
+/// Configuration for creating a game
+#[napi(object)]
+pub struct GameConfig {
+    pub starting_pot: i32,
+    pub effective_stack: i32,
+    pub oop_range: String,
+    pub ip_range: String,
+    pub flop: String,
+    pub turn: Option<String>,
+    pub river: Option<String>,
+    /// Default bet sizes for every street and player (default: "60%,100%,a")
+    pub bet_sizes: Option<String>,
+    /// Default raise sizes for every street and player (default: "2.5x")
+    pub raise_sizes: Option<String>,
+    pub flop_sizes: Option<StreetBetSizes>,
+    pub turn_sizes: Option<StreetBetSizes>,
+    pub river_sizes: Option<StreetBetSizes>,
+    pub turn_donk_sizes: Option<String>,
+    pub river_donk_sizes: Option<String>,
+    /// Rake rate between 0 and 1 (default: 0)
+    pub rake_rate: Option<f64>,
+    /// Rake cap in chips (default: 0)
+    pub rake_cap: Option<f64>,
+    /// Default: 1.5
+    pub add_allin_threshold: Option<f64>,
+    /// Default: 0.15
+    pub force_allin_threshold: Option<f64>,
+    /// Default: 0.1
+    pub merging_threshold: Option<f64>,
+    /// Whether to compress the storage (default: chosen by `memory_budget`, else false)
+    pub compression: Option<bool>,
+    /// Memory budget in bytes; `init_game` fails if the game does not fit
+    pub memory_budget: Option<f64>,
+    /// Pays tournament equity instead of chips at the end of the hand
+    pub tournament: Option<TournamentOptions>,
+    /// Plays 36-card short-deck hold'em (default: false)
+    pub short_deck: Option<bool>,
+}
+
+/// Tournament payoff model of a game
+#[napi(object)]
+pub struct TournamentOptions {
+    /// Stacks of OOP and IP at the start of the postflop game, excluding the starting pot
+    pub stacks: Vec<f64>,
+    /// Stacks of the other players in the tournament
+    pub other_stacks: Option<Vec<f64>>,
+    /// Payouts from the first place
+    pub payouts: Vec<f64>,
+    /// Cash won by eliminating OOP and IP (default: none)
+    pub bounties: Option<Vec<f64>>,
+}
+
+/// Converts the tournament options into the payoff model
+fn create_tournament_payoff(options: &TournamentOptions) -> Result<TournamentPayoff> {
+    let pair = |values: &[f64], name: &str| -> Result<[f64; 2]> {
+        values
+            .try_into()
+            .map_err(|_| Error::from_reason(format!("Tournament {} must have 2 elements", name)))
+    };
+
+    Ok(TournamentPayoff {
+        stacks: pair(&options.stacks, "stacks")?,
+        other_stacks: options.other_stacks.clone().unwrap_or_default(),
+        payouts: options.payouts.clone(),
+        bounties: match &options.bounties {
+            Some(bounties) => pair(bounties, "bounties")?,
+            None => [0.0; 2],
+        },
+    })
+}
+
+/// Parses the bet sizes of both players for one street
+fn parse_street_sizes(
+    sizes: Option<&StreetBetSizes>,
+    default_bet: &str,
+    default_raise: &str,
+    street: &str,
+) -> Result<[BetSizeOptions; 2]> {
+    let parse = |bet: Option<&String>, raise: Option<&String>, player: &str| {
+        let bet = bet.map_or(default_bet, String::as_str);
+        let raise = raise.map_or(default_raise, String::as_str);
+        BetSizeOptions::try_from((bet, raise))
+            .map_err(|e| Error::from_reason(format!("Invalid {} {} bet sizes: {}", street, player, e)))
+    };
+
+    Ok([
+        parse(
+            sizes.and_then(|s| s.oop_bet.as_ref()),
+            sizes.and_then(|s| s.oop_raise.as_ref()),
+            "OOP",
+        )?,
+        parse(
+            sizes.and_then(|s| s.ip_bet.as_ref()),
+            sizes.and_then(|s| s.ip_raise.as_ref()),
+            "IP",
+        )?,
+    ])
+}
+
+/// Parses optional donk sizes for one street
+fn parse_donk_sizes(sizes: Option<&String>, street: &str) -> Result<Option<DonkSizeOptions>> {
+    sizes
+        .map(|s| DonkSizeOptions::try_from(s.as_str()))
+        .transpose()
+        .map_err(|e| Error::from_reason(format!("Invalid {} donk sizes: {}", street, e)))
+}
+
+/// Builds the tree configuration from the game configuration
+fn build_tree_config(config: &GameConfig, initial_state: BoardState) -> Result<TreeConfig> {
+    let bet_sizes = config.bet_sizes.as_deref().unwrap_or("60%,100%,a");
+    let raise_sizes = config.raise_sizes.as_deref().unwrap_or("2.5x");
+
+    Ok(TreeConfig {
+        initial_state,
+        starting_pot: config.starting_pot,
+        effective_stack: config.effective_stack,
+        rake_rate: config.rake_rate.unwrap_or(0.0),
+        rake_cap: config.rake_cap.unwrap_or(0.0),
+        flop_bet_sizes: parse_street_sizes(config.flop_sizes.as_ref(), bet_sizes, raise_sizes, "flop")?,
+        turn_bet_sizes: parse_street_sizes(config.turn_sizes.as_ref(), bet_sizes, raise_sizes, "turn")?,
+        river_bet_sizes: parse_street_sizes(config.river_sizes.as_ref(), bet_sizes, raise_sizes, "river")?,
+        turn_donk_sizes: parse_donk_sizes(config.turn_donk_sizes.as_ref(), "turn")?,
+        river_donk_sizes: parse_donk_sizes(config.river_donk_sizes.as_ref(), "river")?,
+        add_allin_threshold: config.add_allin_threshold.unwrap_or(1.5),
+        force_allin_threshold: config.force_allin_threshold.unwrap_or(0.15),
+        merging_threshold: config.merging_threshold.unwrap_or(0.1),
+    })
+}
+
+/// Options for re-solving the subgame rooted at the current node
+///
+/// Streets without any size options keep the bet sizes of the current game.
+#[napi(object)]
+pub struct SubgameConfig {
+    /// Default bet sizes of the remaining streets (default: "60%,100%,a")
+    pub bet_sizes: Option<String>,
+    /// Default raise sizes of the remaining streets (default: "2.5x")
+    pub raise_sizes: Option<String>,
+    pub turn_sizes: Option<StreetBetSizes>,
+    pub river_sizes: Option<StreetBetSizes>,
+    pub turn_donk_sizes: Option<String>,
+    pub river_donk_sizes: Option<String>,
+    pub add_allin_threshold: Option<f64>,
+    pub force_allin_threshold: Option<f64>,
+    pub merging_threshold: Option<f64>,
+    /// Player (0 = OOP, 1 = IP) who may take their current EVs instead of entering the
+    /// subgame, for safe re-solving; requires a solved game
+    pub gadget_player: Option<u32>,
+    /// Whether to compress the storage (default: chosen by `memory_budget`, else false)
+    pub compression: Option<bool>,
+    /// Memory budget in bytes
+    pub memory_budget: Option<f64>,
+}
+
+/// Applies the subgame options to the tree configuration of the subgame
+fn build_subgame_tree_config(config: &SubgameConfig, base: TreeConfig) -> Result<TreeConfig> {
+    let has_defaults = config.bet_sizes.is_some() || config.raise_sizes.is_some();
+    let bet_sizes = config.bet_sizes.as_deref().unwrap_or("60%,100%,a");
+    let raise_sizes = config.raise_sizes.as_deref().unwrap_or("2.5x");
+
+    let street_sizes = |sizes: Option<&StreetBetSizes>, current: [BetSizeOptions; 2], street: &str| {
+        if sizes.is_none() && !has_defaults {
+            return Ok(current);
+        }
+        parse_street_sizes(sizes, bet_sizes, raise_sizes, street)
+    };
+
+    Ok(TreeConfig {
+        turn_bet_sizes: street_sizes(config.turn_sizes.as_ref(), base.turn_bet_sizes, "turn")?,
+        river_bet_sizes: street_sizes(config.river_sizes.as_ref(), base.river_bet_sizes, "river")?,
+        turn_donk_sizes: match config.turn_donk_sizes {
+            Some(_) => parse_donk_sizes(config.turn_donk_sizes.as_ref(), "turn")?,
+            None => base.turn_donk_sizes,
+        },
+        river_donk_sizes: match config.river_donk_sizes {
+            Some(_) => parse_donk_sizes(config.river_donk_sizes.as_ref(), "river")?,
+            None => base.river_donk_sizes,
+        },
+        add_allin_threshold: config.add_allin_threshold.unwrap_or(base.add_allin_threshold),
+        force_allin_threshold: config.force_allin_threshold.unwrap_or(base.force_allin_threshold),
+        merging_threshold: config.merging_threshold.unwrap_or(base.merging_threshold),
+        ..base
+    })
+}
+
+/// Kind of an action
+#[napi(string_enum = "lowercase")]
+#[derive(PartialEq, Eq)]
+pub enum ActionKind {
+    Fold,
+    Check,
+    Call,
+    Bet,
+    Raise,
+    AllIn,
+    Chance,
+}
+
+/// Action available at the current node
+#[napi(object)]
+pub struct ActionInfo {
+    /// Value accepted by `play_action` (action index, or card id at chance nodes)
+    pub index: u32,
+    pub kind: ActionKind,
+    /// Street total after a bet/raise/all-in, or the amount to call
+    pub amount: Option<i32>,
+    /// Dealt card at chance nodes (e.g. "Qc")
+    pub card: Option<String>,
+    /// Pot size after the action
+    pub pot: i32,
+    /// Remaining stack of the acting player after the action
+    pub stack: i32,
+}
+
+/// Lists the actions available at the current node
+fn action_infos(game: &PostFlopGame) -> Result<Vec<ActionInfo>> {
+    if game.is_terminal_node() {
+        return Ok(Vec::new());
+    }
+
+    let starting_pot = game.tree_config().starting_pot;
+    let effective_stack = game.tree_config().effective_stack;
+    let total_bet_amount = game.total_bet_amount();
+
+    // list every possible card instead of the isomorphic representatives
+    if game.is_chance_node() {
+        let possible_cards = game.possible_cards();
+        return (0..52u8)
+            .filter(|&card| possible_cards & (1 << card) != 0)
+            .map(|card| {
+                Ok(ActionInfo {
+                    index: card as u32,
+                    kind: ActionKind::Chance,
+                    amount: None,
+                    card: Some(card_to_string(card).map_err(Error::from_reason)?),
+                    pot: starting_pot + total_bet_amount[0] + total_bet_amount[1],
+                    stack: effective_stack - total_bet_amount[0],
+                })
+            })
+            .collect();
+    }
+
+    let player = game.current_player();
+    game.available_actions()
+        .into_iter()
+        .enumerate()
+        .map(|(index, action)| {
+            let after = game.total_bet_amount_after(index);
+            let (kind, amount) = match action {
+                Action::Fold => (ActionKind::Fold, None),
+                Action::Check => (ActionKind::Check, None),
+                Action::Call => (ActionKind::Call, Some(after[player] - total_bet_amount[player])),
+                Action::Bet(amount) => (ActionKind::Bet, Some(amount)),
+                Action::Raise(amount) => (ActionKind::Raise, Some(amount)),
+                Action::AllIn(amount) => (ActionKind::AllIn, Some(amount)),
+                _ => return Err(Error::from_reason(format!("Unexpected action: {:?}", action))),
+            };
+
+            Ok(ActionInfo {
+                index: index as u32,
+                kind,
+                amount,
+                card: None,
+                pot: starting_pot + after[0] + after[1],
+                stack: effective_stack - after[player],
+            })
+        })
+        .collect()
+}
+
+/// Action in a line of the action tree
+#[napi(object)]
+pub struct TreeAction {
+    pub kind: ActionKind,
+    /// Street total of a bet, raise, or all-in
+    pub amount: Option<i32>,
+    /// Dealt card of a chance action (e.g. "Qc"); chance actions only appear in game lines
+    pub card: Option<String>,
+}
+
+/// Converts an action to its JS representation
+fn tree_action(action: Action) -> Result<TreeAction> {
+    let (kind, amount, card) = match action {
+        Action::Fold => (ActionKind::Fold, None, None),
+        Action::Check => (ActionKind::Check, None, None),
+        Action::Call => (ActionKind::Call, None, None),
+        Action::Bet(amount) => (ActionKind::Bet, Some(amount), None),
+        Action::Raise(amount) => (ActionKind::Raise, Some(amount), None),
+        Action::AllIn(amount) => (ActionKind::AllIn, Some(amount), None),
+        Action::Chance(card) => (
+            ActionKind::Chance,
+            None,
+            Some(card_to_string(card).map_err(Error::from_reason)?),
+        ),
+        Action::None => return Err(Error::from_reason("Unexpected action: None")),
+    };
+    Ok(TreeAction { kind, amount, card })
+}
+
+/// Converts a JS action to an action
+fn parse_tree_action(action: &TreeAction) -> Result<Action> {
+    let amount = || {
+        action.amount
+            .ok_or_else(|| Error::from_reason("Bet, raise, and all-in actions require an amount"))
+    };
+
+    Ok(match action.kind {
+        ActionKind::Fold => Action::Fold,
+        ActionKind::Check => Action::Check,
+        ActionKind::Call => Action::Call,
+        ActionKind::Bet => Action::Bet(amount()?),
+        ActionKind::Raise => Action::Raise(amount()?),
+        ActionKind::AllIn => Action::AllIn(amount()?),
+        ActionKind::Chance => {
+            let card = action.card.as_deref()
+                .ok_or_else(|| Error::from_reason("Chance actions require a card"))?;
+            Action::Chance(card_from_str(card).map_err(Error::from_reason)?)
+        }
+    })
+}
+
+/// Converts a JS line to a line of the action tree, where chance actions must be omitted
+fn parse_tree_line(line: &[TreeAction]) -> Result<Vec<Action>> {
+    line.iter()
+        .map(|action| match action.kind {
+            ActionKind::Chance => Err(Error::from_reason(
+                "Chance actions must be omitted from action tree lines",
+            )),
+            _ => parse_tree_action(action),
+        })
+        .collect()
+}
+
+/// Converts lines to their JS representation
+fn tree_lines(lines: &[Vec<Action>]) -> Result<Vec<Vec<TreeAction>>> {
+    lines.iter()
+        .map(|line| line.iter().map(|&action| tree_action(action)).collect())
+        .collect()
+}
+
+/// Strategy to lock for the hands matching a range string
+#[napi(object)]
+pub struct HandLock {
+    /// Specific combo ("AsKs"), grid class ("AKs", "QQ") or any range notation
+    pub hand: String,
+    /// Frequency of each action at the current node
+    pub frequencies: Vec<f64>,
+}
+
+/// Locked node and the path leading to it
+#[napi(object)]
+pub struct LockedNode {
+    /// History accepted by `apply_history`
+    pub history: Vec<u32>,
+    /// Actions played from the root
+    pub actions: Vec<ActionInfo>,
+}
+
+/// Per-player results at the current node
+#[napi(object, object_from_js = false)]
+pub struct PlayerSnapshot {
+    /// Private hands (e.g. "AsKs"), in the order of every per-hand array
+    pub hands: Vec<String>,
+    pub weights: Float32Array,
+    pub normalized_weights: Float32Array,
+    pub equity: Float32Array,
+    /// Empty unless the game is solved
+    pub ev: Float32Array,
+}
+
+/// Results needed to render the current node
+#[napi(object, object_from_js = false)]
+pub struct NodeSnapshot {
+    pub is_terminal: bool,
+    pub is_chance: bool,
+    /// 0 for OOP and 1 for IP; absent at chance and terminal nodes
+    pub current_player: Option<u32>,
+    pub board: Vec<String>,
+    /// Total bet amount of each player (OOP, IP)
+    pub total_bet_amount: Vec<i32>,
+    /// Cards that can be dealt at a chance node
+    pub possible_cards: Vec<String>,
+    pub actions: Vec<ActionInfo>,
+    /// Strategy of the current player laid out as `#actions * #hands`
+    pub strategy: Float32Array,
+    pub oop: PlayerSnapshot,
+    pub ip: PlayerSnapshot,
+}
+
+/// Collects the results of a player at the current node
+///
+/// The normalized weights must be cached beforehand.
+fn player_snapshot(game: &PostFlopGame, player: usize) -> Result<PlayerSnapshot> {
+    let ev = if game.is_solved() {
+        game.expected_values(player)
+    } else {
+        Vec::new()
+    };
+
+    Ok(PlayerSnapshot {
+        hands: holes_to_strings(game.private_cards(player)).map_err(Error::from_reason)?,
+        weights: Float32Array::new(game.weights(player).to_vec()),
+        normalized_weights: Float32Array::new(game.normalized_weights(player).to_vec()),
+        equity: Float32Array::new(game.equity(player)),
+        ev: Float32Array::new(ev),
+    })
+}
+
+/// Made-hand category of a private hand
+#[napi(string_enum = "snake_case")]
+pub enum MadeHandKind {
+    StraightFlush,
+    Quads,
+    FullHouse,
+    Flush,
+    Straight,
+    Set,
+    Trips,
+    TwoPair,
+    Overpair,
+    TopPair,
+    Underpair,
+    SecondPair,
+    LowPair,
+    AceHigh,
+    HighCard,
+}
+
+impl From<MadeHand> for MadeHandKind {
+    fn from(made_hand: MadeHand) -> Self {
+        match made_hand {
+            MadeHand::StraightFlush => MadeHandKind::StraightFlush,
+            MadeHand::Quads => MadeHandKind::Quads,
+            MadeHand::FullHouse => MadeHandKind::FullHouse,
+            MadeHand::Flush => MadeHandKind::Flush,
+            MadeHand::Straight => MadeHandKind::Straight,
+            MadeHand::Set => MadeHandKind::Set,
+            MadeHand::Trips => MadeHandKind::Trips,
+            MadeHand::TwoPair => MadeHandKind::TwoPair,
+            MadeHand::Overpair => MadeHandKind::Overpair,
+            MadeHand::TopPair => MadeHandKind::TopPair,
+            MadeHand::Underpair => MadeHandKind::Underpair,
+            MadeHand::SecondPair => MadeHandKind::SecondPair,
+            MadeHand::LowPair => MadeHandKind::LowPair,
+            MadeHand::AceHigh => MadeHandKind::AceHigh,
+            MadeHand::HighCard => MadeHandKind::HighCard,
+        }
+    }
+}
+
+/// Drawing category of a private hand
+#[napi(string_enum = "snake_case")]
+pub enum DrawKind {
+    FlushDraw,
+    OpenEnded,
+    Gutshot,
+    BackdoorFlushDraw,
+    Overcards,
+}
+
+impl From<Draw> for DrawKind {
+    fn from(draw: Draw) -> Self {
+        match draw {
+            Draw::FlushDraw => DrawKind::FlushDraw,
+            Draw::OpenEnded => DrawKind::OpenEnded,
+            Draw::Gutshot => DrawKind::Gutshot,
+            Draw::BackdoorFlushDraw => DrawKind::BackdoorFlushDraw,
+            Draw::Overcards => DrawKind::Overcards,
+        }
+    }
+}
+
+/// Classification of a private hand at the current board
+#[napi(object)]
+pub struct HandClassInfo {
+    /// Index into the private hands of the player
+    pub index: u32,
+    pub hand: String,
+    pub made_hand: MadeHandKind,
+    pub draws: Vec<DrawKind>,
+}
+
+/// Aggregated results of the hands in one category at the current node
+#[napi(object)]
+pub struct CategoryStats {
+    /// Set for made-hand categories
+    pub made_hand: Option<MadeHandKind>,
+    /// Set for draw categories; a hand can have several draws
+    pub draw: Option<DrawKind>,
+    /// Number of hands with a positive weight
+    pub num_combos: u32,
+    /// Sum of normalized weights
+    pub weight: f64,
+    /// Share of the player's total weight
+    pub frequency: f64,
+    /// Weighted action frequencies; empty unless the player acts at the current node
+    pub strategy: Vec<f64>,
+    pub equity: f64,
+    /// Absent unless the game is solved
+    pub ev: Option<f64>,
+}
+
+/// Classifies the private hands of a player at the current board with the rankings of the deck
+///
+/// Hands that overlap with the board are `None`.
+fn hand_classes(game: &PostFlopGame, player: usize) -> Result<Vec<Option<HandClass>>> {
+    let board = game.current_board();
+    let deck = game.card_config().deck;
+    let board_mask = board.iter().fold(0u64, |mask, &card| mask | (1 << card));
+
+    game.private_cards(player)
+        .iter()
+        .map(|&(card1, card2)| {
+            if board_mask & ((1 << card1) | (1 << card2)) != 0 {
+                return Ok(None);
+            }
+            classify_hand(&board, (card1, card2), deck)
+                .map(Some)
+                .map_err(Error::from_reason)
+        })
+        .collect()
+}
+
+/// Aggregates the strategy, equity, and EV of a player by hand category
+///
+/// Categories are listed from the strongest with the rankings of the deck. Normalized weights
+/// must be cached.
+fn category_stats(game: &PostFlopGame, player: usize) -> Result<Vec<CategoryStats>> {
+    let classes = hand_classes(game, player)?;
+    let weights = game.normalized_weights(player);
+    let num_hands = weights.len();
+
+    let is_player_node = !game.is_terminal_node() && !game.is_chance_node();
+    let strategy = (is_player_node && game.current_player() == player).then(|| game.strategy());
+    let num_actions = strategy.as_ref().map_or(0, |strategy| strategy.len() / num_hands);
+    let equity = game.equity(player);
+    let ev = game.is_solved().then(|| game.expected_values(player));
+    let total_weight = weights.iter().map(|&w| w as f64).sum::<f64>();
+
+    let aggregate = |made_hand: Option<MadeHand>, draw: Option<Draw>, hands: Vec<usize>| {
+        let weight = hands.iter().map(|&i| weights[i] as f64).sum::<f64>();
+        let average = |values: &[f32], offset: usize| {
+            hands.iter()
+                .map(|&i| weights[i] as f64 * values[offset + i] as f64)
+                .sum::<f64>() / weight
+        };
+
+        CategoryStats {
+            made_hand: made_hand.map(Into::into),
+            draw: draw.map(Into::into),
+            num_combos: hands.len() as u32,
+            weight,
+            frequency: weight / total_weight,
+            strategy: strategy.as_ref().map_or(Vec::new(), |strategy| {
+                (0..num_actions).map(|action| average(strategy, action * num_hands)).collect()
+            }),
+            equity: average(&equity, 0),
+            ev: ev.as_ref().map(|ev| average(ev, 0)),
+        }
+    };
+
+    let members = |filter: &dyn Fn(&HandClass) -> bool| {
+        (0..num_hands)
+            .filter(|&i| weights[i] > 0.0 && classes[i].as_ref().is_some_and(filter))
+            .collect::<Vec<_>>()
+    };
+
+    let made_hands = MadeHand::ranking(game.card_config().deck).into_iter().map(|made_hand| {
+        (Some(made_hand), None, members(&|class| class.made_hand == made_hand))
+    });
+    let draws = Draw::ALL.iter().map(|&draw| {
+        (None, Some(draw), members(&|class| class.draws.contains(&draw)))
+    });
+
+    Ok(made_hands
+        .chain(draws)
+        .filter(|(_, _, hands)| !hands.is_empty())
+        .map(|(made_hand, draw, hands)| aggregate(made_hand, draw, hands))
+        .collect())
+}
+
+/// Reason why a background solve stopped
+#[napi(string_enum = "snake_case")]
+#[derive(PartialEq, Eq)]
+pub enum StopReasonKind {
+    MaxIterations,
+    TargetExploitability,
+    TimeLimit,
+    Cancelled,
+}
+
+/// A point of the convergence curve
+#[napi(object)]
+#[derive(Clone)]
+pub struct ConvergenceInfo {
+    pub iteration: u32,
+    /// Solving time in seconds since the start of the solve, excluding pauses
+    pub elapsed: f64,
+    pub exploitability: f64,
+    /// EV of OOP and IP under the current strategy; absent if not computed
+    pub ev: Option<Vec<f64>>,
+}
+
+impl ConvergenceInfo {
+    fn new(point: &ConvergencePoint, starting_pot: i32) -> Self {
+        // `compute_current_ev` subtracts half the starting pot
+        let bias = starting_pot as f64 / 2.0;
+        Self {
+            iteration: point.iteration,
+            elapsed: point.elapsed.as_secs_f64(),
+            exploitability: point.exploitability as f64,
+            ev: point.ev.map(|ev| ev.iter().map(|&v| v as f64 + bias).collect()),
+        }
+    }
+}
+
+/// Stop conditions of a background solve
+#[napi(object)]
+pub struct SolveConfig {
+    pub max_iterations: u32,
+    pub target_exploitability: f64,
+    /// Wall-clock budget in seconds, excluding pauses
+    pub time_limit: Option<f64>,
+    /// Iterations between exploitability checks (default: 10)
+    pub check_interval: Option<u32>,
+    /// Whether to record both players' EVs at each check (default: true)
+    pub compute_ev: Option<bool>,
+    pub algorithm: Option<SolverAlgorithm>,
+}
+
+/// Result of an asynchronous solve
+#[napi(object)]
+pub struct SolveResult {
+    pub iterations: u32,
+    pub exploitability: f64,
+    pub cancelled: bool,
+    pub stop_reason: StopReasonKind,
+    /// Exploitability checks of this solve, starting with the state before solving
+    pub history: Vec<ConvergenceInfo>,
+}
+
+/// CFR variant used by the solver
+#[napi(string_enum = "snake_case")]
+pub enum AlgorithmKind {
+    Dcfr,
+    LinearCfr,
+    CfrPlus,
+    PcfrPlus,
+}
+
+/// Solver algorithm of a solve
+///
+/// `alpha`, `beta`, and `gamma` only apply to DCFR. If none of them are given, DCFR uses
+/// (1.5, 0, 3) and resets the average strategy at powers of 4; otherwise missing ones default to
+/// the same values and no resets are made.
+#[napi(object)]
+pub struct SolverAlgorithm {
+    pub kind: AlgorithmKind,
+    pub alpha: Option<f64>,
+    pub beta: Option<f64>,
+    pub gamma: Option<f64>,
+}
+
+/// Converts the solver algorithm option; DCFR is used when absent
+fn cfr_algorithm(algorithm: Option<SolverAlgorithm>) -> Result<CfrAlgorithm> {
+    let Some(algorithm) = algorithm else {
+        return Ok(CfrAlgorithm::Discounted);
+    };
+
+    let has_params =
+        algorithm.alpha.is_some() || algorithm.beta.is_some() || algorithm.gamma.is_some();
+
+    match algorithm.kind {
+        AlgorithmKind::Dcfr if has_params => {
+            let alpha = algorithm.alpha.unwrap_or(1.5);
+            let beta = algorithm.beta.unwrap_or(0.0);
+            let gamma = algorithm.gamma.unwrap_or(3.0);
+            if !alpha.is_finite() || !beta.is_finite() || !gamma.is_finite() || gamma < 0.0 {
+                return Err(Error::from_reason(
+                    "alpha and beta must be finite and gamma must be non-negative",
+                ));
+            }
+            Ok(CfrAlgorithm::DiscountedWith {
+                alpha: alpha as f32,
+                beta: beta as f32,
+                gamma: gamma as f32,
+            })
+        }
+        AlgorithmKind::Dcfr => Ok(CfrAlgorithm::Discounted),
+        _ if has_params => Err(Error::from_reason("alpha, beta, and gamma only apply to DCFR")),
+        AlgorithmKind::LinearCfr => Ok(CfrAlgorithm::Linear),
+        AlgorithmKind::CfrPlus => Ok(CfrAlgorithm::CfrPlus),
+        AlgorithmKind::PcfrPlus => Ok(CfrAlgorithm::PredictiveCfrPlus),
+    }
+}
+
+/// Flags shared between the solver instance and a running solve task
+#[derive(Default)]
+struct SolveControl {
+    running: AtomicBool,
+    paused: AtomicBool,
+    cancelled: AtomicBool,
+    iteration: AtomicU32,
+}
+
+type SharedGame = Arc<Mutex<Option<PostFlopGame>>>;
+
+fn lock_game(game: &SharedGame) -> Result<MutexGuard<'_, Option<PostFlopGame>>> {
+    game.lock()
+        .map_err(|_| Error::from_reason("Solver state is poisoned"))
+}
+
+/// Background task that runs the solver off the main thread
+pub struct SolveTask {
+    game: SharedGame,
+    control: Arc<SolveControl>,
+    max_iterations: u32,
+    target_exploitability: f32,
+    algorithm: CfrAlgorithm,
+    time_limit: Option<Duration>,
+    check_interval: u32,
+    compute_ev: bool,
+    callback: Option<ThreadsafeFunction<ConvergenceInfo, ErrorStrategy::Fatal>>,
+}
+
+impl SolveTask {
+    fn report(&self, info: ConvergenceInfo, history: &mut Vec<ConvergenceInfo>) {
+        if let Some(callback) = &self.callback {
+            callback.call(info.clone(), ThreadsafeFunctionCallMode::NonBlocking);
+        }
+        history.push(info);
+    }
+}
+
+impl Task for SolveTask {
+    type Output = SolveResult;
+    type JsValue = SolveResult;
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        let mut history = Vec::new();
+        let mut elapsed = Duration::ZERO;
+
+        let (mut iteration, mut exploitability, starting_pot) = {
+            let guard = lock_game(&self.game)?;
+            let game = guard.as_ref()
+                .ok_or_else(|| Error::from_reason("Game not initialized"))?;
+
+            if game.is_solved() {
+                return Err(Error::from_reason("Game is already solved"));
+            }
+
+            if !game.is_ready() {
+                return Err(Error::from_reason("Game is not ready"));
+            }
+
+            let starting_pot = game.tree_config().starting_pot;
+            let point = convergence_point(game, game.num_iterations(), elapsed, self.compute_ev);
+            self.report(ConvergenceInfo::new(&point, starting_pot), &mut history);
+            (point.iteration, point.exploitability, starting_pot)
+        };
+
+        let stop_reason = loop {
+            if self.control.cancelled.load(Ordering::Acquire) {
+                break StopReasonKind::Cancelled;
+            }
+
+            if iteration >= self.max_iterations {
+                break StopReasonKind::MaxIterations;
+            }
+
+            if exploitability <= self.target_exploitability {
+                break StopReasonKind::TargetExploitability;
+            }
+
+            if self.time_limit.is_some_and(|limit| elapsed >= limit) {
+                break StopReasonKind::TimeLimit;
+            }
+
+            // The lock is released while paused so that the game can be inspected
+            if self.control.paused.load(Ordering::Acquire) {
+                thread::sleep(Duration::from_millis(10));
+                continue;
+            }
+
+            let started = Instant::now();
+            let mut guard = lock_game(&self.game)?;
+            let game = guard.as_mut()
+                .ok_or_else(|| Error::from_reason("Game not initialized"))?;
+
+            solve_step_with(game, self.algorithm, iteration);
+            game.set_num_iterations(iteration + 1);
+            iteration += 1;
+
+            let now = elapsed + started.elapsed();
+            let is_timed_out = self.time_limit.is_some_and(|limit| now >= limit);
+
+            if iteration.is_multiple_of(self.check_interval)
+                || iteration == self.max_iterations
+                || is_timed_out
+            {
+                let point = convergence_point(game, iteration, now, self.compute_ev);
+                exploitability = point.exploitability;
+                self.report(ConvergenceInfo::new(&point, starting_pot), &mut history);
+            }
+
+            drop(guard);
+            elapsed += started.elapsed();
+            self.control.iteration.store(iteration, Ordering::Release);
+        };
+
+        // A cancelled game is left unfinalized so that it can be resumed later
+        let cancelled = stop_reason == StopReasonKind::Cancelled;
+        if !cancelled {
+            let mut guard = lock_game(&self.game)?;
+            let game = guard.as_mut()
+                .ok_or_else(|| Error::from_reason("Game not initialized"))?;
+            finalize(game);
+        }
+
+        Ok(SolveResult {
+            iterations: iteration,
+            exploitability: exploitability as f64,
+            cancelled,
+            stop_reason,
+            history,
+        })
+    }
+
+    fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+        Ok(output)
+    }
+
+    fn finally(&mut self, _env: Env) -> Result<()> {
+        self.control.paused.store(false, Ordering::Release);
+        self.control.running.store(false, Ordering::Release);
+        Ok(())
+    }
+}
+
+/// Progress of the bunching computation
+#[napi(object)]
+pub struct BunchingProgress {
+    /// Current phase (0-3)
+    pub phase: u32,
+    /// Progress of the current phase (0-100)
+    pub percent: u32,
+    pub ready: bool,
+}
+
+/// Flags shared between the solver instance and a running bunching task
+#[derive(Default)]
+struct BunchingControl {
+    running: AtomicBool,
+    cancelled: AtomicBool,
+    phase: AtomicU32,
+    percent: AtomicU32,
+}
+
+impl BunchingControl {
+    fn update(&self, data: &BunchingData) {
+        self.phase.store(data.phase() as u32, Ordering::Release);
+        self.percent.store(data.progress_percent() as u32, Ordering::Release);
+    }
+}
+
+type SharedBunching = Arc<Mutex<Option<BunchingData>>>;
+
+/// Background task that computes the bunching effect off the main thread
+pub struct BunchingTask {
+    bunching: SharedBunching,
+    control: Arc<BunchingControl>,
+    callback: Option<ThreadsafeFunction<(u32, u32), ErrorStrategy::Fatal>>,
+}
+
+impl Task for BunchingTask {
+    type Output = bool;
+    type JsValue = bool;
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        loop {
+            if self.control.cancelled.load(Ordering::Acquire) {
+                return Ok(false);
+            }
+
+            let mut guard = self.bunching.lock()
+                .map_err(|_| Error::from_reason("Bunching state is poisoned"))?;
+            let data = guard.as_mut()
+                .ok_or_else(|| Error::from_reason("Bunching data not initialized"))?;
+
+            if data.is_ready() {
+                return Ok(true);
+            }
+
+            // a cancelled computation resumes from the last completed step
+            match (data.phase(), data.progress_percent()) {
+                (0, _) => data.phase1_prepare(),
+                (1, 100) => data.phase2_prepare(),
+                (2, 100) => data.phase3_prepare(),
+                (1, _) => data.phase1_proceed_by_percent(),
+                (2, _) => data.phase2_proceed_by_percent(),
+                _ => data.phase3_proceed_by_percent(),
+            }
+
+            self.control.update(data);
+
+            if let Some(callback) = &self.callback {
+                callback.call(
+                    (data.phase() as u32, data.progress_percent() as u32),
+                    ThreadsafeFunctionCallMode::NonBlocking,
+                );
+            }
+        }
+    }
+
+    fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+        Ok(output)
+    }
+
+    fn finally(&mut self, _env: Env) -> Result<()> {
+        self.control.running.store(false, Ordering::Release);
+        Ok(())
+    }
+}
+
+/// Root results of one flop in an aggregate report
+#[napi(object)]
+pub struct FlopReportRow {
+    /// Empty for the average row
+    pub flop: String,
+    /// Number of flops the row represents; the total for the average row
+    pub weight: f64,
+    pub iterations: u32,
+    pub exploitability: f64,
+    /// Overall frequency of each root action of OOP
+    pub frequencies: Vec<f64>,
+    /// Average equity of OOP and IP
+    pub equity: Vec<f64>,
+    /// Average EV of OOP and IP
+    pub ev: Vec<f64>,
+}
+
+impl FlopReportRow {
+    fn new(row: &FlopReport) -> Self {
+        Self {
+            flop: flop_to_string(row.flop).unwrap_or_default(),
+            weight: row.weight,
+            iterations: row.num_iterations,
+            exploitability: row.exploitability as f64,
+            frequencies: row.frequencies.iter().map(|&f| f as f64).collect(),
+            equity: row.equity.iter().map(|&e| e as f64).collect(),
+            ev: row.ev.iter().map(|&e| e as f64).collect(),
+        }
+    }
+}
+
+/// A flop that failed to solve in an aggregate report
+#[napi(object)]
+pub struct FlopReportError {
+    pub flop: String,
+    pub error: String,
+}
+
+/// Result of an aggregate report over many flops
+#[napi(object)]
+pub struct AggregateReportResult {
+    /// Root actions of OOP (e.g. "Bet 50")
+    pub actions: Vec<String>,
+    pub rows: Vec<FlopReportRow>,
+    /// Flops that failed to solve; the other flops are still reported
+    pub errors: Vec<FlopReportError>,
+    /// Average of all rows; absent if no flop was solved
+    pub average: Option<FlopReportRow>,
+    pub csv: String,
+    pub json: String,
+    pub cancelled: bool,
+}
+
+/// Flags shared between the solver instance and a running report task
+#[derive(Default)]
+struct ReportControl {
+    running: AtomicBool,
+    cancelled: AtomicBool,
+}
+
+/// Background task that solves a list of flops for an aggregate report
+pub struct ReportTask {
+    config: ReportConfig,
+    flops: Vec<WeightedFlop>,
+    control: Arc<ReportControl>,
+    callback: Option<ThreadsafeFunction<(u32, u32, String), ErrorStrategy::Fatal>>,
+}
+
+impl Task for ReportTask {
+    type Output = (AggregateReport, bool);
+    type JsValue = AggregateReportResult;
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        let num_flops = self.flops.len() as u32;
+        let mut cancelled = false;
+
+        let flops = &self.flops;
+        let report = run_aggregate_report(&self.config, flops, |index, _| {
+            if let Some(callback) = &self.callback {
+                let flop = flop_to_string(flops[index].flop).unwrap_or_default();
+                callback.call(
+                    (index as u32 + 1, num_flops, flop),
+                    ThreadsafeFunctionCallMode::NonBlocking,
+                );
+            }
+            cancelled = self.control.cancelled.load(Ordering::Acquire);
+            !cancelled
+        })
+        .map_err(Error::from_reason)?;
+
+        Ok((report, cancelled))
+    }
+
+    fn resolve(&mut self, _env: Env, (report, cancelled): Self::Output) -> Result<Self::JsValue> {
+        Ok(AggregateReportResult {
+            actions: report.actions.iter().map(|&action| action_label(action)).collect(),
+            rows: report.rows.iter().map(FlopReportRow::new).collect(),
+            errors: report
+                .errors
+                .iter()
+                .map(|(flop, error)| FlopReportError {
+                    flop: flop_to_string(*flop).unwrap_or_default(),
+                    error: error.clone(),
+                })
+                .collect(),
+            average: report.average().as_ref().map(FlopReportRow::new),
+            csv: report.to_csv().map_err(Error::from_reason)?,
+            json: report.to_json().map_err(Error::from_reason)?,
+            cancelled,
+        })
+    }
+
+    fn finally(&mut self, _env: Env) -> Result<()> {
+        self.control.running.store(false, Ordering::Release);
+        Ok(())
+    }
+}
+
+/// Builds the action tree of the given configuration
+fn create_action_tree(config: &GameConfig) -> Result<ActionTree> {
+    let tree_config = build_tree_config(config, initial_state(config))?;
+    ActionTree::new(tree_config)
+        .map_err(|e| Error::from_reason(format!("Failed to create action tree: {}", e)))
+}
+
+/// Returns the initial board state of the game configuration
+fn initial_state(config: &GameConfig) -> BoardState {
+    if config.river.is_some() {
+        BoardState::River
+    } else if config.turn.is_some() {
+        BoardState::Turn
+    } else {
+        BoardState::Flop
+    }
+}
+
+/// Parses a range in the default, PioSOLVER, or GTO+ notation
+fn parse_range(range: &str) -> Result<Range> {
+    let result = if range.contains('[') {
+        Range::from_gtoplus_str(range)
+    } else {
+        range.parse::<Range>()
+    };
+    result.map_err(Error::from_reason)
+}
+
+/// Returns the deck of the given configuration
+fn create_deck(config: &GameConfig) -> Deck {
+    if config.short_deck.unwrap_or(false) { Deck::ShortDeck } else { Deck::Standard }
+}
+
+/// Parses the ranges of the given configuration without the hands outside the deck
+fn create_ranges(config: &GameConfig, deck: Deck) -> Result<[Range; 2]> {
+    let mut ranges = [parse_range(&config.oop_range)?, parse_range(&config.ip_range)?];
+    for range in &mut ranges {
+        range.restrict_to_deck(deck);
+    }
+    Ok(ranges)
+}
+
+/// Parses the ranges and the board of the given configuration
+fn create_card_config(config: &GameConfig) -> Result<CardConfig> {
+    // Parse ranges
+    let deck = create_deck(config);
+    let range = create_ranges(config, deck)?;
+    
+    // Parse flop
+    let flop = flop_from_str(&config.flop)
+        .map_err(|e| Error::from_reason(e))?;
+    
+    // Parse turn if provided
+    let turn = if let Some(turn_str) = &config.turn {
+        card_from_str(turn_str)
+            .map_err(|e| Error::from_reason(e))?
+    } else {
+        NOT_DEALT
+    };
+    
+    // Parse river if provided
+    let river = if let Some(river_str) = &config.river {
+        card_from_str(river_str)
+            .map_err(|e| Error::from_reason(e))?
+    } else {
+        NOT_DEALT
+    };
+    
+    Ok(CardConfig {
+        range,
+        flop,
+        turn,
+        river,
+        deck,
+    })
+}
+
+/// Builds the game tree of the given configuration without allocating memory
+///
+/// If `action_tree` is given, it replaces the tree built from the bet sizes of `config`.
+fn create_game(config: &GameConfig, action_tree: Option<ActionTree>) -> Result<PostFlopGame> {
+    let card_config = create_card_config(config)?;
+
+    // Build action tree
+    let action_tree = match action_tree {
+        Some(action_tree) => action_tree,
+        None => create_action_tree(config)?,
+    };
+    
+    // Create the game
+    let mut game = PostFlopGame::with_config(card_config, action_tree)
+        .map_err(|e| Error::from_reason(format!("Failed to create game: {}", e)))?;
+
+    if let Some(tournament) = &config.tournament {
+        game.set_tournament_payoff(create_tournament_payoff(tournament)?)
+            .map_err(|e| Error::from_reason(format!("Invalid tournament payoff: {}", e)))?;
+    }
+
+    Ok(game)
+}
+
+/// Memory requirements of a game in bytes
+#[napi(object)]
+pub struct MemoryUsage {
+    pub uncompressed: f64,
+    pub compressed: f64,
+    /// Additional memory needed when the bunching effect is applied
+    pub bunching: f64,
+    /// Additional memory needed by PCFR+
+    pub predictions: f64,
+    pub allocated: bool,
+    pub compression_enabled: bool,
+}
+
+/// Size statistics of an action tree
+#[napi(object)]
+pub struct TreeStats {
+    /// Player nodes of each street [flop, turn, river], without expanding the dealt cards
+    pub num_player_nodes: Vec<f64>,
+    pub num_chance_nodes: f64,
+    pub num_terminal_nodes: f64,
+    pub num_allin_nodes: f64,
+    /// Maximum number of player actions in a line
+    pub max_depth: u32,
+    /// Estimated storage in bytes of the game built with the given ranges, excluding small fixed
+    /// overhead
+    pub memory_uncompressed: Option<f64>,
+    pub memory_compressed: Option<f64>,
+}
+
+/// Options for exporting a solved game as a JSON tree
+#[napi(object)]
+pub struct TreeExportConfig {
+    /// Levels below the root to export, counting both actions and dealt cards (default: 3)
+    pub max_depth: Option<u32>,
+    /// Exports every possible card at chance nodes (default: false)
+    pub expand_chance: Option<bool>,
+    /// Exports the strategy and EV of each hand at player nodes (default: true)
+    pub include_hands: Option<bool>,
+}
+
+/// Chooses whether to compress the storage so that the game fits the memory budget
+fn choose_compression(
+    game: &PostFlopGame,
+    compression: Option<bool>,
+    memory_budget: Option<f64>,
+) -> Result<bool> {
+    let (uncompressed, compressed) = game.memory_usage();
+    let budget = match memory_budget {
+        Some(budget) => budget,
+        None => return Ok(compression.unwrap_or(false)),
+    };
+    
+    let fits = |usage: u64| usage as f64 <= budget;
+    let to_mb = |usage: u64| usage as f64 / (1024.0 * 1024.0);
+    
+    match compression {
+        Some(enabled) => {
+            let usage = if enabled { compressed } else { uncompressed };
+            if !fits(usage) {
+                return Err(Error::from_reason(format!(
+                    "Memory usage ({:.2} MB) exceeds the memory budget ({:.2} MB)",
+                    to_mb(usage),
+                    budget / (1024.0 * 1024.0)
+                )));
+            }
+            Ok(enabled)
+        }
+        None if fits(uncompressed) => Ok(false),
+        None if fits(compressed) => Ok(true),
+        None => Err(Error::from_reason(format!(
+            "Memory usage ({:.2} MB compressed) exceeds the memory budget ({:.2} MB)",
+            to_mb(compressed),
+            budget / (1024.0 * 1024.0)
+        ))),
+    }
+}
+
+/// Fails if the memory of the game has not been allocated
+fn ensure_allocated(game: &PostFlopGame) -> Result<()> {
+    if game.is_memory_allocated().is_none() {
+        return Err(Error::from_reason("Memory is not allocated"));
+    }
+    Ok(())
+}
+
+/// Fails unless the player is 0 (OOP) or 1 (IP)
+fn ensure_player(player: u32) -> Result<()> {
+    if player > 1 {
+        return Err(Error::from_reason(format!("Invalid player: {}", player)));
+    }
+    Ok(())
+}
+
+/// Native solver exposed to Node.js
+#[napi]
+pub struct NativeSolver {
+    game: SharedGame,
+    control: Arc<SolveControl>,
+    bunching: SharedBunching,
+    bunching_control: Arc<BunchingControl>,
+    report_control: Arc<ReportControl>,
+}
+
+impl NativeSolver {
+    /// Locks the game for use on the main thread.
+    ///
+    /// Fails instead of blocking if a solve task is currently running.
+    fn lock(&self) -> Result<MutexGuard<'_, Option<PostFlopGame>>> {
+        if self.control.running.load(Ordering::Acquire)
+            && !self.control.paused.load(Ordering::Acquire)
+        {
+            return Err(Error::from_reason("Solver is running"));
+        }
+
+        match self.game.try_lock() {
+            Ok(guard) => Ok(guard),
+            Err(TryLockError::WouldBlock) => Err(Error::from_reason("Solver is running")),
+            Err(TryLockError::Poisoned(_)) => Err(Error::from_reason("Solver state is poisoned")),
+        }
+    }
+
+    /// Fails if a solve task is running, even if it is paused.
+    fn ensure_idle(&self) -> Result<()> {
+        if self.control.running.load(Ordering::Acquire) {
+            return Err(Error::from_reason("Solver is running"));
+        }
+        Ok(())
+    }
+
+    /// Locks the bunching data for use on the main thread.
+    ///
+    /// Fails if a bunching task is currently running.
+    fn lock_bunching(&self) -> Result<MutexGuard<'_, Option<BunchingData>>> {
+        if self.bunching_control.running.load(Ordering::Acquire) {
+            return Err(Error::from_reason("Bunching computation is running"));
+        }
+
+        self.bunching.lock()
+            .map_err(|_| Error::from_reason("Bunching state is poisoned"))
+    }
+
+    /// Replaces the current game and syncs the iteration counter with it.
+    fn set_game(&self, game: PostFlopGame) -> Result<()> {
+        self.ensure_idle()?;
+        let num_iterations = game.num_iterations();
+        *self.lock()? = Some(game);
+        self.control.iteration.store(num_iterations, Ordering::Release);
+        Ok(())
+    }
+}
+
+#[napi]
+impl NativeSolver {
+    /// Creates a new solver instance
+    #[napi(constructor)]
+    pub fn new() -> Self {
+        Self {
+            game: Arc::new(Mutex::new(None)),
+            control: Arc::new(SolveControl::default()),
+            bunching: Arc::new(Mutex::new(None)),
+            bunching_control: Arc::new(BunchingControl::default()),
+            report_control: Arc::new(ReportControl::default()),
+        }
+    }
+
+    /// Initializes the game with given configuration
+    ///
+    /// Memory is allocated right away. Compression is chosen automatically if a memory budget
+    /// is given and `compression` is unset.
+    ///
+    /// If `tree` is given, its action tree replaces the one built from the bet sizes of `config`.
+    #[napi]
+    pub fn init_game(&mut self, config: GameConfig, tree: Option<&NativeActionTree>) -> Result<()> {
+        self.ensure_idle()?;
+
+        let mut game = create_game(&config, tree.map(|tree| tree.tree.clone()))?;
+        let enable_compression = choose_compression(&game, config.compression, config.memory_budget)?;
+        game.allocate_memory(enable_compression);
+        
+        self.set_game(game)
+    }
+    
+    /// Builds the game tree without allocating memory
+    ///
+    /// Use `get_memory_usage` to inspect the requirements, then call `allocate_memory`.
+    /// If `tree` is given, its action tree replaces the one built from the bet sizes of `config`.
+    #[napi]
+    pub fn build_game(&mut self, config: GameConfig, tree: Option<&NativeActionTree>) -> Result<()> {
+        self.ensure_idle()?;
+        let game = create_game(&config, tree.map(|tree| tree.tree.clone()))?;
+        self.set_game(game)
+    }
+    
+    /// Removes lines from a built game before allocating memory
+    ///
+    /// Unlike action tree lines, these lines include the dealt cards, so a line can be removed
+    /// only on specific turns or rivers. Cards must be the isomorphic representatives.
+    #[napi]
+    pub fn remove_lines(&mut self, lines: Vec<Vec<TreeAction>>) -> Result<()> {
+        self.ensure_idle()?;
+        let mut guard = self.lock()?;
+        let game = guard.as_mut()
+            .ok_or_else(|| Error::from_reason("Game not initialized"))?;
+
+        let lines = lines.iter()
+            .map(|line| line.iter().map(parse_tree_action).collect())
+            .collect::<Result<Vec<Vec<_>>>>()?;
+        game.remove_lines(&lines).map_err(Error::from_reason)
+    }
+    
+    /// Allocates the memory of a built game and returns whether compression is enabled
+    ///
+    /// If `compression` is unset, the uncompressed layout is preferred when it fits the budget.
+    /// Reallocating discards any solving progress.
+    #[napi]
+    pub fn allocate_memory(&mut self, compression: Option<bool>, memory_budget: Option<f64>) -> Result<bool> {
+        self.ensure_idle()?;
+        let mut guard = self.lock()?;
+        let game = guard.as_mut()
+            .ok_or_else(|| Error::from_reason("Game not initialized"))?;
+        
+        let enable_compression = choose_compression(game, compression, memory_budget)?;
+        game.allocate_memory(enable_compression);
+        self.control.iteration.store(game.num_iterations(), Ordering::Release);
+        
+        Ok(enable_compression)
+    }
+    
+    /// Solves the game for a specified number of iterations
+    #[napi]
+    pub fn solve(
+        &mut self,
+        max_iterations: u32,
+        target_exploitability: f64,
+        algorithm: Option<SolverAlgorithm>,
+    ) -> Result<f64> {
+        let algorithm = cfr_algorithm(algorithm)?;
+        self.ensure_idle()?;
+        let mut guard = self.lock()?;
+        let game = guard.as_mut()
+            .ok_or_else(|| Error::from_reason("Game not initialized"))?;
+        ensure_allocated(game)?;
+        
+        let exploitability = solve_with(
+            game, 
+            algorithm,
+            max_iterations, 
+            target_exploitability as f32,
+            false // Don't print progress in Node.js binding
+        );
+        self.control.iteration.store(game.num_iterations(), Ordering::Release);
+        
+        Ok(exploitability as f64)
+    }
+
+    /// Continues solving from the stored iteration count without finalizing
+    #[napi]
+    pub fn continue_solve(
+        &mut self,
+        max_iterations: u32,
+        target_exploitability: f64,
+        algorithm: Option<SolverAlgorithm>,
+    ) -> Result<f64> {
+        let algorithm = cfr_algorithm(algorithm)?;
+        self.ensure_idle()?;
+        let mut guard = self.lock()?;
+        let game = guard.as_mut()
+            .ok_or_else(|| Error::from_reason("Game not initialized"))?;
+        ensure_allocated(game)?;
+        
+        if game.is_solved() {
+            return Err(Error::from_reason("Game is already solved"));
+        }
+        
+        let exploitability = continue_solve_with(
+            game,
+            algorithm,
+            max_iterations,
+            target_exploitability as f32,
+            false
+        );
+        self.control.iteration.store(game.num_iterations(), Ordering::Release);
+        
+        Ok(exploitability as f64)
+    }
+    
+    /// Solves the game on a background thread.
+    ///
+    /// The optional callback receives the iteration count and exploitability every 10
+    /// iterations. A cancelled solve leaves the game unfinalized; calling this method again
+    /// resumes from the last completed iteration.
+    #[napi(
+        ts_args_type = "maxIterations: number, targetExploitability: number, callback?: ((iteration: number, exploitability: number) => void) | undefined | null, algorithm?: SolverAlgorithm"
+    )]
+    pub fn solve_async(
+        &self,
+        max_iterations: u32,
+        target_exploitability: f64,
+        callback: Option<JsFunction>,
+        algorithm: Option<SolverAlgorithm>,
+    ) -> Result<AsyncTask<SolveTask>> {
+        let callback = match callback {
+            Some(callback) => Some(callback.create_threadsafe_function(
+                0,
+                |ctx: ThreadSafeCallContext<ConvergenceInfo>| {
+                    Ok(vec![ctx.value.iteration as f64, ctx.value.exploitability])
+                },
+            )?),
+            None => None,
+        };
+
+        let config = SolveConfig {
+            max_iterations,
+            target_exploitability,
+            time_limit: None,
+            check_interval: None,
+            compute_ev: Some(false),
+            algorithm,
+        };
+
+        self.start_solve(config, callback)
+    }
+
+    /// Solves the game on a background thread until any stop condition of the config is met
+    ///
+    /// The optional callback receives each point of the convergence curve, which is also
+    /// returned as `history`. The game is finalized unless the solve is cancelled.
+    #[napi(
+        ts_args_type = "config: SolveConfig, callback?: (point: ConvergenceInfo) => void"
+    )]
+    pub fn solve_with_config(
+        &self,
+        config: SolveConfig,
+        callback: Option<JsFunction>,
+    ) -> Result<AsyncTask<SolveTask>> {
+        let callback = match callback {
+            Some(callback) => Some(callback.create_threadsafe_function(
+                0,
+                |ctx: ThreadSafeCallContext<ConvergenceInfo>| Ok(vec![ctx.value]),
+            )?),
+            None => None,
+        };
+
+        self.start_solve(config, callback)
+    }
+
+    fn start_solve(
+        &self,
+        config: SolveConfig,
+        callback: Option<ThreadsafeFunction<ConvergenceInfo, ErrorStrategy::Fatal>>,
+    ) -> Result<AsyncTask<SolveTask>> {
+        let algorithm = cfr_algorithm(config.algorithm)?;
+
+        let time_limit = match config.time_limit {
+            Some(seconds) => Some(
+                Duration::try_from_secs_f64(seconds)
+                    .map_err(|_| Error::from_reason(format!("Invalid time limit: {}", seconds)))?,
+            ),
+            None => None,
+        };
+
+        if config.check_interval == Some(0) {
+            return Err(Error::from_reason("Check interval must be positive"));
+        }
+
+        if self.control.running.swap(true, Ordering::AcqRel) {
+            return Err(Error::from_reason("Solver is running"));
+        }
+
+        self.control.paused.store(false, Ordering::Release);
+        self.control.cancelled.store(false, Ordering::Release);
+
+        Ok(AsyncTask::new(SolveTask {
+            game: self.game.clone(),
+            control: self.control.clone(),
+            max_iterations: config.max_iterations,
+            target_exploitability: config.target_exploitability as f32,
+            algorithm,
+            time_limit,
+            check_interval: config.check_interval.unwrap_or(10),
+            compute_ev: config.compute_ev.unwrap_or(true),
+            callback,
+        }))
+    }
+
+    /// Pauses a running background solve
+    #[napi]
+    pub fn pause(&self) {
+        if self.control.running.load(Ordering::Acquire) {
+            self.control.paused.store(true, Ordering::Release);
+        }
+    }
+
+    /// Resumes a paused background solve
+    #[napi]
+    pub fn resume(&self) {
+        self.control.paused.store(false, Ordering::Release);
+    }
+
+    /// Cancels a running background solve
+    #[napi]
+    pub fn cancel(&self) {
+        if self.control.running.load(Ordering::Acquire) {
+            self.control.cancelled.store(true, Ordering::Release);
+            self.control.paused.store(false, Ordering::Release);
+        }
+    }
+
+    /// Returns whether a background solve is in progress
+    #[napi]
+    pub fn is_solving(&self) -> bool {
+        self.control.running.load(Ordering::Acquire)
+    }
+
+    /// Returns whether the background solve is paused
+    #[napi]
+    pub fn is_paused(&self) -> bool {
+        self.control.paused.load(Ordering::Acquire)
+    }
+
+    /// Gets the number of completed solver iterations
+    #[napi]
+    pub fn get_iteration(&self) -> u32 {
+        self.control.iteration.load(Ordering::Acquire)
+    }
+
+    /// Runs a single iteration of the solver
+    #[napi]
+    pub fn solve_step(&mut self, iteration: u32, algorithm: Option<SolverAlgorithm>) -> Result<()> {
+        let algorithm = cfr_algorithm(algorithm)?;
+        self.ensure_idle()?;
+        let mut guard = self.lock()?;
+        let game = guard.as_mut()
+            .ok_or_else(|| Error::from_reason("Game not initialized"))?;
+        ensure_allocated(game)?;
+        
+        if game.is_solved() {
+            return Err(Error::from_reason("Game is already solved"));
+        }
+        
+        solve_step_with(game, algorithm, iteration);
+        Ok(())
+    }
+    
+    /// Computes the current exploitability
+    #[napi]
+    pub fn get_exploitability(&self) -> Result<f64> {
+        let guard = self.lock()?;
+        let game = guard.as_ref()
+            .ok_or_else(|| Error::from_reason("Game not initialized"))?;
+        ensure_allocated(game)?;
+        
+        Ok(compute_exploitability(game) as f64)
+    }
+    
+    /// Finalizes the solution
+    #[napi]
+    pub fn finalize(&mut self) -> Result<()> {
+        self.ensure_idle()?;
+        let mut guard = self.lock()?;
+        let game = guard.as_mut()
+            .ok_or_else(|| Error::from_reason("Game not initialized"))?;
+        ensure_allocated(game)?;
+        
+        finalize(game);
+        Ok(())
+    }
+    
+    /// Gets available actions at the current node
+    #[napi]
+    pub fn get_actions(&self) -> Result<Vec<ActionInfo>> {
+        let guard = self.lock()?;
+        let game = guard.as_ref()
+            .ok_or_else(|| Error::from_reason("Game not initialized"))?;
+        
+        action_infos(game)
+    }
+    
+    /// Plays an action
+    #[napi]
+    pub fn play_action(&mut self, action_index: u32) -> Result<()> {
+        let mut guard = self.lock()?;
+        let game = guard.as_mut()
+            .ok_or_else(|| Error::from_reason("Game not initialized"))?;
+        ensure_allocated(game)?;
+        
+        game.play(action_index as usize);
+        Ok(())
+    }
+    
+    /// Plays an action object returned by `get_actions`
+    #[napi]
+    pub fn play_action_by_action(&mut self, action: ActionInfo) -> Result<()> {
+        let mut guard = self.lock()?;
+        let game = guard.as_mut()
+            .ok_or_else(|| Error::from_reason("Game not initialized"))?;
+        ensure_allocated(game)?;
+        
+        // match by content so that stale indices are never played
+        let index = action_infos(game)?
+            .into_iter()
+            .find(|a| a.kind == action.kind && a.amount == action.amount && a.card == action.card)
+            .map(|a| a.index)
+            .ok_or_else(|| Error::from_reason("Action is not available at the current node"))?;
+        
+        game.play(index as usize);
+        Ok(())
+    }
+    
+    /// Moves to the node reached by the given history
+    #[napi]
+    pub fn apply_history(&mut self, history: Vec<u32>) -> Result<()> {
+        let mut guard = self.lock()?;
+        let game = guard.as_mut()
+            .ok_or_else(|| Error::from_reason("Game not initialized"))?;
+        ensure_allocated(game)?;
+        
+        let history = history.iter().map(|&a| a as usize).collect::<Vec<_>>();
+        game.apply_history(&history);
+        Ok(())
+    }
+    
+    /// Gets the history of the current node
+    #[napi]
+    pub fn get_history(&self) -> Result<Vec<u32>> {
+        let guard = self.lock()?;
+        let game = guard.as_ref()
+            .ok_or_else(|| Error::from_reason("Game not initialized"))?;
+        
+        Ok(game.history().iter().map(|&a| a as u32).collect())
+    }
+    
+    /// Goes back to the root node
+    #[napi]
+    pub fn back_to_root(&mut self) -> Result<()> {
+        let mut guard = self.lock()?;
+        let game = guard.as_mut()
+            .ok_or_else(|| Error::from_reason("Game not initialized"))?;
+        
+        game.back_to_root();
+        Ok(())
+    }
+    
+    /// Gets the strategy at the current node
+    #[napi]
+    pub fn get_strategy(&self) -> Result<Vec<f64>> {
+        let guard = self.lock()?;
+        let game = guard.as_ref()
+            .ok_or_else(|| Error::from_reason("Game not initialized"))?;
+        ensure_allocated(game)?;
+        
+        let strategy = game.strategy();
+        Ok(strategy.iter().map(|&x| x as f64).collect())
+    }
+    
+    /// Gets everything needed to render the current node in one call
+    #[napi]
+    pub fn get_node_snapshot(&mut self) -> Result<NodeSnapshot> {
+        let mut guard = self.lock()?;
+        let game = guard.as_mut()
+            .ok_or_else(|| Error::from_reason("Game not initialized"))?;
+        ensure_allocated(game)?;
+        
+        game.cache_normalized_weights();
+        
+        let is_terminal = game.is_terminal_node();
+        let is_chance = game.is_chance_node();
+        let is_player_node = !is_terminal && !is_chance;
+        
+        let to_strings = |cards: &[Card]| -> Result<Vec<String>> {
+            cards.iter()
+                .map(|&card| card_to_string(card).map_err(Error::from_reason))
+                .collect()
+        };
+        
+        let possible_cards = game.possible_cards();
+        let possible_cards = (0..52u8)
+            .filter(|&card| possible_cards & (1 << card) != 0)
+            .collect::<Vec<_>>();
+        
+        Ok(NodeSnapshot {
+            is_terminal,
+            is_chance,
+            current_player: is_player_node.then(|| game.current_player() as u32),
+            board: to_strings(&game.current_board())?,
+            total_bet_amount: game.total_bet_amount().to_vec(),
+            possible_cards: to_strings(&possible_cards)?,
+            actions: action_infos(game)?,
+            strategy: Float32Array::new(if is_player_node { game.strategy() } else { Vec::new() }),
+            oop: player_snapshot(game, 0)?,
+            ip: player_snapshot(game, 1)?,
+        })
+    }
+    
+    /// Locks the strategy of the given hands at the current node
+    ///
+    /// Hands that are already locked keep their strategy unless they are overwritten.
+    /// Later entries take precedence over earlier ones.
+    #[napi]
+    pub fn lock_strategy(&mut self, locks: Vec<HandLock>) -> Result<()> {
+        let mut guard = self.lock()?;
+        let game = guard.as_mut()
+            .ok_or_else(|| Error::from_reason("Game not initialized"))?;
+        ensure_allocated(game)?;
+        
+        if game.is_solved() {
+            return Err(Error::from_reason("Game is already solved"));
+        }
+        
+        if game.is_terminal_node() || game.is_chance_node() {
+            return Err(Error::from_reason("Strategy can be locked only at player nodes"));
+        }
+        
+        let player = game.current_player();
+        let num_actions = game.available_actions().len();
+        let private_cards = game.private_cards(player);
+        let num_hands = private_cards.len();
+        
+        let mut strategy = game.current_locking_strategy()
+            .unwrap_or_else(|| vec![-1.0; num_actions * num_hands]);
+        
+        for lock in &locks {
+            if lock.frequencies.len() != num_actions {
+                return Err(Error::from_reason(format!(
+                    "Expected {} frequencies for {}, got {}",
+                    num_actions, lock.hand, lock.frequencies.len()
+                )));
+            }
+            
+            let range = lock.hand.parse::<Range>()
+                .map_err(|e| Error::from_reason(format!("Invalid hand {}: {}", lock.hand, e)))?;
+            
+            let mut matched = false;
+            for (hand, &(card1, card2)) in private_cards.iter().enumerate() {
+                if range.get_weight_by_cards(card1, card2) > 0.0 {
+                    for (action, &freq) in lock.frequencies.iter().enumerate() {
+                        strategy[action * num_hands + hand] = freq as f32;
+                    }
+                    matched = true;
+                }
+            }
+            
+            if !matched {
+                return Err(Error::from_reason(format!("No hands in range match {}", lock.hand)));
+            }
+        }
+        
+        game.lock_current_strategy(&strategy);
+        Ok(())
+    }
+    
+    /// Unlocks the strategy of the current node
+    #[napi]
+    pub fn unlock_strategy(&mut self) -> Result<()> {
+        let mut guard = self.lock()?;
+        let game = guard.as_mut()
+            .ok_or_else(|| Error::from_reason("Game not initialized"))?;
+        ensure_allocated(game)?;
+        
+        if game.is_solved() {
+            return Err(Error::from_reason("Game is already solved"));
+        }
+        
+        if game.is_terminal_node() || game.is_chance_node() {
+            return Err(Error::from_reason("Strategy can be locked only at player nodes"));
+        }
+        
+        game.unlock_current_strategy();
+        Ok(())
+    }
+    
+    /// Gets the locking strategy of the current node (`-1` for unlocked hands)
+    #[napi]
+    pub fn get_locking_strategy(&self) -> Result<Option<Float32Array>> {
+        let guard = self.lock()?;
+        let game = guard.as_ref()
+            .ok_or_else(|| Error::from_reason("Game not initialized"))?;
+        ensure_allocated(game)?;
+        
+        if game.is_terminal_node() || game.is_chance_node() {
+            return Ok(None);
+        }
+        
+        Ok(game.current_locking_strategy().map(Float32Array::new))
+    }
+    
+    /// Lists all locked nodes with the actions leading to them
+    #[napi]
+    pub fn get_locked_nodes(&mut self) -> Result<Vec<LockedNode>> {
+        let mut guard = self.lock()?;
+        let game = guard.as_mut()
+            .ok_or_else(|| Error::from_reason("Game not initialized"))?;
+        ensure_allocated(game)?;
+        
+        // replay each history to describe its actions, then restore the current node
+        let current_history = game.history().to_vec();
+        let mut locked_nodes = Vec::new();
+        
+        for history in game.locked_node_histories() {
+            game.back_to_root();
+            let mut actions = Vec::with_capacity(history.len());
+            for &action in &history {
+                let info = action_infos(game)?
+                    .into_iter()
+                    .find(|a| a.index as usize == action)
+                    .ok_or_else(|| Error::from_reason("Invalid locked node history"))?;
+                actions.push(info);
+                game.play(action);
+            }
+            
+            locked_nodes.push(LockedNode {
+                history: history.iter().map(|&a| a as u32).collect(),
+                actions,
+            });
+        }
+        
+        game.apply_history(&current_history);
+        Ok(locked_nodes)
+    }
+    
+    /// Gets expected values for a player
+    #[napi]
+    pub fn get_ev(&mut self, player: u32) -> Result<Vec<f64>> {
+        let mut guard = self.lock()?;
+        let game = guard.as_mut()
+            .ok_or_else(|| Error::from_reason("Game not initialized"))?;
+        ensure_allocated(game)?;
+        
+        game.cache_normalized_weights();
+        let ev = game.expected_values(player as usize);
+        Ok(ev.iter().map(|&x| x as f64).collect())
+    }
+    
+    /// Gets equity for a player
+    #[napi]
+    pub fn get_equity(&mut self, player: u32) -> Result<Vec<f64>> {
+        let mut guard = self.lock()?;
+        let game = guard.as_mut()
+            .ok_or_else(|| Error::from_reason("Game not initialized"))?;
+        ensure_allocated(game)?;
+        
+        game.cache_normalized_weights();
+        let equity = game.equity(player as usize);
+        Ok(equity.iter().map(|&x| x as f64).collect())
+    }
+    
+    /// Classifies the private hands of a player at the current board
+    #[napi]
+    pub fn get_hand_classes(&self, player: u32) -> Result<Vec<HandClassInfo>> {
+        let guard = self.lock()?;
+        let game = guard.as_ref()
+            .ok_or_else(|| Error::from_reason("Game not initialized"))?;
+        ensure_allocated(game)?;
+        ensure_player(player)?;
+        
+        let private_cards = game.private_cards(player as usize);
+        hand_classes(game, player as usize)?
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, class)| class.map(|class| (index, class)))
+            .map(|(index, class)| {
+                Ok(HandClassInfo {
+                    index: index as u32,
+                    hand: hole_to_string(private_cards[index]).map_err(Error::from_reason)?,
+                    made_hand: class.made_hand.into(),
+                    draws: class.draws.into_iter().map(Into::into).collect(),
+                })
+            })
+            .collect()
+    }
+    
+    /// Aggregates the strategy, equity, and EV of a player by hand category at the current node
+    #[napi]
+    pub fn get_category_stats(&mut self, player: u32) -> Result<Vec<CategoryStats>> {
+        let mut guard = self.lock()?;
+        let game = guard.as_mut()
+            .ok_or_else(|| Error::from_reason("Game not initialized"))?;
+        ensure_allocated(game)?;
+        ensure_player(player)?;
+        
+        game.cache_normalized_weights();
+        category_stats(game, player as usize)
+    }
+    
+    /// Builds a new solver for the subgame rooted at the current node
+    ///
+    /// The current node must be the root or the first decision of the turn or the river. The
+    /// subgame starts from the current ranges, board, pot, and stacks, and its memory is
+    /// allocated right away.
+    #[napi]
+    pub fn create_subgame(&mut self, config: SubgameConfig) -> Result<NativeSolver> {
+        self.ensure_idle()?;
+        if let Some(player) = config.gadget_player {
+            ensure_player(player)?;
+        }
+
+        let mut guard = self.lock()?;
+        let game = guard.as_mut()
+            .ok_or_else(|| Error::from_reason("Game not initialized"))?;
+        ensure_allocated(game)?;
+
+        let base = game.subgame_tree_config().map_err(Error::from_reason)?;
+        let tree_config = build_subgame_tree_config(&config, base)?;
+        let action_tree = ActionTree::new(tree_config)
+            .map_err(|e| Error::from_reason(format!("Failed to create action tree: {}", e)))?;
+        let mut subgame = game
+            .subgame(action_tree, config.gadget_player.map(|p| p as usize))
+            .map_err(|e| Error::from_reason(format!("Failed to create subgame: {}", e)))?;
+
+        let enable_compression = choose_compression(&subgame, config.compression, config.memory_budget)?;
+        subgame.allocate_memory(enable_compression);
+
+        let solver = NativeSolver::new();
+        solver.set_game(subgame)?;
+        Ok(solver)
+    }
+
+    /// Exports the solved game from the root as a JSON tree of actions, strategies, and EVs
+    ///
+    /// The current node is kept.
+    #[napi]
+    pub fn export_tree_json(&mut self, config: Option<TreeExportConfig>) -> Result<String> {
+        self.ensure_idle()?;
+        let mut guard = self.lock()?;
+        let game = guard.as_mut()
+            .ok_or_else(|| Error::from_reason("Game not initialized"))?;
+        ensure_allocated(game)?;
+
+        let mut options = TreeExportOptions::default();
+        if let Some(config) = config {
+            if let Some(max_depth) = config.max_depth {
+                options.max_depth = max_depth as usize;
+            }
+            if let Some(expand_chance) = config.expand_chance {
+                options.expand_chance = expand_chance;
+            }
+            if let Some(include_hands) = config.include_hands {
+                options.include_hands = include_hands;
+            }
+        }
+
+        export_tree_json(game, &options).map_err(Error::from_reason)
+    }
+
+    /// Gets memory usage information
+    #[napi]
+    pub fn get_memory_usage(&self) -> Result<MemoryUsage> {
+        let guard = self.lock()?;
+        let game = guard.as_ref()
+            .ok_or_else(|| Error::from_reason("Game not initialized"))?;
+        
+        let (uncompressed, compressed) = game.memory_usage();
+        let allocated = game.is_memory_allocated();
+        
+        Ok(MemoryUsage {
+            uncompressed: uncompressed as f64,
+            compressed: compressed as f64,
+            bunching: game.memory_usage_bunching() as f64,
+            predictions: game.memory_usage_predictions() as f64,
+            allocated: allocated.is_some(),
+            compression_enabled: allocated.unwrap_or(false),
+        })
+    }
+    
+    /// Computes the bunching effect of the folded players' ranges in the background
+    ///
+    /// The flop of the current game is used. If an unfinished computation with the same
+    /// ranges was cancelled, it is resumed. The optional callback receives the phase (1-3)
+    /// and its progress in percent. Resolves to `false` if cancelled.
+    #[napi(
+        ts_args_type = "foldRanges: string[], callback?: (phase: number, percent: number) => void"
+    )]
+    pub fn compute_bunching(
+        &self,
+        fold_ranges: Vec<String>,
+        callback: Option<JsFunction>,
+    ) -> Result<AsyncTask<BunchingTask>> {
+        let flop = {
+            let guard = self.lock()?;
+            let game = guard.as_ref()
+                .ok_or_else(|| Error::from_reason("Game not initialized"))?;
+            game.card_config().flop
+        };
+        
+        let fold_ranges = fold_ranges.iter()
+            .map(|range| range.parse::<Range>())
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(Error::from_reason)?;
+        
+        let callback = match callback {
+            Some(callback) => Some(callback.create_threadsafe_function(
+                0,
+                |ctx: ThreadSafeCallContext<(u32, u32)>| Ok(vec![ctx.value.0, ctx.value.1]),
+            )?),
+            None => None,
+        };
+        
+        {
+            let mut guard = self.lock_bunching()?;
+            let data = BunchingData::new(&fold_ranges, flop).map_err(Error::from_reason)?;
+            let is_resumable = guard.as_ref().is_some_and(|current| {
+                current.flop() == data.flop() && current.fold_ranges() == data.fold_ranges()
+            });
+            if !is_resumable {
+                self.bunching_control.update(&data);
+                *guard = Some(data);
+            }
+        }
+        
+        if self.bunching_control.running.swap(true, Ordering::AcqRel) {
+            return Err(Error::from_reason("Bunching computation is running"));
+        }
+        self.bunching_control.cancelled.store(false, Ordering::Release);
+        
+        Ok(AsyncTask::new(BunchingTask {
+            bunching: self.bunching.clone(),
+            control: self.bunching_control.clone(),
+            callback,
+        }))
+    }
+    
+    /// Cancels a running bunching computation
+    #[napi]
+    pub fn cancel_bunching(&self) {
+        if self.bunching_control.running.load(Ordering::Acquire) {
+            self.bunching_control.cancelled.store(true, Ordering::Release);
+        }
+    }
+    
+    /// Gets the progress of the bunching computation
+    #[napi]
+    pub fn get_bunching_progress(&self) -> BunchingProgress {
+        let phase = self.bunching_control.phase.load(Ordering::Acquire);
+        let percent = self.bunching_control.percent.load(Ordering::Acquire);
+        BunchingProgress {
+            phase,
+            percent,
+            ready: phase == 3 && percent == 100,
+        }
+    }
+    
+    /// Applies the computed bunching effect to the current game
+    #[napi]
+    pub fn apply_bunching(&mut self) -> Result<()> {
+        let bunching = self.lock_bunching()?;
+        let data = bunching.as_ref()
+            .ok_or_else(|| Error::from_reason("Bunching data not initialized"))?;
+        
+        let mut guard = self.lock()?;
+        let game = guard.as_mut()
+            .ok_or_else(|| Error::from_reason("Game not initialized"))?;
+        
+        if game.is_solved() {
+            return Err(Error::from_reason("Game is already solved"));
+        }
+        
+        game.set_bunching_effect(data).map_err(Error::from_reason)
+    }
+    
+    /// Removes the bunching effect from the current game
+    #[napi]
+    pub fn reset_bunching(&mut self) -> Result<()> {
+        let mut guard = self.lock()?;
+        let game = guard.as_mut()
+            .ok_or_else(|| Error::from_reason("Game not initialized"))?;
+        
+        if game.is_solved() {
+            return Err(Error::from_reason("Game is already solved"));
+        }
+        
+        game.reset_bunching_effect();
+        Ok(())
+    }
+    
+    /// Saves the computed bunching data to a file
+    #[napi]
+    #[cfg(feature = "bincode")]
+    pub fn save_bunching(&self, filename: String) -> Result<()> {
+        let guard = self.lock_bunching()?;
+        let data = guard.as_ref()
+            .ok_or_else(|| Error::from_reason("Bunching data not initialized"))?;
+        
+        save_data_to_file(data, "", &filename, None)
+            .map_err(|e| Error::from_reason(format!("Failed to save: {}", e)))
+    }
+    
+    /// Loads bunching data from a file
+    #[napi]
+    #[cfg(feature = "bincode")]
+    pub fn load_bunching(&mut self, filename: String) -> Result<()> {
+        let mut guard = self.lock_bunching()?;
+        let (data, _) = load_data_from_file::<BunchingData, _>(&filename, None)
+            .map_err(|e| Error::from_reason(format!("Failed to load: {}", e)))?;
+        
+        self.bunching_control.update(&data);
+        *guard = Some(data);
+        Ok(())
+    }
+    
+    /// Solves the same spot on many flops in the background and collects root results
+    ///
+    /// The flop, turn, and river of the configuration are ignored. `flops` is either a list of
+    /// flops weighted equally or the size of a representative subset (see `getFlopSubset`).
+    /// All 1,755 strategically distinct flops with their weights are used if `flops` is absent,
+    /// which assumes suit-symmetric ranges. The average row is weighted by the flop weights.
+    /// The callback receives the number of finished flops, the total, and the last flop.
+    /// Cancellation takes effect after the current flop; the finished rows are still returned.
+    /// A flop that fails to solve is listed in `errors` and does not stop the report.
+    /// With `shortDeck`, the subset and the default flops are taken from the 36-card deck.
+    #[napi(
+        ts_args_type = "config: GameConfig, flops: string[] | number | undefined | null, maxIterations: number, targetExploitability: number, callback?: (done: number, total: number, flop: string) => void"
+    )]
+    pub fn run_aggregate_report(
+        &self,
+        config: GameConfig,
+        flops: Option<Either<Vec<String>, u32>>,
+        max_iterations: u32,
+        target_exploitability: f64,
+        callback: Option<JsFunction>,
+    ) -> Result<AsyncTask<ReportTask>> {
+        let deck = create_deck(&config);
+        let in_deck = |flop: &WeightedFlop| flop.flop.iter().all(|&card| deck.contains(card));
+        let flops = match flops {
+            Some(Either::A(flops)) => flops.iter()
+                .map(|flop| flop_from_str(flop).map(|flop| WeightedFlop { flop, weight: 1.0 }))
+                .collect::<std::result::Result<Vec<_>, _>>()
+                .map_err(Error::from_reason)?,
+            Some(Either::B(size)) => flop_subset(size as usize, deck).map_err(Error::from_reason)?,
+            None => weighted_canonical_flops().into_iter().filter(in_deck).collect(),
+        };
+        
+        let report_config = ReportConfig {
+            tree_config: build_tree_config(&config, BoardState::Flop)?,
+            ranges: create_ranges(&config, deck)?,
+            deck,
+            max_num_iterations: max_iterations,
+            target_exploitability: target_exploitability as f32,
+            enable_compression: config.compression.unwrap_or(false),
+        };
+        
+        let callback = match callback {
+            Some(callback) => Some(callback.create_threadsafe_function(
+                0,
+                |ctx: ThreadSafeCallContext<(u32, u32, String)>| {
+                    let (done, total, flop) = ctx.value;
+                    Ok(vec![
+                        ctx.env.create_uint32(done)?.into_unknown(),
+                        ctx.env.create_uint32(total)?.into_unknown(),
+                        ctx.env.create_string(&flop)?.into_unknown(),
+                    ])
+                },
+            )?),
+            None => None,
+        };
+        
+        if self.report_control.running.swap(true, Ordering::AcqRel) {
+            return Err(Error::from_reason("Report is running"));
+        }
+        self.report_control.cancelled.store(false, Ordering::Release);
+        
+        Ok(AsyncTask::new(ReportTask {
+            config: report_config,
+            flops,
+            control: self.report_control.clone(),
+            callback,
+        }))
+    }
+    
+    /// Cancels a running aggregate report
+    #[napi]
+    pub fn cancel_report(&self) {
+        if self.report_control.running.load(Ordering::Acquire) {
+            self.report_control.cancelled.store(true, Ordering::Release);
+        }
+    }
+    
+    /// Returns whether an aggregate report is in progress
+    #[napi]
+    pub fn is_report_running(&self) -> bool {
+        self.report_control.running.load(Ordering::Acquire)
+    }
+    
+    /// Saves the game to a file
+    #[napi]
+    #[cfg(feature = "bincode")]
+    pub fn save_to_file(&self, filename: String) -> Result<()> {
+        let guard = self.lock()?;
+        let game = guard.as_ref()
+            .ok_or_else(|| Error::from_reason("Game not initialized"))?;
+        
+        game.save_to_file(&filename)
+            .map_err(|e| Error::from_reason(format!("Failed to save: {}", e)))?;
+        
+        Ok(())
+    }
+    
+    /// Loads a game from a file
+    #[napi]
+    #[cfg(feature = "bincode")]
+    pub fn load_from_file(&mut self, filename: String) -> Result<()> {
+        self.ensure_idle()?;
+
+        let game = PostFlopGame::load_from_file(&filename)
+            .map_err(|e| Error::from_reason(format!("Failed to load: {}", e)))?;
+        
+        self.set_game(game)
+    }
+}
+
+/// Editable action tree exposed to Node.js
+///
+/// Lines omit chance actions. Pass the tree to `NativeSolver.buildGame` or `initGame` to build
+/// a game with it.
+#[napi]
+pub struct NativeActionTree {
+    tree: ActionTree,
+}
+
+#[napi]
+impl NativeActionTree {
+    /// Builds the action tree from the pot, stacks, and bet sizes of the game configuration
+    #[napi(constructor)]
+    pub fn new(config: GameConfig) -> Result<Self> {
+        Ok(Self {
+            tree: create_action_tree(&config)?,
+        })
+    }
+
+    /// Adds a line ending in a bet, raise, or all-in, or restores a removed line
+    #[napi]
+    pub fn add_line(&mut self, line: Vec<TreeAction>) -> Result<()> {
+        self.tree.add_line(&parse_tree_line(&line)?).map_err(Error::from_reason)
+    }
+
+    /// Removes a line and everything below it
+    #[napi]
+    pub fn remove_line(&mut self, line: Vec<TreeAction>) -> Result<()> {
+        self.tree.remove_line(&parse_tree_line(&line)?).map_err(Error::from_reason)
+    }
+
+    /// Gets the lines added to the original tree
+    #[napi]
+    pub fn get_added_lines(&self) -> Result<Vec<Vec<TreeAction>>> {
+        tree_lines(self.tree.added_lines())
+    }
+
+    /// Gets the lines removed from the original tree
+    #[napi]
+    pub fn get_removed_lines(&self) -> Result<Vec<Vec<TreeAction>>> {
+        tree_lines(self.tree.removed_lines())
+    }
+
+    /// Gets the terminal lines that should continue, which must be fixed before building a game
+    #[napi]
+    pub fn get_invalid_terminals(&self) -> Result<Vec<Vec<TreeAction>>> {
+        tree_lines(&self.tree.invalid_terminals())
+    }
+
+    /// Moves back to the root node
+    #[napi]
+    pub fn back_to_root(&mut self) {
+        self.tree.back_to_root();
+    }
+
+    /// Gets the line of the current node
+    #[napi]
+    pub fn get_history(&self) -> Result<Vec<TreeAction>> {
+        self.tree.history().iter().map(|&action| tree_action(action)).collect()
+    }
+
+    /// Moves to the node of the given line
+    #[napi]
+    pub fn apply_history(&mut self, history: Vec<TreeAction>) -> Result<()> {
+        self.tree.apply_history(&parse_tree_line(&history)?).map_err(Error::from_reason)
+    }
+
+    /// Returns whether the current node is terminal
+    #[napi]
+    pub fn is_terminal_node(&self) -> bool {
+        self.tree.is_terminal_node()
+    }
+
+    /// Returns whether the current node deals the next street
+    #[napi]
+    pub fn is_chance_node(&self) -> bool {
+        self.tree.is_chance_node()
+    }
+
+    /// Gets the actions at the current node (after the next street at chance nodes)
+    #[napi]
+    pub fn get_actions(&self) -> Result<Vec<TreeAction>> {
+        self.tree.available_actions().iter().map(|&action| tree_action(action)).collect()
+    }
+
+    /// Plays an action at the current node
+    #[napi]
+    pub fn play(&mut self, action: TreeAction) -> Result<()> {
+        self.tree.play(parse_tree_line(&[action])?[0]).map_err(Error::from_reason)
+    }
+
+    /// Undoes the last action
+    #[napi]
+    pub fn undo(&mut self) -> Result<()> {
+        self.tree.undo().map_err(Error::from_reason)
+    }
+
+    /// Adds a bet, raise, or all-in to the current node
+    #[napi]
+    pub fn add_action(&mut self, action: TreeAction) -> Result<()> {
+        self.tree.add_action(parse_tree_line(&[action])?[0]).map_err(Error::from_reason)
+    }
+
+    /// Removes an action from the current node
+    #[napi]
+    pub fn remove_action(&mut self, action: TreeAction) -> Result<()> {
+        self.tree.remove_action(parse_tree_line(&[action])?[0]).map_err(Error::from_reason)
+    }
+
+    /// Removes the current node and moves to its parent
+    #[napi]
+    pub fn remove_current_node(&mut self) -> Result<()> {
+        self.tree.remove_current_node().map_err(Error::from_reason)
+    }
+
+    /// Gets the total bet amount of each player [OOP, IP] at the current node
+    #[napi]
+    pub fn get_total_bet_amount(&self) -> Vec<i32> {
+        self.tree.total_bet_amount().to_vec()
+    }
+
+    /// Builds the action tree from the `add_line` and `remove_line` commands of a PioSOLVER
+    /// tree-building script
+    #[napi(factory)]
+    pub fn from_pio_script(script: String) -> Result<Self> {
+        let script = script.parse::<PioScript>().map_err(Error::from_reason)?;
+        Ok(Self {
+            tree: script.action_tree().map_err(Error::from_reason)?,
+        })
+    }
+
+    /// Exports the tree as a PioSOLVER tree-building script with the ranges and the board of
+    /// `config`
+    #[napi]
+    pub fn to_pio_script(&self, config: GameConfig) -> Result<String> {
+        let card_config = create_card_config(&config)?;
+        PioScript::new(&card_config, &self.tree)
+            .and_then(|script| script.to_script())
+            .map_err(Error::from_reason)
+    }
+
+    /// Gets the node counts of the tree, and its estimated memory usage if the ranges and the
+    /// board of `config` are given
+    #[napi]
+    pub fn get_stats(&self, config: Option<GameConfig>) -> Result<TreeStats> {
+        let stats = self.tree.stats();
+        let memory_usage = match config {
+            Some(config) => {
+                let card_config = create_card_config(&config)?;
+                let memory_usage = self
+                    .tree
+                    .estimate_memory_usage(&card_config)
+                    .map_err(Error::from_reason)?;
+                Some(memory_usage)
+            }
+            None => None,
+        };
+
+        Ok(TreeStats {
+            num_player_nodes: stats.num_player_nodes.iter().map(|&n| n as f64).collect(),
+            num_chance_nodes: stats.num_chance_nodes as f64,
+            num_terminal_nodes: stats.num_terminal_nodes as f64,
+            num_allin_nodes: stats.num_allin_nodes as f64,
+            max_depth: stats.max_depth as u32,
+            memory_uncompressed: memory_usage.map(|(uncompressed, _)| uncompressed as f64),
+            memory_compressed: memory_usage.map(|(_, compressed)| compressed as f64),
+        })
+    }
+}
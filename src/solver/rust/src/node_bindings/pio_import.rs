@@ -0,0 +1,42 @@
+use super::*;
+
+/// Reads the pot, stack, board, and ranges of a PioSOLVER tree-building script
+///
+/// Pass the result with `NativeActionTree.fromPioScript(script)` to `NativeSolver.initGame`.
+#[napi]
+pub fn parse_pio_script(script: String) -> Result<GameConfig> {
+    let script = script.parse::<PioScript>().map_err(Error::from_reason)?;
+    let card_config = script.card_config().map_err(Error::from_reason)?;
+    let card = |card: Card| -> Result<Option<String>> {
+        match card {
+            NOT_DEALT => Ok(None),
+            _ => card_to_string(card).map(Some).map_err(Error::from_reason),
+        }
+    };
+
+    Ok(GameConfig {
+        starting_pot: script.starting_pot,
+        effective_stack: script.effective_stack,
+        oop_range: script.ranges[0].to_string(),
+        ip_range: script.ranges[1].to_string(),
+        flop: flop_to_string(card_config.flop).map_err(Error::from_reason)?,
+        turn: card(card_config.turn)?,
+        river: card(card_config.river)?,
+        bet_sizes: None,
+        raise_sizes: None,
+        flop_sizes: None,
+        turn_sizes: None,
+        river_sizes: None,
+        turn_donk_sizes: None,
+        river_donk_sizes: None,
+        rake_rate: None,
+        rake_cap: None,
+        add_allin_threshold: None,
+        force_allin_threshold: None,
+        merging_threshold: None,
+        compression: None,
+        memory_budget: None,
+        tournament: None,
+        short_deck: None,
+    })
+}
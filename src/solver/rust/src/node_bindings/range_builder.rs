@@ -0,0 +1,146 @@
+use super::*;
+
+/// Text notation of a range
+#[napi(string_enum = "lowercase")]
+pub enum RangeFormat {
+    /// Compact notation with `+` and `-` (e.g. "QQ+,AKs:0.5")
+    Default,
+    /// PioSOLVER notation listing every hand class (e.g. "AA,KK,QQ,AKs:0.5")
+    Pio,
+    /// GTO+ notation with percentage groups (e.g. "QQ+,[50]AKs[/50]")
+    Gtoplus,
+}
+
+/// Parses a range in the default, PioSOLVER, or GTO+ notation and writes it in `format`
+#[napi]
+pub fn convert_range(range: String, format: RangeFormat) -> Result<String> {
+    let range = parse_range(&range)?;
+    Ok(match format {
+        RangeFormat::Default => range.to_string(),
+        RangeFormat::Pio => range.to_pio_string(),
+        RangeFormat::Gtoplus => range.to_gtoplus_string(),
+    })
+}
+
+/// Editable range for the range builder
+///
+/// Weights are between 0 and 1. Set operations combine the weights of each combo.
+#[napi]
+pub struct NativeRange {
+    range: Range,
+}
+
+#[napi]
+impl NativeRange {
+    /// Parses a range in the default, PioSOLVER, or GTO+ notation (default: empty)
+    #[napi(constructor)]
+    pub fn new(range: Option<String>) -> Result<Self> {
+        let range = match range {
+            Some(range) => parse_range(&range)?,
+            None => Range::new(),
+        };
+        Ok(Self { range })
+    }
+
+    /// Creates the top `percent` percent of hands by all-in equity against a random hand
+    #[napi(factory)]
+    pub fn top_percent(percent: f64) -> Result<Self> {
+        Ok(Self {
+            range: Range::top_percent(percent).map_err(Error::from_reason)?,
+        })
+    }
+
+    /// Creates a range from a 13x13 grid (rows and columns from A to 2, suited above the diagonal)
+    #[napi(factory)]
+    pub fn from_grid(grid: Vec<Vec<f64>>) -> Result<Self> {
+        if grid.len() != 13 || grid.iter().any(|row| row.len() != 13) {
+            return Err(Error::from_reason("Grid must be 13x13"));
+        }
+
+        let mut weights = [[0.0; 13]; 13];
+        for (dst, src) in weights.iter_mut().zip(&grid) {
+            for (dst, &src) in dst.iter_mut().zip(src) {
+                *dst = src as f32;
+            }
+        }
+
+        Ok(Self {
+            range: Range::from_grid(&weights).map_err(Error::from_reason)?,
+        })
+    }
+
+    /// Returns a copy of this range
+    #[napi]
+    pub fn copy(&self) -> NativeRange {
+        NativeRange { range: self.range }
+    }
+
+    /// Writes the range in the given notation (default: compact notation with `+` and `-`)
+    #[napi]
+    pub fn to_range_string(&self, format: Option<RangeFormat>) -> String {
+        match format.unwrap_or(RangeFormat::Default) {
+            RangeFormat::Default => self.range.to_string(),
+            RangeFormat::Pio => self.range.to_pio_string(),
+            RangeFormat::Gtoplus => self.range.to_gtoplus_string(),
+        }
+    }
+
+    /// Gets the average weight of each hand class as a 13x13 grid
+    #[napi]
+    pub fn get_grid(&self) -> Vec<Vec<f64>> {
+        self.range
+            .to_grid()
+            .iter()
+            .map(|row| row.iter().map(|&weight| weight as f64).collect())
+            .collect()
+    }
+
+    /// Gets the weighted number of combos that do not overlap with the dead cards (e.g. a board)
+    #[napi]
+    pub fn num_combos(&self, dead_cards: Option<String>) -> Result<f64> {
+        let dead_cards = match dead_cards {
+            Some(cards) => cards_from_str(&cards).map_err(Error::from_reason)?,
+            None => Vec::new(),
+        };
+        let mask = dead_cards.iter().fold(0u64, |mask, &card| mask | (1 << card));
+        Ok(self.range.num_combos(mask))
+    }
+
+    /// Takes the maximum weight of each combo in this range and `other`
+    #[napi]
+    pub fn union(&mut self, other: &NativeRange) {
+        self.range.union(&other.range);
+    }
+
+    /// Takes the minimum weight of each combo in this range and `other`
+    #[napi]
+    pub fn intersect(&mut self, other: &NativeRange) {
+        self.range.intersect(&other.range);
+    }
+
+    /// Subtracts the weights of `other`, removing combos that fall to zero
+    #[napi]
+    pub fn subtract(&mut self, other: &NativeRange) {
+        self.range.subtract(&other.range);
+    }
+
+    /// Multiplies every weight by `factor`, capping at 1
+    #[napi]
+    pub fn scale(&mut self, factor: f64) -> Result<()> {
+        self.range.scale(factor as f32).map_err(Error::from_reason)
+    }
+
+    /// Clamps the weight of every combo in the range to `[min, max]`
+    #[napi]
+    pub fn clamp(&mut self, min: f64, max: f64) -> Result<()> {
+        self.range
+            .clamp_weights(min as f32, max as f32)
+            .map_err(Error::from_reason)
+    }
+
+    /// Replaces every weight `w` with `1 - w`
+    #[napi]
+    pub fn invert(&mut self) {
+        self.range.invert();
+    }
+}
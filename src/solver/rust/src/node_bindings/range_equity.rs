@@ -0,0 +1,111 @@
+use super::*;
+
+/// Configuration for a range-vs-range equity computation
+#[napi(object)]
+pub struct EquityConfig {
+    pub oop_range: String,
+    pub ip_range: String,
+    /// 0-5 board cards (e.g. "Td9d6h"); preflop if absent
+    pub board: Option<String>,
+    /// Cards removed from the deck and both ranges (e.g. "AsKd")
+    pub dead_cards: Option<String>,
+    /// Samples this many runouts instead of enumerating all of them
+    pub num_samples: Option<u32>,
+    /// Seed for sampling (default: random)
+    pub seed: Option<u32>,
+}
+
+/// Per-hand equity of one range
+#[napi(object, object_from_js = false)]
+pub struct RangeEquity {
+    /// Hands (e.g. "AsKs") that do not conflict with the board and dead cards
+    pub hands: Vec<String>,
+    pub weights: Float32Array,
+    /// `NaN` for hands without any matchups
+    pub equity: Float64Array,
+}
+
+/// Result of a range-vs-range equity computation
+#[napi(object, object_from_js = false)]
+pub struct EquityReport {
+    /// Overall equity of OOP and IP, counting ties as half
+    pub equity: Vec<f64>,
+    pub win_rate: Vec<f64>,
+    pub tie_rate: f64,
+    pub num_runouts: f64,
+    pub oop: RangeEquity,
+    pub ip: RangeEquity,
+}
+
+/// Background task computing range-vs-range equity
+pub struct EquityTask {
+    ranges: [Range; 2],
+    board: Vec<Card>,
+    dead_cards: Vec<Card>,
+    method: EquityMethod,
+}
+
+impl Task for EquityTask {
+    type Output = EquityResult;
+    type JsValue = EquityReport;
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        compute_equity(&self.ranges, &self.board, &self.dead_cards, self.method)
+            .map_err(Error::from_reason)
+    }
+
+    fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+        let EquityResult { equity, win_rate, tie_rate, hands, weights, hand_equity, num_runouts } = output;
+        let [oop_hands, ip_hands] = hands;
+        let [oop_weights, ip_weights] = weights;
+        let [oop_equity, ip_equity] = hand_equity;
+
+        let range_equity = |hands: Vec<(Card, Card)>, weights: Vec<f32>, equity: Vec<f64>| -> Result<RangeEquity> {
+            Ok(RangeEquity {
+                hands: holes_to_strings(&hands).map_err(Error::from_reason)?,
+                weights: Float32Array::new(weights),
+                equity: Float64Array::new(equity),
+            })
+        };
+
+        Ok(EquityReport {
+            equity: equity.to_vec(),
+            win_rate: win_rate.to_vec(),
+            tie_rate,
+            num_runouts: num_runouts as f64,
+            oop: range_equity(oop_hands, oop_weights, oop_equity)?,
+            ip: range_equity(ip_hands, ip_weights, ip_equity)?,
+        })
+    }
+}
+
+/// Computes the equity of two ranges on a partial board in the background
+///
+/// All runouts are enumerated unless `numSamples` is given, which is much faster before the flop.
+#[napi(js_name = "computeEquity")]
+pub fn compute_range_equity(config: EquityConfig) -> Result<AsyncTask<EquityTask>> {
+    let parse_range = |range: &str| range.parse::<Range>().map_err(Error::from_reason);
+    let parse_cards = |cards: &Option<String>| {
+        cards.as_deref().map_or(Ok(Vec::new()), cards_from_str).map_err(Error::from_reason)
+    };
+
+    let method = match config.num_samples {
+        Some(num_samples) => EquityMethod::MonteCarlo {
+            num_samples: num_samples as u64,
+            seed: match config.seed {
+                Some(seed) => seed as u64,
+                None => std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .map_or(0, |d| d.as_nanos() as u64),
+            },
+        },
+        None => EquityMethod::Exact,
+    };
+
+    Ok(AsyncTask::new(EquityTask {
+        ranges: [parse_range(&config.oop_range)?, parse_range(&config.ip_range)?],
+        board: parse_cards(&config.board)?,
+        dead_cards: parse_cards(&config.dead_cards)?,
+        method,
+    }))
+}
@@ -0,0 +1,89 @@
+use super::*;
+
+/// Computes the expected prize of each player by the Malmuth-Harville ICM model
+///
+/// `payouts[i]` is the prize of the `(i + 1)`-th place.
+#[napi]
+pub fn compute_icm_equity(stacks: Vec<f64>, payouts: Vec<f64>) -> Result<Vec<f64>> {
+    compute_icm(&stacks, &payouts).map_err(Error::from_reason)
+}
+
+/// Configuration for a push/fold Nash computation
+#[napi(object)]
+pub struct PushFoldOptions {
+    /// Stacks in order of action; the last two players are the small and big blinds
+    pub stacks: Vec<f64>,
+    pub small_blind: f64,
+    pub big_blind: f64,
+    pub ante: Option<f64>,
+    /// Stacks of the tournament players not at the table (ICM only)
+    pub other_stacks: Option<Vec<f64>>,
+    /// Tournament payouts from the first place; chip EV is used if absent
+    pub payouts: Option<Vec<f64>>,
+    /// Monte Carlo board samples for the all-in equity computation (default: 10,000)
+    pub num_samples: Option<u32>,
+    /// Seed of the board sampling; the result is deterministic for the same seed (default: 0)
+    pub seed: Option<u32>,
+    /// Iterations of fictitious play (default: 1,000)
+    pub num_iterations: Option<u32>,
+}
+
+/// Result of a push/fold Nash computation
+#[napi(object, object_from_js = false)]
+pub struct PushFoldReport {
+    /// Range each player jams with when folded to
+    pub jam_ranges: Vec<String>,
+    /// `callRanges[i][j]` is the range player `j` calls a jam of player `i` with
+    pub call_ranges: Vec<Vec<String>>,
+    /// ICM equity (or expected stack with chip EV) of each player
+    pub ev: Vec<f64>,
+    pub exploitability: f64,
+}
+
+/// Background task solving a push/fold game
+pub struct PushFoldTask {
+    config: PushFoldConfig,
+}
+
+impl Task for PushFoldTask {
+    type Output = PushFoldResult;
+    type JsValue = PushFoldReport;
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        solve_push_fold(&self.config).map_err(Error::from_reason)
+    }
+
+    fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+        Ok(PushFoldReport {
+            jam_ranges: output.jam_ranges.iter().map(|range| range.to_string()).collect(),
+            call_ranges: output
+                .call_ranges
+                .iter()
+                .map(|ranges| ranges.iter().map(|range| range.to_string()).collect())
+                .collect(),
+            ev: output.ev,
+            exploitability: output.exploitability,
+        })
+    }
+}
+
+/// Solves a short-stack push/fold game in the background
+#[napi]
+pub fn solve_push_fold_game(options: PushFoldOptions) -> Result<AsyncTask<PushFoldTask>> {
+    let defaults = PushFoldConfig::default();
+    Ok(AsyncTask::new(PushFoldTask {
+        config: PushFoldConfig {
+            stacks: options.stacks,
+            small_blind: options.small_blind,
+            big_blind: options.big_blind,
+            ante: options.ante.unwrap_or(0.0),
+            other_stacks: options.other_stacks.unwrap_or_default(),
+            payouts: options.payouts.unwrap_or_default(),
+            equity_method: EquityMethod::MonteCarlo {
+                num_samples: options.num_samples.unwrap_or(10000) as u64,
+                seed: options.seed.unwrap_or(0) as u64,
+            },
+            num_iterations: options.num_iterations.unwrap_or(defaults.num_iterations),
+        },
+    }))
+}
@@ -0,0 +1,488 @@
+use crate::action_tree::*;
+use crate::card::*;
+use crate::range::*;
+use std::fmt::Write;
+use std::str::FromStr;
+
+/// A PioSOLVER tree-building script.
+///
+/// The [`PioScript`] struct implements the [`FromStr`] trait for importing a script, and
+/// [`to_script`] for exporting it. The following commands are supported, and the others (e.g.,
+/// `set_isomorphism` and `build_tree`) are ignored:
+///
+/// - `set_pot <oop> <ip> <pot>`: the starting pot is the sum of the three values.
+/// - `set_eff_stack <stack>`
+/// - `set_board <cards>`
+/// - `set_range <OOP|IP> <range>`
+/// - `add_line <amounts>` / `remove_line <amounts>`: each amount is the total chips the acting
+///   player has put in since the root, so a check after a call repeats the previous amount.
+///
+/// # Examples
+/// ```ignore
+/// let script = "set_pot 0 0 100\nset_eff_stack 200\nset_board QsJh2h\nadd_line 0 50 50 0 0 0 0\n";
+/// let script = script.parse::<PioScript>()?;
+/// let game = PostFlopGame::with_config(script.card_config()?, script.action_tree()?)?;
+/// ```
+///
+/// [`to_script`]: #method.to_script
+#[derive(Debug, Clone, Default)]
+pub struct PioScript {
+    /// Board cards (flop, and optionally turn and river).
+    pub board: Vec<Card>,
+
+    /// Ranges of OOP and IP.
+    pub ranges: [Range; 2],
+
+    /// Starting pot size.
+    pub starting_pot: i32,
+
+    /// Initial effective stack.
+    pub effective_stack: i32,
+
+    /// Lines of the `add_line` commands. Chance actions are omitted.
+    pub added_lines: Vec<Vec<Action>>,
+
+    /// Lines of the `remove_line` commands. Chance actions are omitted.
+    pub removed_lines: Vec<Vec<Action>>,
+}
+
+impl PioScript {
+    /// Creates a script that builds `action_tree` with the ranges and the board of `card_config`.
+    ///
+    /// Each line from the root to a terminal node, except for fold actions, becomes one
+    /// `add_line` command. The bet sizes of `action_tree` are not exported separately.
+    pub fn new(card_config: &CardConfig, action_tree: &ActionTree) -> Result<Self, String> {
+        if !action_tree.invalid_terminals().is_empty() {
+            return Err("Action tree has invalid terminal nodes".to_string());
+        }
+
+        let mut board = card_config.flop.to_vec();
+        for card in [card_config.turn, card_config.river] {
+            if card != NOT_DEALT {
+                board.push(card);
+            }
+        }
+
+        let config = action_tree.config();
+        let mut tree = action_tree.clone();
+        let mut added_lines = Vec::new();
+        tree.back_to_root();
+        collect_lines(&mut tree, &mut added_lines)?;
+
+        Ok(Self {
+            board,
+            ranges: card_config.range,
+            starting_pot: config.starting_pot,
+            effective_stack: config.effective_stack,
+            added_lines,
+            removed_lines: Vec::new(),
+        })
+    }
+
+    /// Returns the card configuration of the script.
+    pub fn card_config(&self) -> Result<CardConfig, String> {
+        if !(3..=5).contains(&self.board.len()) {
+            return Err(format!("Invalid board length: {}", self.board.len()));
+        }
+
+        let card = |index: usize| self.board.get(index).copied().unwrap_or(NOT_DEALT);
+        Ok(CardConfig {
+            range: self.ranges,
+            flop: [self.board[0], self.board[1], self.board[2]],
+            turn: card(3),
+            river: card(4),
+            deck: Deck::Standard,
+        })
+    }
+
+    /// Returns the tree configuration of the script.
+    ///
+    /// No bet sizes are set because all bets are given by the lines.
+    pub fn tree_config(&self) -> Result<TreeConfig, String> {
+        Ok(TreeConfig {
+            initial_state: initial_state(self.board.len())?,
+            starting_pot: self.starting_pot,
+            effective_stack: self.effective_stack,
+            ..Default::default()
+        })
+    }
+
+    /// Builds the action tree of the script.
+    pub fn action_tree(&self) -> Result<ActionTree, String> {
+        let mut tree = ActionTree::new(self.tree_config()?)?;
+
+        for line in &self.added_lines {
+            for (index, action) in line.iter().enumerate() {
+                if !matches!(action, Action::Bet(_) | Action::Raise(_) | Action::AllIn(_)) {
+                    continue;
+                }
+                tree.apply_history(&line[..index])?;
+                if !tree.available_actions().contains(action) {
+                    tree.add_line(&line[..=index])?;
+                }
+            }
+        }
+
+        for line in &self.removed_lines {
+            tree.remove_line(line)?;
+        }
+
+        tree.back_to_root();
+        Ok(tree)
+    }
+
+    /// Exports the script.
+    pub fn to_script(&self) -> Result<String, String> {
+        let card_config = self.card_config()?;
+        let board = self
+            .board
+            .iter()
+            .map(|&card| card_to_string(card))
+            .collect::<Result<String, _>>()?;
+
+        let mut ret = String::new();
+        writeln!(
+            ret,
+            "set_range OOP {}",
+            card_config.range[0].to_pio_string()
+        )
+        .unwrap();
+        writeln!(ret, "set_range IP {}", card_config.range[1].to_pio_string()).unwrap();
+        writeln!(ret, "set_board {board}").unwrap();
+        writeln!(ret, "set_pot 0 0 {}", self.starting_pot).unwrap();
+        writeln!(ret, "set_eff_stack {}", self.effective_stack).unwrap();
+        writeln!(ret, "set_isomorphism 1 0").unwrap();
+
+        for (command, lines) in [
+            ("add_line", &self.added_lines),
+            ("remove_line", &self.removed_lines),
+        ] {
+            for line in lines {
+                let amounts = line_to_amounts(line)
+                    .iter()
+                    .map(|amount| amount.to_string())
+                    .collect::<Vec<_>>();
+                writeln!(ret, "{command} {}", amounts.join(" ")).unwrap();
+            }
+        }
+
+        writeln!(ret, "build_tree").unwrap();
+        Ok(ret)
+    }
+}
+
+impl FromStr for PioScript {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut ret = Self::default();
+        let mut lines = [Vec::new(), Vec::new()];
+
+        for line in s.lines().map(str::trim) {
+            let (command, args) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+            let args = args.trim();
+
+            let parse_amounts = || {
+                args.split_whitespace()
+                    .map(|amount| {
+                        amount
+                            .parse::<i32>()
+                            .map_err(|_| format!("Invalid amount: {amount}"))
+                    })
+                    .collect::<Result<Vec<_>, String>>()
+            };
+
+            match command {
+                "set_pot" => {
+                    let amounts = parse_amounts()?;
+                    if amounts.is_empty() || amounts.len() > 3 {
+                        return Err(format!("Invalid pot: {args}"));
+                    }
+                    ret.starting_pot = amounts.iter().sum();
+                }
+                "set_eff_stack" => {
+                    ret.effective_stack = args
+                        .parse()
+                        .map_err(|_| format!("Invalid effective stack: {args}"))?;
+                }
+                "set_board" => {
+                    ret.board = cards_from_str(&args.replace(' ', ""))?;
+                }
+                "set_range" => {
+                    let (player, range) =
+                        args.split_once(char::is_whitespace).unwrap_or((args, ""));
+                    let player = match player {
+                        "OOP" => 0,
+                        "IP" => 1,
+                        _ => return Err(format!("Invalid player: {player}")),
+                    };
+                    ret.ranges[player] = range.trim().parse()?;
+                }
+                "add_line" => lines[0].push(parse_amounts()?),
+                "remove_line" => lines[1].push(parse_amounts()?),
+                _ => {}
+            }
+        }
+
+        let initial_state = initial_state(ret.board.len())?;
+        let [added_lines, removed_lines] = lines.map(|lines| {
+            lines
+                .iter()
+                .map(|amounts| line_from_amounts(amounts, initial_state, ret.effective_stack))
+                .collect::<Result<Vec<_>, String>>()
+        });
+        ret.added_lines = added_lines?;
+        ret.removed_lines = removed_lines?;
+
+        Ok(ret)
+    }
+}
+
+#[inline]
+fn initial_state(board_len: usize) -> Result<BoardState, String> {
+    match board_len {
+        3 => Ok(BoardState::Flop),
+        4 => Ok(BoardState::Turn),
+        5 => Ok(BoardState::River),
+        _ => Err(format!("Invalid board length: {board_len}")),
+    }
+}
+
+/// Converts the amounts of a PioSOLVER line into actions.
+fn line_from_amounts(
+    amounts: &[i32],
+    initial_state: BoardState,
+    effective_stack: i32,
+) -> Result<Vec<Action>, String> {
+    let mut ret = Vec::with_capacity(amounts.len());
+    let mut street = initial_state as usize;
+    let mut street_start = 0;
+    let mut invested = [0, 0];
+    let mut player = 0;
+    let mut is_street_over = false;
+
+    for &amount in amounts {
+        if is_street_over {
+            if street == BoardState::River as usize || invested[0] == effective_stack {
+                return Err(format!(
+                    "Line continues after the end of the hand: {amounts:?}"
+                ));
+            }
+            street += 1;
+            street_start = invested[0];
+            player = 0;
+            is_street_over = false;
+        }
+
+        let opponent = player ^ 1;
+        let action = if amount == invested[player] {
+            if invested[player] < invested[opponent] {
+                Action::Fold
+            } else {
+                is_street_over = player == 1;
+                Action::Check
+            }
+        } else if amount == invested[opponent] && amount > invested[player] {
+            is_street_over = true;
+            Action::Call
+        } else if amount > invested[opponent] && amount <= effective_stack {
+            let street_amount = amount - street_start;
+            if amount == effective_stack {
+                Action::AllIn(street_amount)
+            } else if invested[opponent] == street_start {
+                Action::Bet(street_amount)
+            } else {
+                Action::Raise(street_amount)
+            }
+        } else {
+            return Err(format!("Invalid amount {amount} in line: {amounts:?}"));
+        };
+
+        if matches!(ret.last(), Some(Action::Fold)) {
+            return Err(format!("Line continues after a fold: {amounts:?}"));
+        }
+
+        invested[player] = amount;
+        ret.push(action);
+        player = opponent;
+    }
+
+    Ok(ret)
+}
+
+/// Converts actions into the amounts of a PioSOLVER line.
+fn line_to_amounts(line: &[Action]) -> Vec<i32> {
+    let mut ret = Vec::with_capacity(line.len());
+    let mut street_start = 0;
+    let mut street_amount = [0, 0];
+    let mut player = 0;
+
+    for &action in line {
+        let opponent = player ^ 1;
+        let mut is_street_over = false;
+
+        match action {
+            Action::Check => is_street_over = player == 1,
+            Action::Call => {
+                street_amount[player] = street_amount[opponent];
+                is_street_over = true;
+            }
+            Action::Bet(amount) | Action::Raise(amount) | Action::AllIn(amount) => {
+                street_amount[player] = amount;
+            }
+            _ => {}
+        }
+
+        ret.push(street_start + street_amount[player]);
+        player = opponent;
+
+        if is_street_over {
+            street_start += street_amount[0];
+            street_amount = [0, 0];
+            player = 0;
+        }
+    }
+
+    ret
+}
+
+/// Collects the lines from the current node to the terminal nodes, except for fold actions.
+fn collect_lines(tree: &mut ActionTree, lines: &mut Vec<Vec<Action>>) -> Result<(), String> {
+    if tree.is_terminal_node() {
+        if tree.history().last() != Some(&Action::Fold) {
+            lines.push(tree.history().to_vec());
+        }
+        return Ok(());
+    }
+
+    for action in tree.available_actions().to_vec() {
+        tree.play(action)?;
+        collect_lines(tree, lines)?;
+        tree.undo()?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bet_size::*;
+
+    #[test]
+    fn import_pio_script() {
+        let script = "\
+            set_range OOP AA,KK:0.5\n\
+            set_range IP QQ,AKs\n\
+            set_board QsJh2h\n\
+            set_pot 0 0 100\n\
+            set_eff_stack 200\n\
+            set_isomorphism 1 0\n\
+            add_line 0 0 0 0 0 0\n\
+            add_line 0 50 150 200 200\n\
+            add_line 0 0 30 30 30 30\n\
+            add_line 50 50 50 50 150 150\n\
+            remove_line 0 0 0 0\n\
+            build_tree\n";
+
+        let script = script.parse::<PioScript>().unwrap();
+        assert_eq!(script.board, cards_from_str("QsJh2h").unwrap());
+        assert_eq!(script.ranges[0], "AA,KK:0.5".parse().unwrap());
+        assert_eq!(script.starting_pot, 100);
+        assert_eq!(script.effective_stack, 200);
+        assert_eq!(
+            script.added_lines[1],
+            vec![
+                Action::Check,
+                Action::Bet(50),
+                Action::Raise(150),
+                Action::AllIn(200),
+                Action::Call
+            ]
+        );
+        assert_eq!(
+            script.added_lines[3],
+            vec![
+                Action::Bet(50),
+                Action::Call,
+                Action::Check,
+                Action::Check,
+                Action::Bet(100),
+                Action::Call
+            ]
+        );
+        assert_eq!(
+            script.removed_lines,
+            vec![vec![
+                Action::Check,
+                Action::Check,
+                Action::Check,
+                Action::Check
+            ]]
+        );
+
+        let mut tree = script.action_tree().unwrap();
+        assert_eq!(tree.available_actions(), &[Action::Check, Action::Bet(50)]);
+        tree.apply_history(&[Action::Check, Action::Check]).unwrap();
+        assert_eq!(tree.available_actions(), &[Action::Check, Action::Bet(30)]);
+        tree.apply_history(&[Action::Check, Action::Bet(50)])
+            .unwrap();
+        assert_eq!(
+            tree.available_actions(),
+            &[Action::Fold, Action::Call, Action::Raise(150)]
+        );
+
+        let errors = [
+            "set_board QsJh2h\nset_eff_stack 200\nadd_line 0 50 0 50",
+            "set_board QsJh2h\nset_eff_stack 200\nadd_line 0 250",
+            "set_board QsJh2h\nset_eff_stack 200\nadd_line 200 200 200",
+            "set_board QsJh2hAd9c\nset_eff_stack 200\nadd_line 0 0 0",
+            "set_range BTN AA",
+        ];
+        for script in errors {
+            assert!(script.parse::<PioScript>().is_err(), "{script}");
+        }
+    }
+
+    #[test]
+    fn pio_script_round_trip() {
+        let sizes = BetSizeOptions::try_from(("50%, a", "3x")).unwrap();
+        let card_config = CardConfig {
+            range: ["QQ+,AKs:0.5".parse().unwrap(), "JJ+,AQ+".parse().unwrap()],
+            flop: flop_from_str("Td9d6h").unwrap(),
+            turn: card_from_str("Qc").unwrap(),
+            river: NOT_DEALT,
+            deck: Deck::Standard,
+        };
+        let tree_config = TreeConfig {
+            initial_state: BoardState::Turn,
+            starting_pot: 100,
+            effective_stack: 300,
+            turn_bet_sizes: [sizes.clone(), sizes.clone()],
+            river_bet_sizes: [sizes.clone(), sizes],
+            ..Default::default()
+        };
+
+        let mut action_tree = ActionTree::new(tree_config).unwrap();
+        action_tree
+            .remove_line(&[Action::Check, Action::Bet(50)])
+            .unwrap();
+
+        let script = PioScript::new(&card_config, &action_tree).unwrap();
+        let text = script.to_script().unwrap();
+        assert!(text.starts_with("set_range OOP AA,KK,QQ,AKs:0.5\nset_range IP "));
+        assert!(text.contains("\nset_board 6h9dTdQc\n"));
+        assert!(text.contains("\nadd_line 0 0 0 0\n"));
+        assert!(text.contains("\nadd_line 50 50 50 50\n"));
+
+        let imported = text.parse::<PioScript>().unwrap();
+        assert_eq!(imported.card_config().unwrap().range, card_config.range);
+        assert_eq!(imported.added_lines, script.added_lines);
+
+        let mut expected = Vec::new();
+        let mut actual = Vec::new();
+        collect_lines(&mut action_tree, &mut expected).unwrap();
+        collect_lines(&mut imported.action_tree().unwrap(), &mut actual).unwrap();
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,414 @@
+use super::equity::*;
+use super::*;
+use crate::card::*;
+use crate::interface::*;
+use crate::utility::*;
+use std::mem::{self, MaybeUninit};
+
+#[derive(Clone)]
+struct BuildTreeInfo {
+    num_raises: usize,
+    min_raise: i32,
+}
+
+impl Game for PreflopGame {
+    type Node = PreflopNode;
+
+    #[inline]
+    fn root(&self) -> MutexGuardLike<'_, Self::Node> {
+        self.root.lock()
+    }
+
+    #[inline]
+    fn num_private_hands(&self, player: usize) -> usize {
+        self.hand_combos[player].len()
+    }
+
+    #[inline]
+    fn initial_weights(&self, player: usize) -> &[f32] {
+        &self.initial_weights[player]
+    }
+
+    #[inline]
+    fn evaluate(
+        &self,
+        result: &mut [MaybeUninit<f32>],
+        node: &Self::Node,
+        player: usize,
+        cfreach: &[f32],
+    ) {
+        self.evaluate_internal(result, node, player, cfreach);
+    }
+
+    #[inline]
+    fn chance_factor(&self, _node: &Self::Node) -> usize {
+        unreachable!("Preflop game has no chance nodes")
+    }
+
+    #[inline]
+    fn is_solved(&self) -> bool {
+        self.is_solved
+    }
+
+    #[inline]
+    fn set_solved(&mut self) {
+        self.is_solved = true;
+        visit_nodes_mut(&mut self.root.lock(), &mut |node| {
+            node.predictions = Vec::new();
+        });
+    }
+
+    #[inline]
+    fn num_iterations(&self) -> u32 {
+        self.num_iterations
+    }
+
+    #[inline]
+    fn set_num_iterations(&mut self, num_iterations: u32) {
+        self.num_iterations = num_iterations;
+    }
+
+    #[inline]
+    fn is_ready(&self) -> bool {
+        self.num_nodes > 0
+    }
+
+    #[inline]
+    fn allocate_predictions(&mut self) {
+        visit_nodes_mut(&mut self.root.lock(), &mut |node| {
+            if node.predictions.len() != node.regrets.len() {
+                node.predictions = vec![0.0; node.regrets.len()];
+            }
+        });
+    }
+}
+
+impl PreflopGame {
+    /// Creates a new [`PreflopGame`] with the specified configuration and allocates the memory.
+    ///
+    /// This computes the equity between the hand classes of the ranges, which takes a while with
+    /// full ranges and [`EquityMethod::Exact`].
+    pub fn with_config(config: PreflopConfig) -> Result<Self, String> {
+        check_config(&config)?;
+
+        let mut game = Self {
+            config,
+            ..Default::default()
+        };
+
+        game.init_hands()?;
+        game.build_tree();
+        game.back_to_root();
+
+        Ok(game)
+    }
+
+    /// Returns the configuration.
+    #[inline]
+    pub fn config(&self) -> &PreflopConfig {
+        &self.config
+    }
+
+    /// Returns the list of private hands of the given player.
+    ///
+    /// Each hand is a hand class (e.g., "AKs") or a combination (e.g., "AsKs") depending on
+    /// [`PreflopConfig::hand_grouping`].
+    ///
+    /// Panics if `player` is not `0` or `1`.
+    pub fn private_hands(&self, player: usize) -> Vec<String> {
+        self.hand_combos[player]
+            .iter()
+            .map(|combos| {
+                let (c1, c2) = index_to_card_pair(combos[0].0);
+                match self.config.hand_grouping {
+                    PreflopHandGrouping::Classes => hand_class_to_string(hand_class_index(c1, c2)),
+                    PreflopHandGrouping::Combos => hole_to_string((c1, c2)).unwrap(),
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the number of nodes of the game tree.
+    #[inline]
+    pub fn num_nodes(&self) -> u64 {
+        self.num_nodes
+    }
+
+    /// Returns the memory usage of the game in bytes.
+    pub fn memory_usage(&self) -> u64 {
+        mem::size_of::<PreflopNode>() as u64
+            + vec_memory_usage(&self.pair_weights)
+            + vec_memory_usage(&self.equity)
+            + memory_usage_recursive(&self.root.lock())
+    }
+
+    /// Initializes the fields related to the hands.
+    fn init_hands(&mut self) -> Result<(), String> {
+        let config = &self.config;
+
+        for (player, range) in config.range.iter().enumerate() {
+            if range.is_empty() {
+                return Err(format!("Range of player {player} is empty"));
+            }
+
+            if !range.is_valid() {
+                return Err(format!(
+                    "Range of player {player} is invalid (loaded broken data?)"
+                ));
+            }
+        }
+
+        let class_equity = compute_class_equity(&config.range, config.equity_method)?;
+
+        for player in 0..2 {
+            let (hands, weights) = config.range[player].get_hands_weights(0);
+            let mut hand_combos = Vec::<Vec<(usize, f32)>>::new();
+            let mut class_position = [usize::MAX; NUM_HAND_CLASSES];
+
+            for (&(c1, c2), &weight) in hands.iter().zip(&weights) {
+                let combo = (card_pair_to_index(c1, c2), weight);
+                match config.hand_grouping {
+                    PreflopHandGrouping::Classes => {
+                        let class = hand_class_index(c1, c2);
+                        if class_position[class] == usize::MAX {
+                            class_position[class] = hand_combos.len();
+                            hand_combos.push(Vec::new());
+                        }
+                        hand_combos[class_position[class]].push(combo);
+                    }
+                    PreflopHandGrouping::Combos => hand_combos.push(vec![combo]),
+                }
+            }
+
+            self.initial_weights[player] = vec![1.0; hand_combos.len()];
+            self.hand_combos[player] = hand_combos;
+        }
+
+        let [combos_sb, combos_bb] = &self.hand_combos;
+        let num_pairs = combos_sb.len() * combos_bb.len();
+        self.pair_weights = Vec::with_capacity(num_pairs);
+        self.equity = Vec::with_capacity(num_pairs);
+
+        for combos_i in combos_sb {
+            for combos_j in combos_bb {
+                let mut pair_weight = 0.0;
+                for &(x, weight_x) in combos_i {
+                    let (c1, c2) = index_to_card_pair(x);
+                    let mask: u64 = (1 << c1) | (1 << c2);
+                    for &(y, weight_y) in combos_j {
+                        let (c3, c4) = index_to_card_pair(y);
+                        if mask & ((1 << c3) | (1 << c4)) == 0 {
+                            pair_weight += weight_x as f64 * weight_y as f64;
+                        }
+                    }
+                }
+
+                let (c1, c2) = index_to_card_pair(combos_i[0].0);
+                let (c3, c4) = index_to_card_pair(combos_j[0].0);
+                let class_pair =
+                    hand_class_index(c1, c2) * NUM_HAND_CLASSES + hand_class_index(c3, c4);
+
+                self.pair_weights.push(pair_weight as f32);
+                self.equity.push(class_equity[class_pair]);
+            }
+        }
+
+        self.num_combinations = self.pair_weights.iter().map(|&w| w as f64).sum();
+        if self.num_combinations <= 0.0 {
+            return Err("Valid card assignment does not exist".to_string());
+        }
+
+        Ok(())
+    }
+
+    /// Builds the game tree and allocates the memory.
+    fn build_tree(&mut self) {
+        let mut root = self.root.lock();
+        *root = PreflopNode {
+            player: PLAYER_SB as u8,
+            amount: [self.config.small_blind, self.config.big_blind],
+            ..Default::default()
+        };
+
+        let info = BuildTreeInfo {
+            num_raises: 0,
+            min_raise: self.config.big_blind,
+        };
+
+        let mut num_nodes = 0;
+        self.build_tree_recursive(&mut root, &info, &mut num_nodes);
+        self.num_nodes = num_nodes;
+    }
+
+    fn build_tree_recursive(
+        &self,
+        node: &mut PreflopNode,
+        info: &BuildTreeInfo,
+        num_nodes: &mut u64,
+    ) {
+        *num_nodes += 1;
+
+        if node.is_terminal() {
+            node.children.shrink_to_fit();
+            return;
+        }
+
+        let actions = self.compute_actions(node, info);
+        let player = node.player as usize;
+        let num_hands = self.num_private_hands(player);
+
+        node.strategy = vec![0.0; actions.len() * num_hands];
+        node.regrets = vec![0.0; actions.len() * num_hands];
+
+        for action in actions {
+            let mut next_info = info.clone();
+            let mut amount = node.amount;
+
+            let next_player = match action {
+                Action::Fold | Action::Check => PLAYER_TERMINAL,
+                Action::Call => {
+                    amount[player] = amount[player ^ 1];
+                    // the big blind has the option after the limp
+                    if node.prev_action == Action::None {
+                        PLAYER_BB as u8
+                    } else {
+                        PLAYER_TERMINAL
+                    }
+                }
+                Action::Raise(to) | Action::AllIn(to) => {
+                    next_info.min_raise = info.min_raise.max(to - amount[player ^ 1]);
+                    next_info.num_raises += 1;
+                    amount[player] = to;
+                    (player ^ 1) as u8
+                }
+                _ => panic!("Unexpected action: {action:?}"),
+            };
+
+            let mut child = PreflopNode {
+                prev_action: action,
+                player: next_player,
+                amount,
+                ..Default::default()
+            };
+
+            self.build_tree_recursive(&mut child, &next_info, num_nodes);
+            node.children.push(MutexLike::new(child));
+        }
+
+        node.children.shrink_to_fit();
+    }
+
+    /// Computes the available actions of the given player node.
+    fn compute_actions(&self, node: &PreflopNode, info: &BuildTreeInfo) -> Vec<Action> {
+        let config = &self.config;
+        let player = node.player as usize;
+
+        let prev_amount = node.amount[player ^ 1];
+        let to_call = prev_amount - node.amount[player];
+        let pot = 2 * config.ante + node.amount.iter().sum::<i32>() + to_call;
+        let max_amount = config.effective_stack;
+        let min_amount = (prev_amount + info.min_raise).min(max_amount);
+
+        let mut actions = Vec::new();
+
+        if to_call == 0 {
+            actions.push(Action::Check);
+        } else {
+            actions.push(Action::Fold);
+            if node.prev_action != Action::None || config.allow_limp {
+                actions.push(Action::Call);
+            }
+        }
+
+        if prev_amount < max_amount {
+            let raise_sizes = config.raise_sizes.get(info.num_raises);
+            for &raise_size in raise_sizes.into_iter().flatten() {
+                let amount = match raise_size {
+                    BetSize::PotRelative(ratio) => {
+                        prev_amount + (pot as f64 * ratio).round() as i32
+                    }
+                    BetSize::PrevBetRelative(ratio) => (prev_amount as f64 * ratio).round() as i32,
+                    BetSize::Additive(adder, raise_cap) => {
+                        if raise_cap != 0 && info.num_raises as i32 > raise_cap {
+                            continue;
+                        }
+                        prev_amount + adder
+                    }
+                    BetSize::AllIn => max_amount,
+                    _ => panic!("Unexpected raise size: {raise_size:?}"),
+                };
+
+                let amount = amount.clamp(min_amount, max_amount);
+                if amount == max_amount {
+                    actions.push(Action::AllIn(max_amount));
+                } else {
+                    actions.push(Action::Raise(amount));
+                }
+            }
+
+            // all-in
+            actions.push(Action::AllIn(max_amount));
+        }
+
+        // remove duplicates
+        actions.sort_unstable();
+        actions.dedup();
+        actions
+    }
+}
+
+fn check_config(config: &PreflopConfig) -> Result<(), String> {
+    if config.small_blind <= 0 {
+        return Err(format!(
+            "Small blind must be positive: {}",
+            config.small_blind
+        ));
+    }
+
+    if config.big_blind < config.small_blind {
+        return Err(format!(
+            "Big blind must be greater than or equal to the small blind: {}",
+            config.big_blind
+        ));
+    }
+
+    if config.ante < 0 {
+        return Err(format!("Ante must be non-negative: {}", config.ante));
+    }
+
+    if config.effective_stack <= config.big_blind {
+        return Err(format!(
+            "Effective stack must be greater than the big blind: {}",
+            config.effective_stack
+        ));
+    }
+
+    if config
+        .raise_sizes
+        .iter()
+        .flatten()
+        .any(|size| matches!(size, BetSize::Geometric(..)))
+    {
+        return Err("Geometric bet sizes are not supported in preflop games".to_string());
+    }
+
+    Ok(())
+}
+
+fn visit_nodes_mut(node: &mut PreflopNode, op: &mut impl FnMut(&mut PreflopNode)) {
+    op(node);
+    for child in &node.children {
+        visit_nodes_mut(&mut child.lock(), op);
+    }
+}
+
+fn memory_usage_recursive(node: &PreflopNode) -> u64 {
+    let mut ret = vec_memory_usage(&node.children)
+        + vec_memory_usage(&node.strategy)
+        + vec_memory_usage(&node.regrets)
+        + vec_memory_usage(&node.predictions);
+    for child in &node.children {
+        ret += memory_usage_recursive(&child.lock());
+    }
+    ret
+}
@@ -0,0 +1,214 @@
+use super::*;
+use crate::card::*;
+use crate::hand::*;
+use std::cmp::Ordering;
+
+/// Accumulated win, tie, and matchup weights of each pair of hand classes.
+struct ClassAccumulator {
+    win: Vec<f64>,
+    tie: Vec<f64>,
+    total: Vec<f64>,
+}
+
+/// Hands of a range with their weights and the (local) indices of their hand classes.
+struct ClassHands {
+    hands: Vec<(Card, Card)>,
+    weights: Vec<f32>,
+    classes: Vec<usize>,
+}
+
+/// Returns the index of the hand class of the given combination.
+///
+/// The index is `row * 13 + col` in the grid of [`Range::to_grid`].
+#[inline]
+pub(crate) fn hand_class_index(card1: Card, card2: Card) -> usize {
+    let (rank1, rank2) = (card1 / 4, card2 / 4);
+    let row = 12 - rank1.max(rank2) as usize;
+    let col = 12 - rank1.min(rank2) as usize;
+    if card1 % 4 == card2 % 4 {
+        row * 13 + col
+    } else {
+        col * 13 + row
+    }
+}
+
+/// Returns the string representation of the given hand class (e.g., "AKs").
+pub(super) fn hand_class_to_string(index: usize) -> String {
+    const RANKS: &[u8; 13] = b"AKQJT98765432";
+    let (row, col) = (index / 13, index % 13);
+    let (high, low) = (RANKS[row.min(col)] as char, RANKS[row.max(col)] as char);
+    match row.cmp(&col) {
+        Ordering::Equal => format!("{high}{low}"),
+        Ordering::Less => format!("{high}{low}s"),
+        Ordering::Greater => format!("{high}{low}o"),
+    }
+}
+
+/// Computes the all-in equity of each hand class of `range[0]` against each hand class of
+/// `range[1]`.
+///
+/// The `i * NUM_HAND_CLASSES + j`-th element is the equity of the `i`-th class against the
+/// `j`-th class, where the matchups of the combinations are weighted by the ranges. Pairs of
+/// classes without any matchups have the value of `0.5`.
+pub(crate) fn compute_class_equity(
+    range: &[Range; 2],
+    method: EquityMethod,
+) -> Result<Vec<f32>, String> {
+    // map the hand classes of each range to local indices
+    let mut class_ids = [Vec::new(), Vec::new()];
+    let hands = [0, 1].map(|player| {
+        let (hands, weights) = range[player].get_hands_weights(0);
+        let mut local_index = [usize::MAX; NUM_HAND_CLASSES];
+        let classes = hands
+            .iter()
+            .map(|&(c1, c2)| {
+                let class = hand_class_index(c1, c2);
+                if local_index[class] == usize::MAX {
+                    local_index[class] = class_ids[player].len();
+                    class_ids[player].push(class);
+                }
+                local_index[class]
+            })
+            .collect();
+        ClassHands {
+            hands,
+            weights,
+            classes,
+        }
+    });
+
+    let num_cols = class_ids[1].len();
+    let len = class_ids[0].len() * num_cols;
+
+    // local class index and weight of the identical combination in `range[1]`
+    let same_weight = hands[0]
+        .hands
+        .iter()
+        .map(|&(c1, c2)| {
+            let weight = range[1].get_weight_by_cards(c1, c2) as f64;
+            let class = hand_class_index(c1, c2);
+            let col = class_ids[1].iter().position(|&id| id == class);
+            col.filter(|_| weight > 0.0).map(|col| (col, weight))
+        })
+        .collect::<Vec<_>>();
+
+    let evaluate = |runouts: &mut dyn Iterator<Item = [Card; 5]>| {
+        let mut acc = ClassAccumulator {
+            win: vec![0.0; len],
+            tie: vec![0.0; len],
+            total: vec![0.0; len],
+        };
+        for runout in runouts {
+            acc.add_runout(&runout, &hands, &same_weight, num_cols);
+        }
+        acc
+    };
+
+    let deck = (0..52).collect::<Vec<Card>>();
+    let accumulators = evaluate_runouts(&deck, &[], method, evaluate)?;
+
+    let mut ret = vec![0.5; NUM_HAND_CLASSES * NUM_HAND_CLASSES];
+    for (i, &row) in class_ids[0].iter().enumerate() {
+        for (j, &col) in class_ids[1].iter().enumerate() {
+            let index = i * num_cols + j;
+            let (mut win, mut tie, mut total) = (0.0, 0.0, 0.0);
+            for acc in &accumulators {
+                win += acc.win[index];
+                tie += acc.tie[index];
+                total += acc.total[index];
+            }
+            if total > 0.0 {
+                ret[row * NUM_HAND_CLASSES + col] = ((win + 0.5 * tie) / total) as f32;
+            }
+        }
+    }
+
+    Ok(ret)
+}
+
+impl ClassAccumulator {
+    /// Adds the matchups of every pair of hands on the given complete board.
+    fn add_runout(
+        &mut self,
+        runout: &[Card; 5],
+        hands: &[ClassHands; 2],
+        same_weight: &[Option<(usize, f64)>],
+        num_cols: usize,
+    ) {
+        let board_mask = runout.iter().fold(0u64, |mask, &card| mask | (1 << card));
+        let board_hand = runout
+            .iter()
+            .fold(Hand::new(), |hand, &card| hand.add_card(card as usize));
+
+        let [strength, opponent_strength] = [0, 1].map(|player| {
+            let mut ret = hands[player]
+                .hands
+                .iter()
+                .enumerate()
+                .filter(|&(_, &(c1, c2))| board_mask & ((1 << c1) | (1 << c2)) == 0)
+                .map(|(i, &(c1, c2))| {
+                    let hand = board_hand.add_card(c1 as usize).add_card(c2 as usize);
+                    (hand.evaluate(), i)
+                })
+                .collect::<Vec<_>>();
+            ret.sort_unstable();
+            ret
+        });
+
+        let [player_hands, opponent_hands] = hands;
+        let mut all = (0..num_cols).map(|_| CardSums::new()).collect::<Vec<_>>();
+        for &(_, k) in &opponent_strength {
+            let weight = opponent_hands.weights[k] as f64;
+            all[opponent_hands.classes[k]].add(opponent_hands.hands[k], weight);
+        }
+
+        let mut lower = (0..num_cols).map(|_| CardSums::new()).collect::<Vec<_>>();
+        let mut j = 0;
+        let mut i = 0;
+
+        while i < strength.len() {
+            let value = strength[i].0;
+
+            while j < opponent_strength.len() && opponent_strength[j].0 < value {
+                let k = opponent_strength[j].1;
+                let weight = opponent_hands.weights[k] as f64;
+                lower[opponent_hands.classes[k]].add(opponent_hands.hands[k], weight);
+                j += 1;
+            }
+
+            let equal = opponent_strength[j..]
+                .iter()
+                .take_while(|&&(v, _)| v == value)
+                .map(|&(_, k)| k)
+                .collect::<Vec<_>>();
+
+            while i < strength.len() && strength[i].0 == value {
+                let k = strength[i].1;
+                let hand @ (c1, c2) = player_hands.hands[k];
+                let weight = player_hands.weights[k] as f64;
+                let offset = player_hands.classes[k] * num_cols;
+
+                for col in 0..num_cols {
+                    self.win[offset + col] += weight * lower[col].disjoint(hand);
+                    self.total[offset + col] += weight * all[col].disjoint(hand);
+                }
+
+                // the identical combination is subtracted twice by `disjoint`
+                if let Some((col, same)) = same_weight[k] {
+                    self.total[offset + col] += weight * same;
+                }
+
+                let mask: u64 = (1 << c1) | (1 << c2);
+                for &m in &equal {
+                    let (c3, c4) = opponent_hands.hands[m];
+                    if mask & ((1 << c3) | (1 << c4)) == 0 {
+                        let index = offset + opponent_hands.classes[m];
+                        self.tie[index] += weight * opponent_hands.weights[m] as f64;
+                    }
+                }
+
+                i += 1;
+            }
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use super::*;
+use std::mem::MaybeUninit;
+
+impl PreflopGame {
+    pub(super) fn evaluate_internal(
+        &self,
+        result: &mut [MaybeUninit<f32>],
+        node: &PreflopNode,
+        player: usize,
+        cfreach: &[f32],
+    ) {
+        let ante = self.config.ante;
+        let amount = node.amount[player] + ante;
+        let amount_opponent = node.amount[player ^ 1] + ante;
+        let num_hands_bb = self.hand_combos[PLAYER_BB].len();
+
+        let is_showdown = node.prev_action != Action::Fold;
+        let fold_payoff = if amount < amount_opponent {
+            -amount as f64
+        } else {
+            amount_opponent as f64
+        };
+
+        // the amounts are equal at showdown
+        let num_combinations = self.num_combinations;
+        let payoff = |index: usize| {
+            if !is_showdown {
+                return fold_payoff;
+            }
+            let equity = match player {
+                PLAYER_SB => self.equity[index] as f64,
+                _ => 1.0 - self.equity[index] as f64,
+            };
+            (2.0 * equity - 1.0) * amount as f64
+        };
+
+        for (i, r) in result.iter_mut().enumerate() {
+            let mut value = 0.0;
+            for (j, &cfreach_j) in cfreach.iter().enumerate() {
+                if cfreach_j == 0.0 {
+                    continue;
+                }
+                let index = if player == PLAYER_SB {
+                    i * num_hands_bb + j
+                } else {
+                    j * num_hands_bb + i
+                };
+                value += cfreach_j as f64 * self.pair_weights[index] as f64 * payoff(index);
+            }
+            r.write((value / num_combinations) as f32);
+        }
+    }
+}
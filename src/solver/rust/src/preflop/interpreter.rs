@@ -0,0 +1,187 @@
+use super::*;
+use crate::interface::*;
+use crate::utility::*;
+
+impl PreflopGame {
+    /// Moves the current node back to the root node.
+    #[inline]
+    pub fn back_to_root(&mut self) {
+        self.action_history.clear();
+        self.weights = self.initial_weights.clone();
+    }
+
+    /// Returns the history of the current node, i.e., the arguments of [`play`].
+    ///
+    /// [`play`]: #method.play
+    #[inline]
+    pub fn history(&self) -> &[usize] {
+        &self.action_history
+    }
+
+    /// Applies the given history from the root node.
+    #[inline]
+    pub fn apply_history(&mut self, history: &[usize]) {
+        self.back_to_root();
+        for &action in history {
+            self.play(action);
+        }
+    }
+
+    /// Returns whether the current node is a terminal node.
+    #[inline]
+    pub fn is_terminal_node(&self) -> bool {
+        self.node().is_terminal()
+    }
+
+    /// Returns the available actions for the current node.
+    #[inline]
+    pub fn available_actions(&self) -> Vec<Action> {
+        self.node()
+            .children
+            .iter()
+            .map(|child| child.lock().prev_action)
+            .collect()
+    }
+
+    /// Returns the current player ([`PLAYER_SB`] or [`PLAYER_BB`]).
+    ///
+    /// If the current node is a terminal node, returns an undefined value.
+    #[inline]
+    pub fn current_player(&self) -> usize {
+        self.node().player()
+    }
+
+    /// Returns the total bet amount of each player including the blinds (excluding the ante).
+    #[inline]
+    pub fn total_bet_amount(&self) -> [i32; 2] {
+        self.node().amount
+    }
+
+    /// Plays the `action`-th action of [`available_actions`]. Playing an action from a terminal
+    /// node is not allowed.
+    ///
+    /// [`available_actions`]: #method.available_actions
+    pub fn play(&mut self, action: usize) {
+        let node = self.node();
+        if node.is_terminal() {
+            panic!("Terminal node is not allowed");
+        }
+
+        let num_actions = node.num_actions();
+        if action >= num_actions {
+            panic!("Invalid action: {action}");
+        }
+
+        let player = node.player();
+        let num_hands = self.num_private_hands(player);
+        let strategy = normalized_strategy(node.strategy(), num_actions);
+        let row = &strategy[action * num_hands..(action + 1) * num_hands];
+
+        self.weights[player]
+            .iter_mut()
+            .zip(row)
+            .for_each(|(weight, &probability)| *weight *= probability);
+
+        self.action_history.push(action);
+    }
+
+    /// Returns the reach probabilities of each private hand of the given player at the current
+    /// node, i.e., the probabilities of the actions taken (the range weights are not included).
+    #[inline]
+    pub fn weights(&self, player: usize) -> &[f32] {
+        &self.weights[player]
+    }
+
+    /// Returns the range of the given player at the current node, i.e., the initial range weights
+    /// multiplied by the probabilities of the actions taken.
+    ///
+    /// The result can be used as the range of [`CardConfig`] for the postflop game.
+    ///
+    /// [`CardConfig`]: crate::CardConfig
+    pub fn range(&self, player: usize) -> Range {
+        let mut data = vec![0.0; 52 * 51 / 2];
+        for (combos, &weight) in self.hand_combos[player].iter().zip(&self.weights[player]) {
+            for &(index, combo_weight) in combos {
+                data[index] = (combo_weight * weight).min(1.0);
+            }
+        }
+        Range::from_raw_data(&data).unwrap()
+    }
+
+    /// Returns the average strategy of the current player.
+    ///
+    /// The probability of the `i`-th action with the `j`-th private hand is stored in the
+    /// `i * #(private hands) + j`-th element.
+    ///
+    /// Panics if the current node is a terminal node.
+    pub fn strategy(&self) -> Vec<f32> {
+        let node = self.node();
+        if node.is_terminal() {
+            panic!("Terminal node is not allowed");
+        }
+
+        normalized_strategy(node.strategy(), node.num_actions())
+    }
+
+    /// Returns the expected value of each private hand of the current player.
+    ///
+    /// As in [`PostFlopGame::expected_values`], the expected value is the amount of chips the
+    /// player is expected to get from the pot, including the blinds and the ante of the player.
+    /// Hands that cannot be combined with any hands of the opponent have the value of `0`.
+    ///
+    /// Panics if the game is not solved or if the current node is a terminal node.
+    ///
+    /// [`PostFlopGame::expected_values`]: crate::PostFlopGame::expected_values
+    pub fn expected_values(&self) -> Vec<f32> {
+        if !self.is_solved {
+            panic!("Game is not solved");
+        }
+
+        let strategy = self.strategy();
+        let node = self.node();
+        let player = node.player();
+        let num_hands = self.num_private_hands(player);
+        let num_hands_bb = self.num_private_hands(PLAYER_BB);
+        let offset = (node.amount[player] + self.config.ante) as f64;
+
+        (0..num_hands)
+            .map(|i| {
+                let cfreach_sum = self.weights[player ^ 1]
+                    .iter()
+                    .enumerate()
+                    .map(|(j, &weight)| {
+                        let index = match player {
+                            PLAYER_SB => i * num_hands_bb + j,
+                            _ => j * num_hands_bb + i,
+                        };
+                        weight as f64 * self.pair_weights[index] as f64
+                    })
+                    .sum::<f64>();
+
+                if cfreach_sum == 0.0 {
+                    return 0.0;
+                }
+
+                let cfvalue = strategy
+                    .iter()
+                    .skip(i)
+                    .step_by(num_hands)
+                    .zip(node.cfvalues().iter().skip(i).step_by(num_hands))
+                    .fold(0.0, |acc, (&s, &v)| acc + s as f64 * v as f64);
+
+                (cfvalue * self.num_combinations / cfreach_sum + offset) as f32
+            })
+            .collect()
+    }
+
+    /// Returns the current node.
+    fn node(&self) -> MutexGuardLike<'_, PreflopNode> {
+        let mut node: &MutexLike<PreflopNode> = &self.root;
+        for &action in &self.action_history {
+            // This is safe because the children are owned by the game tree, which is not modified
+            // while `self` is borrowed.
+            node = unsafe { &*(&node.lock().children[action] as *const MutexLike<PreflopNode>) };
+        }
+        node.lock()
+    }
+}
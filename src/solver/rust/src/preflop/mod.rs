@@ -0,0 +1,163 @@
+mod base;
+pub(crate) mod equity;
+mod evaluation;
+mod interpreter;
+mod node;
+
+#[cfg(test)]
+mod tests;
+
+use crate::action_tree::*;
+use crate::bet_size::*;
+use crate::equity::*;
+use crate::mutex_like::*;
+use crate::range::*;
+
+/// The player index of the small blind, who acts first preflop (and last postflop).
+pub const PLAYER_SB: usize = 0;
+
+/// The player index of the big blind.
+pub const PLAYER_BB: usize = 1;
+
+const PLAYER_TERMINAL: u8 = 2;
+
+/// The number of hand classes (e.g., "AA", "AKs", "AKo").
+pub const NUM_HAND_CLASSES: usize = 169;
+
+/// Available granularities of the preflop strategy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PreflopHandGrouping {
+    /// Each of the 169 hand classes has a single strategy (e.g., all combinations of "AKs" play
+    /// the same).
+    #[default]
+    Classes,
+
+    /// Each of the 1326 combinations has its own strategy.
+    Combos,
+}
+
+/// A struct containing the configuration of a heads-up preflop game.
+///
+/// The small blind acts first. Each raise level has its own raise sizes: `raise_sizes[0]` is used
+/// for the open (or a raise after a limp), `raise_sizes[1]` for the 3-bet, and so on. All-in is
+/// always available, and once `raise_sizes` is exhausted it is the only raise option.
+///
+/// # Examples
+/// ```
+/// use postflop_solver::*;
+///
+/// let config = PreflopConfig {
+///     range: [Range::ones(), Range::ones()],
+///     small_blind: 1,
+///     big_blind: 2,
+///     effective_stack: 40,
+///     raise_sizes: vec![
+///         BetSizeOptions::try_from(("", "2.5x")).unwrap().raise, // open
+///         BetSizeOptions::try_from(("", "3x")).unwrap().raise,   // 3-bet
+///     ],
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone)]
+pub struct PreflopConfig {
+    /// Initial range of the small blind and the big blind.
+    pub range: [Range; 2],
+
+    /// Small blind. Must be positive.
+    pub small_blind: i32,
+
+    /// Big blind. Must be greater than or equal to the small blind.
+    pub big_blind: i32,
+
+    /// Ante paid by each player. Antes are dead money and are not included in the stacks.
+    pub ante: i32,
+
+    /// Effective stack including the blinds. Must be greater than the big blind.
+    pub effective_stack: i32,
+
+    /// Raise sizes of each raise level. Geometric sizes are not supported.
+    pub raise_sizes: Vec<Vec<BetSize>>,
+
+    /// Whether the small blind is allowed to just call the big blind.
+    pub allow_limp: bool,
+
+    /// Granularity of the strategy.
+    pub hand_grouping: PreflopHandGrouping,
+
+    /// Method of computing the all-in equity between the hand classes.
+    pub equity_method: EquityMethod,
+}
+
+impl Default for PreflopConfig {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            range: Default::default(),
+            small_blind: 0,
+            big_blind: 0,
+            ante: 0,
+            effective_stack: 0,
+            raise_sizes: Vec::new(),
+            allow_limp: false,
+            hand_grouping: PreflopHandGrouping::Classes,
+            equity_method: EquityMethod::MonteCarlo {
+                num_samples: 10000,
+                seed: 0,
+            },
+        }
+    }
+}
+
+/// A struct representing a heads-up preflop game.
+///
+/// Every line that is not ended by a fold goes to showdown, where each player realizes the
+/// all-in equity of the hand. The equity is computed between the hand classes (e.g., "AKs" vs.
+/// "QQ") at construction and is shared by all combinations of the same classes; card removal is
+/// still accounted for exactly.
+///
+/// The game implements [`Game`] and is solved with the same functions as [`PostFlopGame`], e.g.,
+/// [`solve`]. The solved strategy of a line can be converted into a [`Range`] with [`range`] and
+/// used as the input of a postflop game.
+///
+/// [`Game`]: crate::Game
+/// [`PostFlopGame`]: crate::PostFlopGame
+/// [`solve`]: crate::solve
+/// [`range`]: #method.range
+#[derive(Default)]
+pub struct PreflopGame {
+    // configuration
+    config: PreflopConfig,
+    root: Box<MutexLike<PreflopNode>>,
+
+    // computed from the configuration
+    num_combinations: f64,
+    initial_weights: [Vec<f32>; 2],
+    num_nodes: u64,
+    is_solved: bool,
+    num_iterations: u32,
+
+    // combinations (index by `card_pair_to_index`) and their weights of each private hand
+    hand_combos: [Vec<Vec<(usize, f32)>>; 2],
+
+    // weighted number of compatible combinations of each pair of private hands
+    pair_weights: Vec<f32>,
+
+    // equity of each private hand of the small blind against each private hand of the big blind
+    equity: Vec<f32>,
+
+    // result interpreter
+    action_history: Vec<usize>,
+    weights: [Vec<f32>; 2],
+}
+
+/// A struct representing a node in a heads-up preflop game tree.
+#[derive(Debug, Default)]
+pub struct PreflopNode {
+    prev_action: Action,
+    player: u8,
+    amount: [i32; 2],
+    children: Vec<MutexLike<PreflopNode>>,
+    strategy: Vec<f32>,
+    regrets: Vec<f32>, // regrets or cfvalues
+    predictions: Vec<f32>,
+}
@@ -0,0 +1,74 @@
+use super::*;
+use crate::interface::*;
+
+impl GameNode for PreflopNode {
+    #[inline]
+    fn is_terminal(&self) -> bool {
+        self.player == PLAYER_TERMINAL
+    }
+
+    #[inline]
+    fn is_chance(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    fn player(&self) -> usize {
+        self.player as usize
+    }
+
+    #[inline]
+    fn num_actions(&self) -> usize {
+        self.children.len()
+    }
+
+    #[inline]
+    fn play(&self, action: usize) -> MutexGuardLike<'_, Self> {
+        self.children[action].lock()
+    }
+
+    #[inline]
+    fn strategy(&self) -> &[f32] {
+        &self.strategy
+    }
+
+    #[inline]
+    fn strategy_mut(&mut self) -> &mut [f32] {
+        &mut self.strategy
+    }
+
+    #[inline]
+    fn regrets(&self) -> &[f32] {
+        &self.regrets
+    }
+
+    #[inline]
+    fn regrets_mut(&mut self) -> &mut [f32] {
+        &mut self.regrets
+    }
+
+    #[inline]
+    fn cfvalues(&self) -> &[f32] {
+        &self.regrets
+    }
+
+    #[inline]
+    fn cfvalues_mut(&mut self) -> &mut [f32] {
+        &mut self.regrets
+    }
+
+    #[inline]
+    fn predictions(&self) -> &[f32] {
+        &self.predictions
+    }
+
+    #[inline]
+    fn predictions_mut(&mut self) -> &mut [f32] {
+        &mut self.predictions
+    }
+
+    #[inline]
+    fn enable_parallelization(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,226 @@
+use super::equity::*;
+use super::*;
+use crate::card::*;
+use crate::solver::*;
+use crate::utility::*;
+
+fn raise_sizes(sizes: &[&str]) -> Vec<Vec<BetSize>> {
+    sizes
+        .iter()
+        .map(|&size| BetSizeOptions::try_from(("", size)).unwrap().raise)
+        .collect()
+}
+
+#[test]
+fn hand_classes() {
+    let mut num_combos = [0; NUM_HAND_CLASSES];
+    for index in 0..52 * 51 / 2 {
+        let (c1, c2) = index_to_card_pair(index);
+        num_combos[hand_class_index(c1, c2)] += 1;
+    }
+
+    let mut range = Range::new();
+    for (class, &n) in num_combos.iter().enumerate() {
+        let name = hand_class_to_string(class);
+        let expected = match name.len() {
+            2 => 6,
+            _ if name.ends_with('s') => 4,
+            _ => 12,
+        };
+        assert_eq!(n, expected);
+
+        let mut grid = [[0.0; 13]; 13];
+        grid[class / 13][class % 13] = 1.0;
+        assert_eq!(Range::from_grid(&grid).unwrap(), name.parse().unwrap());
+        range.union(&name.parse().unwrap());
+    }
+
+    assert_eq!(range, Range::ones());
+}
+
+#[test]
+fn preflop_tree() {
+    let config = PreflopConfig {
+        range: ["AA,KK".parse().unwrap(), "AA,KK".parse().unwrap()],
+        small_blind: 1,
+        big_blind: 2,
+        effective_stack: 20,
+        raise_sizes: raise_sizes(&["2.5x", "3x"]),
+        allow_limp: true,
+        equity_method: EquityMethod::MonteCarlo {
+            num_samples: 100,
+            seed: 0,
+        },
+        ..Default::default()
+    };
+
+    let mut game = PreflopGame::with_config(config.clone()).unwrap();
+    assert_eq!(game.private_hands(PLAYER_SB), vec!["KK", "AA"]);
+
+    assert_eq!(game.current_player(), PLAYER_SB);
+    assert_eq!(
+        game.available_actions(),
+        vec![
+            Action::Fold,
+            Action::Call,
+            Action::Raise(5),
+            Action::AllIn(20)
+        ]
+    );
+
+    // open, 3-bet, then only all-in is left
+    game.apply_history(&[2]);
+    assert_eq!(game.current_player(), PLAYER_BB);
+    assert_eq!(
+        game.available_actions(),
+        vec![
+            Action::Fold,
+            Action::Call,
+            Action::Raise(15),
+            Action::AllIn(20)
+        ]
+    );
+    game.play(2);
+    assert_eq!(
+        game.available_actions(),
+        vec![Action::Fold, Action::Call, Action::AllIn(20)]
+    );
+    game.play(1);
+    assert!(game.is_terminal_node());
+    assert_eq!(game.total_bet_amount(), [15, 15]);
+
+    // limp, then the big blind has the option
+    game.apply_history(&[1]);
+    assert_eq!(game.current_player(), PLAYER_BB);
+    assert_eq!(
+        game.available_actions(),
+        vec![Action::Check, Action::Raise(5), Action::AllIn(20)]
+    );
+    game.play(0);
+    assert!(game.is_terminal_node());
+
+    let game = PreflopGame::with_config(PreflopConfig {
+        allow_limp: false,
+        hand_grouping: PreflopHandGrouping::Combos,
+        ..config.clone()
+    })
+    .unwrap();
+    assert_eq!(game.private_hands(PLAYER_BB).len(), 12);
+    assert_eq!(
+        game.available_actions(),
+        vec![Action::Fold, Action::Raise(5), Action::AllIn(20)]
+    );
+
+    let invalid_configs = [
+        PreflopConfig {
+            effective_stack: 2,
+            ..config.clone()
+        },
+        PreflopConfig {
+            raise_sizes: vec![vec![BetSize::Geometric(0, 1.0)]],
+            ..config.clone()
+        },
+        PreflopConfig {
+            range: ["AsAh".parse().unwrap(), "AsAh".parse().unwrap()],
+            ..config
+        },
+    ];
+    for config in invalid_configs {
+        assert!(PreflopGame::with_config(config).is_err());
+    }
+}
+
+#[test]
+fn preflop_push_fold() {
+    let config = PreflopConfig {
+        range: ["AA".parse().unwrap(), "KK".parse().unwrap()],
+        small_blind: 1,
+        big_blind: 2,
+        ante: 1,
+        effective_stack: 20,
+        equity_method: EquityMethod::MonteCarlo {
+            num_samples: 20000,
+            seed: 0,
+        },
+        ..Default::default()
+    };
+
+    let mut game = PreflopGame::with_config(config).unwrap();
+    let equity = game.equity[0];
+    assert!((0.80..0.84).contains(&equity));
+
+    let exploitability = solve(&mut game, 1000, 0.0, false);
+    assert!(exploitability < 0.01);
+
+    // aces always shove and kings always fold: the small blind wins the blind and the ante
+    let ev = compute_current_ev(&game);
+    assert!((ev[0] - 3.0).abs() < 0.01);
+    assert!((ev[0] + ev[1]).abs() < 1e-4);
+
+    assert_eq!(
+        game.available_actions(),
+        vec![Action::Fold, Action::AllIn(20)]
+    );
+    assert!(game.strategy()[1] > 0.99);
+    assert!((game.expected_values()[0] - 5.0).abs() < 0.01);
+
+    // folding kings get nothing back from the pot
+    game.play(1);
+    assert!(game.strategy()[0] > 0.99);
+    assert!(game.expected_values()[0].abs() < 0.01);
+    game.play(1);
+    assert!(game.range(PLAYER_BB).raw_data().iter().all(|&w| w < 0.01));
+}
+
+#[test]
+fn preflop_solve() {
+    let range = "AA,KK,QQ,JJ,AKs,AKo,AQs,KQs,76s,72o";
+    let config = PreflopConfig {
+        range: [range.parse().unwrap(), range.parse().unwrap()],
+        small_blind: 1,
+        big_blind: 2,
+        effective_stack: 40,
+        raise_sizes: raise_sizes(&["2.5x", "3x"]),
+        allow_limp: true,
+        hand_grouping: PreflopHandGrouping::Combos,
+        equity_method: EquityMethod::MonteCarlo {
+            num_samples: 2000,
+            seed: 1,
+        },
+        ..Default::default()
+    };
+
+    let mut game = PreflopGame::with_config(config.clone()).unwrap();
+    assert!(game.memory_usage() > 0);
+
+    let initial = compute_exploitability(&game);
+    let exploitability = solve(&mut game, 200, 0.0, false);
+    assert!(exploitability < initial * 0.1);
+
+    let ev = compute_current_ev(&game);
+    assert!((ev[0] + ev[1]).abs() < 1e-3);
+
+    // the range after the open and the call is a subset of the initial range
+    game.apply_history(&[2, 1]);
+    assert!(game.is_terminal_node());
+    let initial_range: Range = range.parse().unwrap();
+    for player in 0..2 {
+        let range = game.range(player);
+        assert!(!range.is_empty());
+        let mut data = range.raw_data().iter().zip(initial_range.raw_data());
+        assert!(data.all(|(&w, &initial)| w <= initial));
+    }
+
+    // the classes mode produces the same weights for the combinations of a class
+    let mut game = PreflopGame::with_config(PreflopConfig {
+        hand_grouping: PreflopHandGrouping::Classes,
+        ..config
+    })
+    .unwrap();
+    solve(&mut game, 100, 0.0, false);
+    game.apply_history(&[2]);
+    let range = game.range(PLAYER_SB);
+    let aces = cards_from_str("AcAdAhAs").unwrap();
+    let weight = range.get_weight_by_cards(aces[0], aces[1]);
+    assert_eq!(range.get_weight_by_cards(aces[2], aces[3]), weight);
+}
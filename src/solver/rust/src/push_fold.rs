@@ -0,0 +1,563 @@
+use crate::card::*;
+use crate::equity::*;
+use crate::icm::*;
+use crate::preflop::equity::*;
+use crate::range::*;
+use crate::NUM_HAND_CLASSES;
+
+/// The maximum number of players of a push/fold game.
+pub const MAX_PUSH_FOLD_PLAYERS: usize = 10;
+
+/// A struct containing the configuration of a push/fold game.
+///
+/// Players are listed in order of action: the last two players are the small blind and the big
+/// blind (in heads-up, the first player is the small blind). Each player either folds or moves
+/// all-in if nobody has moved all-in yet; otherwise the player either folds or calls. After a call,
+/// the remaining players always fold, i.e., multiway all-ins are not considered.
+#[derive(Debug, Clone)]
+pub struct PushFoldConfig {
+    /// Stack of each player at the start of the hand, including the blinds and the ante.
+    pub stacks: Vec<f64>,
+
+    /// Small blind.
+    pub small_blind: f64,
+
+    /// Big blind.
+    pub big_blind: f64,
+
+    /// Ante paid by each player.
+    pub ante: f64,
+
+    /// Stacks of the players of the tournament who are not at the table (only used for ICM).
+    pub other_stacks: Vec<f64>,
+
+    /// Payouts of the tournament from the first place. If empty, the chip EV is maximized instead
+    /// of the ICM equity.
+    pub payouts: Vec<f64>,
+
+    /// Method of computing the all-in equity between the hand classes.
+    pub equity_method: EquityMethod,
+
+    /// Number of iterations of fictitious play.
+    pub num_iterations: u32,
+}
+
+impl Default for PushFoldConfig {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            stacks: Vec::new(),
+            small_blind: 0.0,
+            big_blind: 0.0,
+            ante: 0.0,
+            other_stacks: Vec::new(),
+            payouts: Vec::new(),
+            equity_method: EquityMethod::MonteCarlo {
+                num_samples: 10000,
+                seed: 0,
+            },
+            num_iterations: 1000,
+        }
+    }
+}
+
+/// A result of [`solve_push_fold`].
+#[derive(Debug, Clone)]
+pub struct PushFoldResult {
+    /// Range each player moves all-in with when everyone before has folded.
+    pub jam_ranges: Vec<Range>,
+
+    /// `call_ranges[i][j]` is the range player `j` calls an all-in of player `i` with (empty if
+    /// `j <= i`).
+    pub call_ranges: Vec<Vec<Range>>,
+
+    /// Expected value of each player at the start of the hand: the ICM equity if `payouts` is
+    /// given, and the expected stack otherwise.
+    pub ev: Vec<f64>,
+
+    /// Sum of the gains of the players by switching to the best response.
+    pub exploitability: f64,
+}
+
+/// Strategies of all players (the probabilities of moving all-in and calling).
+#[derive(Clone)]
+struct Strategy {
+    jam: Vec<[f64; NUM_HAND_CLASSES]>,
+    call: Vec<Vec<[f64; NUM_HAND_CLASSES]>>,
+}
+
+/// Precomputed values of a push/fold game.
+struct PushFoldGame {
+    num_players: usize,
+
+    // all-in equity of each pair of hand classes
+    equity: Vec<f32>,
+
+    // number of compatible combinations of each pair of hand classes
+    pair_weights: Vec<f64>,
+
+    // probability of each hand class
+    class_prob: [f64; NUM_HAND_CLASSES],
+
+    // values of each player when the big blind wins without a fight
+    walk: Vec<f64>,
+
+    // values of each player when the player moves all-in and everyone folds
+    steal: Vec<Vec<f64>>,
+
+    // values of each player when `i` moves all-in, `j` calls, and `i` (0) or `j` (1) wins
+    showdown: Vec<Vec<[Vec<f64>; 2]>>,
+}
+
+/// Computes an approximate Nash equilibrium of a push/fold game by fictitious play.
+///
+/// The all-in equity is computed between the hand classes and card removal is considered only
+/// between the player and the all-in player. In multiway games, the result may not be an
+/// equilibrium; see `exploitability` of the result.
+pub fn solve_push_fold(config: &PushFoldConfig) -> Result<PushFoldResult, String> {
+    check_config(config)?;
+
+    let game = PushFoldGame::new(config)?;
+    let n = game.num_players;
+    let mut strategy = Strategy {
+        jam: vec![[0.5; NUM_HAND_CLASSES]; n],
+        call: vec![vec![[0.5; NUM_HAND_CLASSES]; n]; n],
+    };
+
+    for iteration in 0..config.num_iterations {
+        let best_response = game.best_response(&strategy);
+        let step = 1.0 / (iteration + 2) as f64;
+        for (dst, src) in strategy.jam.iter_mut().zip(&best_response.jam) {
+            update_average(dst, src, step);
+        }
+        for (dst, src) in strategy.call.iter_mut().zip(&best_response.call) {
+            for (dst, src) in dst.iter_mut().zip(src) {
+                update_average(dst, src, step);
+            }
+        }
+    }
+
+    let ev = game.evaluate(&strategy);
+    let best_response = game.best_response(&strategy);
+    let exploitability = (0..n)
+        .map(|player| {
+            let mut deviation = strategy.clone();
+            deviation.jam[player] = best_response.jam[player];
+            for (call, best_call) in deviation.call.iter_mut().zip(&best_response.call) {
+                call[player] = best_call[player];
+            }
+            game.evaluate(&deviation)[player] - ev[player]
+        })
+        .sum();
+
+    let to_range = |probs: &[f64; NUM_HAND_CLASSES]| {
+        let mut grid = [[0.0; 13]; 13];
+        for (class, &prob) in probs.iter().enumerate() {
+            grid[class / 13][class % 13] = prob as f32;
+        }
+        Range::from_grid(&grid).unwrap()
+    };
+
+    Ok(PushFoldResult {
+        jam_ranges: (0..n)
+            .map(|i| match i {
+                _ if i == n - 1 => Range::new(),
+                _ => to_range(&strategy.jam[i]),
+            })
+            .collect(),
+        call_ranges: (0..n)
+            .map(|i| {
+                (0..n)
+                    .map(|j| match j {
+                        _ if j <= i => Range::new(),
+                        _ => to_range(&strategy.call[i][j]),
+                    })
+                    .collect()
+            })
+            .collect(),
+        ev,
+        exploitability,
+    })
+}
+
+#[inline]
+fn update_average(dst: &mut [f64; NUM_HAND_CLASSES], src: &[f64; NUM_HAND_CLASSES], step: f64) {
+    dst.iter_mut()
+        .zip(src)
+        .for_each(|(d, &s)| *d += (s - *d) * step);
+}
+
+fn check_config(config: &PushFoldConfig) -> Result<(), String> {
+    let n = config.stacks.len();
+    if !(2..=MAX_PUSH_FOLD_PLAYERS).contains(&n) {
+        return Err(format!(
+            "Number of players must be between 2 and {MAX_PUSH_FOLD_PLAYERS}: {n}"
+        ));
+    }
+
+    if config.small_blind <= 0.0 || config.big_blind < config.small_blind {
+        return Err(format!(
+            "Invalid blinds: small blind = {}, big blind = {}",
+            config.small_blind, config.big_blind
+        ));
+    }
+
+    if config.ante < 0.0 {
+        return Err(format!("Ante must be non-negative: {}", config.ante));
+    }
+
+    for (player, &stack) in config.stacks.iter().enumerate() {
+        if stack <= forced_bet(config, player) {
+            return Err(format!(
+                "Stack of player {player} must be greater than the blind and the ante: {stack}"
+            ));
+        }
+    }
+
+    if config.num_iterations == 0 {
+        return Err("Number of iterations must be positive".to_string());
+    }
+
+    Ok(())
+}
+
+/// Returns the amount the given player posts before the cards are dealt.
+#[inline]
+fn forced_bet(config: &PushFoldConfig, player: usize) -> f64 {
+    let n = config.stacks.len();
+    match n - player {
+        2 => config.ante + config.small_blind,
+        1 => config.ante + config.big_blind,
+        _ => config.ante,
+    }
+}
+
+impl PushFoldGame {
+    fn new(config: &PushFoldConfig) -> Result<Self, String> {
+        let n = config.stacks.len();
+        let ranges = [Range::ones(), Range::ones()];
+        let equity = compute_class_equity(&ranges, config.equity_method)?;
+
+        let mut pair_weights = vec![0.0; NUM_HAND_CLASSES * NUM_HAND_CLASSES];
+        let mut class_prob = [0.0; NUM_HAND_CLASSES];
+        for x in 0..52 * 51 / 2 {
+            let (c1, c2) = index_to_card_pair(x);
+            let class_x = hand_class_index(c1, c2);
+            class_prob[class_x] += 1.0 / (52 * 51 / 2) as f64;
+            for y in 0..52 * 51 / 2 {
+                let (c3, c4) = index_to_card_pair(y);
+                if c1 != c3 && c1 != c4 && c2 != c3 && c2 != c4 {
+                    pair_weights[class_x * NUM_HAND_CLASSES + hand_class_index(c3, c4)] += 1.0;
+                }
+            }
+        }
+
+        // values of the final stacks
+        let value = |stacks: Vec<f64>| -> Result<Vec<f64>, String> {
+            if config.payouts.is_empty() {
+                return Ok(stacks);
+            }
+            let mut all_stacks = stacks;
+            all_stacks.extend_from_slice(&config.other_stacks);
+            let mut ret = compute_icm(&all_stacks, &config.payouts)?;
+            ret.truncate(n);
+            Ok(ret)
+        };
+
+        let posted = (0..n).map(|p| forced_bet(config, p)).collect::<Vec<_>>();
+        let pot = posted.iter().sum::<f64>();
+        let fold_stacks = |winner: usize| {
+            let mut stacks = (0..n)
+                .map(|p| config.stacks[p] - posted[p])
+                .collect::<Vec<_>>();
+            stacks[winner] += pot;
+            stacks
+        };
+
+        let walk = value(fold_stacks(n - 1))?;
+        let steal = (0..n)
+            .map(|i| value(fold_stacks(i)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut showdown = vec![vec![[Vec::new(), Vec::new()]; n]; n];
+        for i in 0..n {
+            for j in i + 1..n {
+                let live = [i, j].map(|p| config.stacks[p] - config.ante);
+                let matched = live[0].min(live[1]);
+                let dead = pot - (posted[i] - config.ante) - (posted[j] - config.ante);
+                for (winner, result) in showdown[i][j].iter_mut().enumerate() {
+                    let mut stacks = (0..n)
+                        .map(|p| config.stacks[p] - posted[p])
+                        .collect::<Vec<_>>();
+                    stacks[i] = live[0] - matched;
+                    stacks[j] = live[1] - matched;
+                    stacks[[i, j][winner]] += 2.0 * matched + dead;
+                    *result = value(stacks)?;
+                }
+            }
+        }
+
+        Ok(Self {
+            num_players: n,
+            equity,
+            pair_weights,
+            class_prob,
+            walk,
+            steal,
+            showdown,
+        })
+    }
+
+    /// Returns the probability that each player after `i` calls the all-in of `i` with `hand` and
+    /// the equity of `hand` against the calling range (indexed by `k - i - 1`).
+    fn call_stats(&self, strategy: &Strategy, i: usize, hand: usize) -> Vec<(f64, f64)> {
+        let weights = &self.pair_weights[hand * NUM_HAND_CLASSES..(hand + 1) * NUM_HAND_CLASSES];
+        let equity = &self.equity[hand * NUM_HAND_CLASSES..(hand + 1) * NUM_HAND_CLASSES];
+        let weight_sum = weights.iter().sum::<f64>();
+
+        strategy.call[i][i + 1..]
+            .iter()
+            .map(|call| {
+                let (mut call_weight, mut equity_sum) = (0.0, 0.0);
+                for ((&w, &c), &e) in weights.iter().zip(call).zip(equity) {
+                    call_weight += w * c;
+                    equity_sum += w * c * e as f64;
+                }
+                if call_weight > 0.0 {
+                    (call_weight / weight_sum, equity_sum / call_weight)
+                } else {
+                    (0.0, 0.5)
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the values of each player after `i` moves all-in, for each first player `k` who may
+    /// call (`i + 1 <= k <= n`; indexed by `k - i - 1`).
+    fn jam_values(&self, stats: &[(f64, f64)], i: usize) -> Vec<Vec<f64>> {
+        let n = self.num_players;
+        let mut ret = vec![Vec::new(); n - i];
+        ret[n - i - 1] = self.steal[i].clone();
+
+        for k in (i + 1..n).rev() {
+            let (prob_call, equity) = stats[k - i - 1];
+            let [win, lose] = &self.showdown[i][k];
+            ret[k - i - 1] = (0..n)
+                .map(|p| {
+                    let called = equity * win[p] + (1.0 - equity) * lose[p];
+                    prob_call * called + (1.0 - prob_call) * ret[k - i][p]
+                })
+                .collect();
+        }
+
+        ret
+    }
+
+    /// Returns the values of each player when everyone before `t` has folded (indexed by `t`).
+    fn first_in_values(
+        &self,
+        strategy: &Strategy,
+        jam_values: &[Vec<Vec<Vec<f64>>>],
+    ) -> Vec<Vec<f64>> {
+        let n = self.num_players;
+        let mut ret = vec![self.walk.clone(); n];
+        for t in (0..n - 1).rev() {
+            let mut values = vec![0.0; n];
+            for ((&jam, &prob), jam_value) in strategy.jam[t]
+                .iter()
+                .zip(&self.class_prob)
+                .zip(&jam_values[t])
+            {
+                for (p, value) in values.iter_mut().enumerate() {
+                    *value += prob * (jam * jam_value[0][p] + (1.0 - jam) * ret[t + 1][p]);
+                }
+            }
+            ret[t] = values;
+        }
+        ret
+    }
+
+    /// Returns the values of each player at the start of the hand.
+    fn evaluate(&self, strategy: &Strategy) -> Vec<f64> {
+        let (_, jam_values) = self.compute_jam_values(strategy);
+        self.first_in_values(strategy, &jam_values).swap_remove(0)
+    }
+
+    /// Computes `call_stats` and `jam_values` of each player and hand.
+    #[allow(clippy::type_complexity)]
+    fn compute_jam_values(
+        &self,
+        strategy: &Strategy,
+    ) -> (Vec<Vec<Vec<(f64, f64)>>>, Vec<Vec<Vec<Vec<f64>>>>) {
+        (0..self.num_players - 1)
+            .map(|i| {
+                (0..NUM_HAND_CLASSES)
+                    .map(|hand| {
+                        let stats = self.call_stats(strategy, i, hand);
+                        let values = self.jam_values(&stats, i);
+                        (stats, values)
+                    })
+                    .unzip()
+            })
+            .unzip()
+    }
+
+    /// Computes the best response of every player to `strategy`.
+    fn best_response(&self, strategy: &Strategy) -> Strategy {
+        let n = self.num_players;
+        let mut ret = Strategy {
+            jam: vec![[0.0; NUM_HAND_CLASSES]; n],
+            call: vec![vec![[0.0; NUM_HAND_CLASSES]; n]; n],
+        };
+
+        let (call_stats, jam_values) = self.compute_jam_values(strategy);
+        let fold_values = self.first_in_values(strategy, &jam_values);
+
+        for i in 0..n - 1 {
+            // moving all-in when everyone before has folded
+            for (jam, jam_value) in ret.jam[i].iter_mut().zip(&jam_values[i]) {
+                if jam_value[0][i] > fold_values[i + 1][i] {
+                    *jam = 1.0;
+                }
+            }
+
+            // calling the all-in of `i` after the players in between have folded
+            for j in i + 1..n {
+                let [win, lose] = &self.showdown[i][j];
+                let reach = (0..NUM_HAND_CLASSES)
+                    .map(|h| {
+                        let fold_prob = call_stats[i][h][..j - i - 1]
+                            .iter()
+                            .fold(1.0, |acc, &(prob_call, _)| acc * (1.0 - prob_call));
+                        strategy.jam[i][h] * fold_prob
+                    })
+                    .collect::<Vec<_>>();
+
+                for (hand, call) in ret.call[i][j].iter_mut().enumerate() {
+                    let pair_weights =
+                        &self.pair_weights[hand * NUM_HAND_CLASSES..(hand + 1) * NUM_HAND_CLASSES];
+                    let (mut call_value, mut fold_value) = (0.0, 0.0);
+                    for (h, (&pair_weight, &reach)) in pair_weights.iter().zip(&reach).enumerate() {
+                        let w = pair_weight * reach;
+                        if w == 0.0 {
+                            continue;
+                        }
+                        let equity = self.equity[h * NUM_HAND_CLASSES + hand] as f64;
+                        call_value += w * (equity * win[j] + (1.0 - equity) * lose[j]);
+                        fold_value += w * jam_values[i][h][j - i][j];
+                    }
+                    if call_value > fold_value {
+                        *call = 1.0;
+                    }
+                }
+            }
+        }
+
+        ret
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn class_weight(range: &Range, class: &str) -> f32 {
+        let range_class: Range = class.parse().unwrap();
+        let index = range_class
+            .raw_data()
+            .iter()
+            .position(|&w| w > 0.0)
+            .unwrap();
+        range.raw_data()[index]
+    }
+
+    #[test]
+    fn push_fold_heads_up() {
+        let config = PushFoldConfig {
+            stacks: vec![10.0, 10.0],
+            small_blind: 0.5,
+            big_blind: 1.0,
+            equity_method: EquityMethod::MonteCarlo {
+                num_samples: 2000,
+                seed: 0,
+            },
+            num_iterations: 300,
+            ..Default::default()
+        };
+
+        let result = solve_push_fold(&config).unwrap();
+        assert!(result.exploitability < 0.05);
+        assert!((result.ev.iter().sum::<f64>() - 20.0).abs() < 1e-9);
+
+        let jam = &result.jam_ranges[0];
+        let call = &result.call_ranges[0][1];
+        assert!(class_weight(jam, "AA") > 0.99);
+        assert!(class_weight(call, "AA") > 0.99);
+        assert!(class_weight(call, "72o") < 0.01);
+
+        // the small blind jams about 58% and the big blind calls about 37% of hands at 10bb
+        let jam_ratio = jam.num_combos(0) / 1326.0;
+        let call_ratio = call.num_combos(0) / 1326.0;
+        assert!((0.5..0.66).contains(&jam_ratio));
+        assert!((0.3..0.45).contains(&call_ratio));
+        assert!(result.jam_ranges[1].is_empty());
+
+        // every hand is a jam with a very short stack
+        let config = PushFoldConfig {
+            stacks: vec![1.2, 20.0],
+            ..config
+        };
+        let result = solve_push_fold(&config).unwrap();
+        assert!(result.jam_ranges[0].num_combos(0) > 1300.0);
+    }
+
+    #[test]
+    fn push_fold_icm() {
+        let config = PushFoldConfig {
+            stacks: vec![15.0, 15.0, 15.0],
+            small_blind: 0.5,
+            big_blind: 1.0,
+            ante: 0.1,
+            equity_method: EquityMethod::MonteCarlo {
+                num_samples: 1000,
+                seed: 0,
+            },
+            num_iterations: 100,
+            ..Default::default()
+        };
+
+        let chip_ev = solve_push_fold(&config).unwrap();
+        assert!((chip_ev.ev.iter().sum::<f64>() - 45.0).abs() < 1e-9);
+
+        // on the bubble, calling ranges are tighter than with the chip EV
+        let config = PushFoldConfig {
+            other_stacks: vec![5.0],
+            payouts: vec![50.0, 30.0, 20.0],
+            ..config
+        };
+        let icm = solve_push_fold(&config).unwrap();
+        assert!(icm.ev.iter().all(|&ev| ev > 20.0) && icm.ev.iter().sum::<f64>() < 100.0);
+
+        let call_combos = |result: &PushFoldResult| result.call_ranges[0][2].num_combos(0);
+        assert!(call_combos(&icm) < call_combos(&chip_ev));
+        assert!(icm.call_ranges[2].iter().all(|range| range.is_empty()));
+
+        let invalid_configs = [
+            PushFoldConfig {
+                stacks: vec![10.0],
+                ..config.clone()
+            },
+            PushFoldConfig {
+                stacks: vec![10.0, 1.0],
+                ..config.clone()
+            },
+            PushFoldConfig {
+                num_iterations: 0,
+                ..config
+            },
+        ];
+        for config in invalid_configs {
+            assert!(solve_push_fold(&config).is_err());
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use crate::card::*;
 use once_cell::sync::Lazy;
 use regex::Regex;
+use std::cmp::Ordering;
 use std::fmt::Write;
 use std::str::FromStr;
 
@@ -63,6 +64,23 @@ static RANGE_REGEX: Lazy<Regex> = Lazy::new(|| {
 
 static TRIM_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\s*([-:,])\s*").unwrap());
 
+/// Preflop hand classes in descending order of all-in equity against a random hand.
+const PREFLOP_RANKING: [&str; 169] = [
+    "AA", "KK", "QQ", "JJ", "TT", "99", "88", "AKs", "77", "AQs", "AJs", "AKo", "ATs", "AQo",
+    "AJo", "KQs", "66", "A9s", "ATo", "KJs", "A8s", "KTs", "KQo", "A7s", "A9o", "KJo", "55", "QJs",
+    "A8o", "A5s", "K9s", "A6s", "KTo", "QTs", "A4s", "A7o", "K8s", "A3s", "QJo", "K9o", "A5o",
+    "A6o", "Q9s", "K7s", "JTs", "A2s", "QTo", "44", "A4o", "K6s", "K8o", "A3o", "Q8s", "K5s",
+    "J9s", "Q9o", "K7o", "JTo", "A2o", "K4s", "Q7s", "K6o", "K3s", "J8s", "T9s", "33", "Q8o",
+    "Q6s", "J9o", "K5o", "K2s", "Q5s", "K4o", "T8s", "J7s", "Q4s", "Q7o", "J8o", "T9o", "K3o",
+    "Q3s", "Q6o", "98s", "T7s", "J6s", "K2o", "22", "Q5o", "Q2s", "J5s", "T8o", "J7o", "97s",
+    "Q4o", "J4s", "T6s", "J3s", "98o", "Q3o", "87s", "T7o", "J6o", "96s", "J2s", "J5o", "Q2o",
+    "T5s", "T4s", "97o", "86s", "J4o", "T6o", "95s", "T3s", "76s", "J3o", "87o", "T2s", "85s",
+    "96o", "J2o", "T5o", "94s", "75s", "T4o", "93s", "86o", "65s", "95o", "84s", "T3o", "76o",
+    "92s", "74s", "85o", "T2o", "54s", "64s", "83s", "94o", "75o", "82s", "73s", "93o", "65o",
+    "53s", "84o", "63s", "92o", "43s", "74o", "54o", "72s", "64o", "83o", "52s", "62s", "82o",
+    "42s", "73o", "53o", "63o", "32s", "43o", "72o", "52o", "62o", "42o", "32o",
+];
+
 #[inline]
 fn pair_indices(rank: u8) -> Vec<usize> {
     let mut result = Vec::with_capacity(6);
@@ -334,6 +352,34 @@ pub fn flop_from_str(s: &str) -> Result<[Card; 3], String> {
     Ok(result)
 }
 
+/// Attempts to convert an optionally space-separated string into a sorted list of unique cards.
+///
+/// # Examples
+/// ```
+/// use postflop_solver::cards_from_str;
+///
+/// assert_eq!(cards_from_str(""), Ok(vec![]));
+/// assert_eq!(cards_from_str("4h 2c3d"), Ok(vec![0, 5, 10]));
+/// assert!(cards_from_str("2c2c").is_err());
+/// ```
+#[inline]
+pub fn cards_from_str(s: &str) -> Result<Vec<Card>, String> {
+    let mut result = Vec::new();
+    let mut chars = s.chars().filter(|c| !c.is_whitespace()).peekable();
+
+    while chars.peek().is_some() {
+        result.push(card_from_chars(&mut chars)?);
+    }
+
+    result.sort_unstable();
+
+    if result.windows(2).any(|w| w[0] == w[1]) {
+        return Err("Cards must be unique".to_string());
+    }
+
+    Ok(result)
+}
+
 #[inline]
 fn parse_singleton(combo: &str) -> Result<(u8, u8, Suitedness), String> {
     if combo.len() == 4 {
@@ -391,6 +437,14 @@ fn check_card(card: Card) -> Result<(), String> {
     }
 }
 
+/// Converts a weight to `f64` by its shortest decimal representation.
+///
+/// We want to obtain 0.1 from 0.9 - 0.8, not 0.100000024.
+#[inline]
+fn decimal(weight: f32) -> f64 {
+    weight.to_string().parse().unwrap()
+}
+
 #[inline]
 fn check_weight(weight: f32) -> Result<(), String> {
     if (0.0..=1.0).contains(&weight) {
@@ -527,6 +581,241 @@ impl Range {
         Ok(result)
     }
 
+    /// Attempts to create a range from a GTO+ range string.
+    ///
+    /// GTO+ writes weighted groups as `[weight]hands[/weight]`, where `weight` is a percentage
+    /// (e.g., "AA,KK,[50.00]AKs,AsKh[/50.00]"). Hands outside the brackets have full weight.
+    pub fn from_gtoplus_str(s: &str) -> Result<Self, String> {
+        let mut groups = Vec::new();
+        let mut rest = s;
+
+        while let Some(start) = rest.find('[') {
+            groups.push((&rest[..start], None));
+
+            let tag_len = rest[start..]
+                .find(']')
+                .ok_or_else(|| format!("Unclosed weight tag: {}", &rest[start..]))?;
+            let tag = &rest[start + 1..start + tag_len];
+            let percentage = tag
+                .trim()
+                .parse::<f64>()
+                .map_err(|_| format!("Invalid weight: {tag}"))?;
+            if !(0.0..=100.0).contains(&percentage) {
+                return Err(format!("Invalid weight: {tag}"));
+            }
+
+            let body = &rest[start + tag_len + 1..];
+            let end = body
+                .find("[/")
+                .ok_or_else(|| format!("Missing closing tag: [/{tag}]"))?;
+            let end_len = body[end..]
+                .find(']')
+                .ok_or_else(|| format!("Unclosed weight tag: {}", &body[end..]))?;
+
+            groups.push((&body[..end], Some(percentage / 100.0)));
+            rest = &body[end + end_len + 1..];
+        }
+
+        groups.push((rest, None));
+
+        let mut ranges = Vec::new();
+        for (hands, weight) in groups {
+            for hand in hands
+                .split(',')
+                .map(str::trim)
+                .filter(|hand| !hand.is_empty())
+            {
+                match weight {
+                    Some(weight) => ranges.push(format!("{hand}:{weight}")),
+                    None => ranges.push(hand.to_string()),
+                }
+            }
+        }
+
+        ranges.join(",").parse()
+    }
+
+    /// Returns the range as a PioSOLVER range string without `+` or `-` notation.
+    ///
+    /// Each hand class (e.g., "AKs") is written with its weight (e.g., "AKs:0.5"), and hand
+    /// classes with different weights among suits are written per combination.
+    pub fn to_pio_string(&self) -> String {
+        let mut result = Vec::new();
+        for (hand, weight) in self.explicit_hands() {
+            if weight == 1.0 {
+                result.push(hand);
+            } else {
+                result.push(format!("{hand}:{weight}"));
+            }
+        }
+        result.join(",")
+    }
+
+    /// Returns the range as a GTO+ range string.
+    ///
+    /// Hands with full weight are written first, followed by one `[weight]hands[/weight]` group
+    /// per distinct weight.
+    pub fn to_gtoplus_string(&self) -> String {
+        let mut full = Vec::new();
+        let mut groups: Vec<(f32, Vec<String>)> = Vec::new();
+
+        for (hand, weight) in self.explicit_hands() {
+            if weight == 1.0 {
+                full.push(hand);
+            } else if let Some(group) = groups.iter_mut().find(|group| group.0 == weight) {
+                group.1.push(hand);
+            } else {
+                groups.push((weight, vec![hand]));
+            }
+        }
+
+        let mut result = full;
+        for (weight, hands) in groups {
+            let percentage = (weight as f64 * 1e6).round() / 1e4;
+            result.push(format!("[{percentage}]{}[/{percentage}]", hands.join(",")));
+        }
+        result.join(",")
+    }
+
+    /// Creates a range of the top `percent` percent of hands by a preflop hand ranking.
+    ///
+    /// Hand classes (e.g., "AKs") are added in descending order of their all-in equity against a
+    /// random hand until the selected combinations reach `percent` percent of all 1326
+    /// combinations.
+    pub fn top_percent(percent: f64) -> Result<Self, String> {
+        if !(0.0..=100.0).contains(&percent) {
+            return Err(format!("Invalid percentage: {percent}"));
+        }
+
+        let mut result = Self::new();
+        let target = 1326.0 * percent / 100.0;
+        let mut num_combos = 0.0;
+
+        for class in PREFLOP_RANKING {
+            if num_combos >= target - 1e-9 {
+                break;
+            }
+            result.update_with_singleton(class, 1.0)?;
+            num_combos += match class.len() {
+                2 => 6.0,
+                _ if class.ends_with('s') => 4.0,
+                _ => 12.0,
+            };
+        }
+
+        Ok(result)
+    }
+
+    /// Attempts to create a range from a 13x13 grid of weights.
+    ///
+    /// Rows and columns are ordered from ace to deuce. Pairs are on the diagonal, suited hands are
+    /// above it (e.g., `grid[0][1]` is "AKs"), and offsuit hands are below it (e.g., `grid[1][0]`
+    /// is "AKo").
+    pub fn from_grid(grid: &[[f32; 13]; 13]) -> Result<Self, String> {
+        let mut result = Self::new();
+        for (row, weights) in grid.iter().enumerate() {
+            for (col, &weight) in weights.iter().enumerate() {
+                check_weight(weight)?;
+                let (rank1, rank2) = (12 - row.min(col) as u8, 12 - row.max(col) as u8);
+                let indices = match row.cmp(&col) {
+                    Ordering::Equal => pair_indices(rank1),
+                    Ordering::Less => suited_indices(rank1, rank2),
+                    Ordering::Greater => offsuit_indices(rank1, rank2),
+                };
+                result.set_weight(&indices, weight);
+            }
+        }
+        Ok(result)
+    }
+
+    /// Returns the average weight of each hand class as a 13x13 grid.
+    ///
+    /// See [`from_grid`] for the layout.
+    ///
+    /// [`from_grid`]: #method.from_grid
+    pub fn to_grid(&self) -> [[f32; 13]; 13] {
+        let mut result = [[0.0; 13]; 13];
+        for (row, weights) in result.iter_mut().enumerate() {
+            for (col, weight) in weights.iter_mut().enumerate() {
+                let (rank1, rank2) = (12 - row.min(col) as u8, 12 - row.max(col) as u8);
+                *weight = match row.cmp(&col) {
+                    Ordering::Equal => self.get_weight_pair(rank1),
+                    Ordering::Less => self.get_weight_suited(rank1, rank2),
+                    Ordering::Greater => self.get_weight_offsuit(rank1, rank2),
+                };
+            }
+        }
+        result
+    }
+
+    /// Returns the weighted number of combinations that do not overlap with the dead cards.
+    ///
+    /// If there are no dead cards, pass `0` to `dead_cards_mask`.
+    pub fn num_combos(&self, dead_cards_mask: u64) -> f64 {
+        let (_, weights) = self.get_hands_weights(dead_cards_mask);
+        weights.iter().map(|&weight| weight as f64).sum()
+    }
+
+    /// Removes the hands containing cards that are not in `deck`.
+    #[inline]
+    pub fn restrict_to_deck(&mut self, deck: Deck) {
+        let removed_mask = deck.removed_mask();
+        for card1 in 0..52 {
+            for card2 in card1 + 1..52 {
+                let hand_mask: u64 = (1 << card1) | (1 << card2);
+                if hand_mask & removed_mask != 0 {
+                    self.set_weight_by_cards(card1, card2, 0.0);
+                }
+            }
+        }
+    }
+
+    /// Updates each weight to the maximum of the weights of this range and `other`.
+    #[inline]
+    pub fn union(&mut self, other: &Self) {
+        self.combine(other, f64::max);
+    }
+
+    /// Updates each weight to the minimum of the weights of this range and `other`.
+    #[inline]
+    pub fn intersect(&mut self, other: &Self) {
+        self.combine(other, f64::min);
+    }
+
+    /// Subtracts the weights of `other` from this range. Negative weights become `0.0`.
+    #[inline]
+    pub fn subtract(&mut self, other: &Self) {
+        self.combine(other, |weight, other| (weight - other).max(0.0));
+    }
+
+    /// Multiplies each weight by `factor`. Weights above `1.0` become `1.0`.
+    #[inline]
+    pub fn scale(&mut self, factor: f32) -> Result<(), String> {
+        if !(factor >= 0.0 && factor.is_finite()) {
+            return Err(format!("Invalid factor: {factor}"));
+        }
+        let factor = decimal(factor);
+        self.data
+            .iter_mut()
+            .for_each(|el| *el = (decimal(*el) * factor).min(1.0) as f32);
+        Ok(())
+    }
+
+    /// Clamps each nonzero weight to the range `[min, max]`. Hands not in the range are kept out.
+    #[inline]
+    pub fn clamp_weights(&mut self, min: f32, max: f32) -> Result<(), String> {
+        check_weight(min)?;
+        check_weight(max)?;
+        if min > max {
+            return Err(format!("Invalid bounds: min = {min}, max = {max}"));
+        }
+        self.data
+            .iter_mut()
+            .filter(|el| **el > 0.0)
+            .for_each(|el| *el = el.clamp(min, max));
+        Ok(())
+    }
+
     /// Clears the range.
     #[inline]
     pub fn clear(&mut self) {
@@ -542,10 +831,9 @@ impl Range {
     /// Inverts the range.
     #[inline]
     pub fn invert(&mut self) {
-        // we want to obtain 0.1 when the previous value was 0.9, not 0.100000024
         self.data
             .iter_mut()
-            .for_each(|el| *el = (1.0 - el.to_string().parse::<f64>().unwrap()) as f32);
+            .for_each(|el| *el = (1.0 - decimal(*el)) as f32);
     }
 
     /// Obtains the weight of a specified hand.
@@ -683,6 +971,14 @@ impl Range {
         true
     }
 
+    #[inline]
+    fn combine(&mut self, other: &Self, f: impl Fn(f64, f64) -> f64) {
+        self.data
+            .iter_mut()
+            .zip(other.data.iter())
+            .for_each(|(el, &other)| *el = f(decimal(*el), decimal(other)) as f32);
+    }
+
     #[inline]
     fn is_same_weight(&self, indices: &[usize]) -> bool {
         let weight = self.data[indices[0]];
@@ -877,6 +1173,52 @@ impl Range {
         }
     }
 
+    /// Returns the hand classes and combinations with nonzero weights in descending order.
+    fn explicit_hands(&self) -> Vec<(String, f32)> {
+        let mut result = Vec::new();
+
+        let mut push_hands = |indices: &[usize], class: String| {
+            if self.is_same_weight(indices) {
+                let weight = self.data[indices[0]];
+                if weight > 0.0 {
+                    result.push((class, weight));
+                }
+            } else {
+                for &index in indices.iter().rev() {
+                    let weight = self.data[index];
+                    if weight > 0.0 {
+                        let hand = hole_to_string(index_to_card_pair(index)).unwrap();
+                        result.push((hand, weight));
+                    }
+                }
+            }
+        };
+
+        for rank in (0..13).rev() {
+            let rank_char = rank_to_char(rank).unwrap();
+            push_hands(&pair_indices(rank), format!("{rank_char}{rank_char}"));
+        }
+
+        for rank1 in (0..13).rev() {
+            for rank2 in (0..rank1).rev() {
+                let class = format!(
+                    "{}{}",
+                    rank_to_char(rank1).unwrap(),
+                    rank_to_char(rank2).unwrap()
+                );
+                let indices = nonpair_indices(rank1, rank2);
+                if self.is_same_weight(&indices) {
+                    push_hands(&indices, class);
+                } else {
+                    push_hands(&suited_indices(rank1, rank2), format!("{class}s"));
+                    push_hands(&offsuit_indices(rank1, rank2), format!("{class}o"));
+                }
+            }
+        }
+
+        result
+    }
+
     fn suit_specified_strings(&self, result: &mut Vec<String>) {
         // pairs
         for rank in (0..13).rev() {
@@ -1142,6 +1484,112 @@ mod tests {
         assert_eq!(data.get_weight_offsuit(6, 3), 0.0);
     }
 
+    #[test]
+    fn range_pio_gtoplus() {
+        let range = "QQ+,AK,AhQh,AQs:0.5,JJ:0.25,KcQd".parse::<Range>().unwrap();
+        assert_eq!(
+            range.to_pio_string(),
+            "AA,KK,QQ,JJ:0.25,AK,AsQs:0.5,AhQh,AdQd:0.5,AcQc:0.5,KcQd"
+        );
+        assert_eq!(range.to_pio_string().parse::<Range>(), Ok(range));
+        assert_eq!(
+            range.to_gtoplus_string(),
+            "AA,KK,QQ,AK,AhQh,KcQd,[25]JJ[/25],[50]AsQs,AdQd,AcQc[/50]"
+        );
+        assert_eq!(
+            Range::from_gtoplus_str(&range.to_gtoplus_string()),
+            Ok(range)
+        );
+
+        let gtoplus = Range::from_gtoplus_str("TT+, [37.50]AKs, AsKh[/37.50], 99:0.5");
+        let expected = "TT+,AKs:0.375,AsKh:0.375,99:0.5".parse::<Range>();
+        assert_eq!(gtoplus, expected);
+
+        assert!(Range::from_gtoplus_str("[50]AKs").is_err());
+        assert!(Range::from_gtoplus_str("[150]AKs[/150]").is_err());
+        assert!(Range::from_gtoplus_str("[x]AKs[/x]").is_err());
+    }
+
+    #[test]
+    fn range_arithmetic() {
+        let a = "AA,KK:0.5,AKs:0.9".parse::<Range>().unwrap();
+        let b = "KK,AKs:0.8,QQ:0.3".parse::<Range>().unwrap();
+
+        let mut union = a;
+        union.union(&b);
+        assert_eq!(union.to_string(), "KK+,QQ:0.3,AKs:0.9");
+
+        let mut intersection = a;
+        intersection.intersect(&b);
+        assert_eq!(intersection.to_string(), "KK:0.5,AKs:0.8");
+
+        let mut difference = a;
+        difference.subtract(&b);
+        assert_eq!(difference.to_string(), "AA,AKs:0.1");
+
+        let mut scaled = "AA:0.5,KK".parse::<Range>().unwrap();
+        scaled.scale(1.5).unwrap();
+        assert_eq!(scaled.to_string(), "AA:0.75,KK");
+        assert!(scaled.scale(-1.0).is_err());
+
+        let mut clamped = "AA,KK:0.1,QQ:0.3".parse::<Range>().unwrap();
+        clamped.clamp_weights(0.25, 0.5).unwrap();
+        assert_eq!(clamped.to_string(), "AA:0.5,KK:0.25,QQ:0.3");
+        assert!(clamped.clamp_weights(0.5, 0.25).is_err());
+    }
+
+    #[test]
+    fn range_top_percent() {
+        assert!(Range::top_percent(0.0).unwrap().is_empty());
+        assert_eq!(Range::top_percent(100.0).unwrap(), Range::ones());
+        assert_eq!(Range::top_percent(0.45).unwrap().to_string(), "AA");
+        assert!(Range::top_percent(100.1).is_err());
+
+        let mut prev = 0.0;
+        for percent in [5.0, 10.0, 25.0, 50.0] {
+            let num_combos = Range::top_percent(percent).unwrap().num_combos(0);
+            assert!(num_combos >= 13.26 * percent && num_combos < 13.26 * percent + 12.0);
+            assert!(num_combos > prev);
+            prev = num_combos;
+        }
+
+        let mut classes = PREFLOP_RANKING.to_vec();
+        classes.sort_unstable();
+        classes.dedup();
+        assert_eq!(classes.len(), 169);
+    }
+
+    #[test]
+    fn range_grid_and_combos() {
+        let range = "AKs,AQo:0.5,TT".parse::<Range>().unwrap();
+        let grid = range.to_grid();
+        assert_eq!(grid[0][1], 1.0);
+        assert_eq!(grid[1][0], 0.0);
+        assert_eq!(grid[2][0], 0.5);
+        assert_eq!(grid[4][4], 1.0);
+        assert_eq!(Range::from_grid(&grid), Ok(range));
+
+        let mut grid = grid;
+        grid[12][12] = 1.5;
+        assert!(Range::from_grid(&grid).is_err());
+
+        let range = "AA,KK".parse::<Range>().unwrap();
+        let dead_cards_mask = 1 << card_from_str("As").unwrap();
+        assert_eq!(range.num_combos(0), 12.0);
+        assert_eq!(range.num_combos(dead_cards_mask), 9.0);
+    }
+
+    #[test]
+    fn range_short_deck() {
+        let mut range = "66-22,A5s-A2s,AKo".parse::<Range>().unwrap();
+        let removed_mask = Deck::ShortDeck.removed_mask();
+        assert_eq!(range.num_combos(removed_mask), 6.0 + 12.0);
+
+        range.restrict_to_deck(Deck::ShortDeck);
+        assert_eq!(range.to_string(), "66,AKo");
+        assert_eq!(range.num_combos(0), 18.0);
+    }
+
     #[test]
     fn range_to_string() {
         let tests = [
@@ -0,0 +1,456 @@
+use crate::action_tree::*;
+use crate::card::*;
+use crate::flops::*;
+use crate::game::*;
+use crate::interface::*;
+use crate::range::*;
+use crate::solver::*;
+use std::fmt::Write;
+
+/// A configuration for running the same flop spot over many boards.
+#[derive(Debug, Clone)]
+pub struct ReportConfig {
+    /// Tree configuration shared by all flops. `initial_state` must be `BoardState::Flop`.
+    pub tree_config: TreeConfig,
+
+    /// Ranges of OOP and IP.
+    pub ranges: [Range; 2],
+
+    /// Deck of each game.
+    pub deck: Deck,
+
+    /// Maximum number of iterations for each flop.
+    pub max_num_iterations: u32,
+
+    /// Target exploitability for each flop.
+    pub target_exploitability: f32,
+
+    /// Whether to compress the storage of each game.
+    pub enable_compression: bool,
+}
+
+/// Root results of one solved flop.
+#[derive(Debug, Clone, Default)]
+pub struct FlopReport {
+    /// The flop.
+    pub flop: [Card; 3],
+
+    /// The number of flops this flop represents in the average.
+    pub weight: f64,
+
+    /// The number of performed iterations.
+    pub num_iterations: u32,
+
+    /// The exploitability of the solution.
+    pub exploitability: f32,
+
+    /// The overall frequency of each root action of OOP.
+    pub frequencies: Vec<f32>,
+
+    /// The average equity of OOP and IP.
+    pub equity: [f32; 2],
+
+    /// The average expected value of OOP and IP.
+    pub ev: [f32; 2],
+}
+
+/// Root results of many flops that share the same action tree.
+#[derive(Debug, Clone, Default)]
+pub struct AggregateReport {
+    /// Root actions of OOP, in the order of [`FlopReport::frequencies`].
+    pub actions: Vec<Action>,
+
+    /// Results of each solved flop.
+    pub rows: Vec<FlopReport>,
+
+    /// Flops that failed to solve and their error messages.
+    pub errors: Vec<([Card; 3], String)>,
+}
+
+/// Solves a single flop and collects its root results.
+pub fn solve_flop_report(config: &ReportConfig, flop: [Card; 3]) -> Result<FlopReport, String> {
+    if config.tree_config.initial_state != BoardState::Flop {
+        return Err("Initial state of the tree must be flop".to_string());
+    }
+
+    let card_config = CardConfig {
+        range: config.ranges,
+        flop,
+        turn: NOT_DEALT,
+        river: NOT_DEALT,
+        deck: config.deck,
+    };
+
+    let action_tree = ActionTree::new(config.tree_config.clone())?;
+    let mut game = PostFlopGame::with_config(card_config, action_tree)?;
+    game.allocate_memory(config.enable_compression);
+
+    let exploitability = solve(
+        &mut game,
+        config.max_num_iterations,
+        config.target_exploitability,
+        false,
+    );
+
+    game.cache_normalized_weights();
+
+    let num_actions = game.available_actions().len();
+    let strategy = game.strategy();
+
+    let mut frequencies = vec![0.0; num_actions];
+    let mut equity = [0.0; 2];
+    let mut ev = [0.0; 2];
+
+    for player in 0..2 {
+        let weights = game.normalized_weights(player);
+        let total = weights.iter().map(|&w| w as f64).sum::<f64>();
+        let average = |values: &[f32]| {
+            let sum = values
+                .iter()
+                .zip(weights)
+                .map(|(&v, &w)| v as f64 * w as f64)
+                .sum::<f64>();
+            (sum / total) as f32
+        };
+
+        equity[player] = average(&game.equity(player));
+        ev[player] = average(&game.expected_values(player));
+
+        if player == game.current_player() {
+            let num_hands = weights.len();
+            for (action, frequency) in frequencies.iter_mut().enumerate() {
+                *frequency = average(&strategy[action * num_hands..(action + 1) * num_hands]);
+            }
+        }
+    }
+
+    Ok(FlopReport {
+        flop,
+        weight: 1.0,
+        num_iterations: game.num_iterations(),
+        exploitability,
+        frequencies,
+        equity,
+        ev,
+    })
+}
+
+/// Solves each flop in turn and collects the root results into one report.
+///
+/// Each row takes the weight of its flop; see [`weighted_canonical_flops`] and [`flop_subset`].
+/// A flop that fails to solve is recorded in [`AggregateReport::errors`] and the run continues.
+/// `on_flop` is called with the index of each finished flop and its results or error; returning
+/// `false` stops the run and returns the report collected so far.
+///
+/// # Examples
+/// ```ignore
+/// let flops = flop_subset(95, config.deck)?;
+/// let report = run_aggregate_report(&config, &flops, |i, _| {
+///     println!("{}/{}", i + 1, flops.len());
+///     true
+/// })?;
+/// std::fs::write("report.csv", report.to_csv()?)?;
+/// ```
+pub fn run_aggregate_report(
+    config: &ReportConfig,
+    flops: &[WeightedFlop],
+    mut on_flop: impl FnMut(usize, Result<&FlopReport, &str>) -> bool,
+) -> Result<AggregateReport, String> {
+    if config.tree_config.initial_state != BoardState::Flop {
+        return Err("Initial state of the tree must be flop".to_string());
+    }
+
+    let action_tree = ActionTree::new(config.tree_config.clone())?;
+    let mut report = AggregateReport {
+        actions: action_tree.available_actions().to_vec(),
+        rows: Vec::with_capacity(flops.len()),
+        errors: Vec::new(),
+    };
+
+    for (index, weighted) in flops.iter().enumerate() {
+        let proceed = match solve_flop_report(config, weighted.flop) {
+            Ok(mut row) => {
+                row.weight = weighted.weight;
+                let proceed = on_flop(index, Ok(&row));
+                report.rows.push(row);
+                proceed
+            }
+            Err(error) => {
+                let proceed = on_flop(index, Err(&error));
+                report.errors.push((weighted.flop, error));
+                proceed
+            }
+        };
+        if !proceed {
+            break;
+        }
+    }
+
+    Ok(report)
+}
+
+/// Returns a short label of an action (e.g., `"Bet 50"`).
+pub fn action_label(action: Action) -> String {
+    match action {
+        Action::None => "None".to_string(),
+        Action::Fold => "Fold".to_string(),
+        Action::Check => "Check".to_string(),
+        Action::Call => "Call".to_string(),
+        Action::Bet(amount) => format!("Bet {amount}"),
+        Action::Raise(amount) => format!("Raise {amount}"),
+        Action::AllIn(amount) => format!("AllIn {amount}"),
+        Action::Chance(card) => card_to_string(card).unwrap_or_default(),
+    }
+}
+
+impl AggregateReport {
+    /// Returns the average of each column over all flops, weighted by the flop weights.
+    ///
+    /// The `flop` field of the result is `[NOT_DEALT; 3]` and `weight` is the total weight.
+    pub fn average(&self) -> Option<FlopReport> {
+        let total = self.rows.iter().map(|row| row.weight).sum::<f64>();
+        if self.rows.is_empty() || total <= 0.0 {
+            return None;
+        }
+
+        let mut ret = FlopReport {
+            flop: [NOT_DEALT; 3],
+            weight: total,
+            frequencies: vec![0.0; self.actions.len()],
+            ..Default::default()
+        };
+
+        for row in &self.rows {
+            let w = (row.weight / total) as f32;
+            ret.num_iterations = ret.num_iterations.max(row.num_iterations);
+            ret.exploitability += row.exploitability * w;
+            for (dst, &src) in ret.frequencies.iter_mut().zip(&row.frequencies) {
+                *dst += src * w;
+            }
+            for player in 0..2 {
+                ret.equity[player] += row.equity[player] * w;
+                ret.ev[player] += row.ev[player] * w;
+            }
+        }
+
+        Some(ret)
+    }
+
+    /// Exports the report as CSV with one row per flop.
+    pub fn to_csv(&self) -> Result<String, String> {
+        let mut ret = "Flop,Weight,Iterations,Exploitability".to_string();
+        for &action in &self.actions {
+            ret.push(',');
+            ret.push_str(&action_label(action));
+        }
+        ret.push_str(",OOP Equity,IP Equity,OOP EV,IP EV\n");
+
+        for row in &self.rows {
+            let flop = flop_to_string(row.flop)?;
+            write!(
+                ret,
+                "{},{},{},{}",
+                flop, row.weight, row.num_iterations, row.exploitability
+            )
+            .unwrap();
+            for frequency in &row.frequencies {
+                write!(ret, ",{frequency}").unwrap();
+            }
+            writeln!(
+                ret,
+                ",{},{},{},{}",
+                row.equity[0], row.equity[1], row.ev[0], row.ev[1]
+            )
+            .unwrap();
+        }
+
+        Ok(ret)
+    }
+
+    /// Exports the report as a JSON object with `actions`, `rows`, and `errors` fields.
+    pub fn to_json(&self) -> Result<String, String> {
+        let actions = self
+            .actions
+            .iter()
+            .map(|&action| format!("\"{}\"", action_label(action)))
+            .collect::<Vec<_>>();
+
+        let rows = self
+            .rows
+            .iter()
+            .map(|row| {
+                let flop = flop_to_string(row.flop)?;
+                let frequencies = row
+                    .frequencies
+                    .iter()
+                    .map(|frequency| json_number(*frequency))
+                    .collect::<Vec<_>>();
+                Ok(format!(
+                    "{{\"flop\":\"{}\",\"weight\":{},\"iterations\":{},\"exploitability\":{},\"frequencies\":[{}],\"equity\":[{},{}],\"ev\":[{},{}]}}",
+                    flop,
+                    row.weight,
+                    row.num_iterations,
+                    json_number(row.exploitability),
+                    frequencies.join(","),
+                    json_number(row.equity[0]),
+                    json_number(row.equity[1]),
+                    json_number(row.ev[0]),
+                    json_number(row.ev[1]),
+                ))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        let errors = self
+            .errors
+            .iter()
+            .map(|(flop, error)| {
+                Ok(format!(
+                    "{{\"flop\":\"{}\",\"error\":{}}}",
+                    flop_to_string(*flop)?,
+                    json_string(error)
+                ))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        Ok(format!(
+            "{{\"actions\":[{}],\"rows\":[{}],\"errors\":[{}]}}",
+            actions.join(","),
+            rows.join(","),
+            errors.join(",")
+        ))
+    }
+}
+
+/// Formats a number for JSON, which does not allow `NaN` or infinities.
+#[inline]
+pub(crate) fn json_number(value: f32) -> String {
+    if value.is_finite() {
+        value.to_string()
+    } else {
+        "null".to_string()
+    }
+}
+
+/// Formats a string as a JSON string literal.
+fn json_string(value: &str) -> String {
+    let mut ret = String::with_capacity(value.len() + 2);
+    ret.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => ret.push_str("\\\""),
+            '\\' => ret.push_str("\\\\"),
+            c if (c as u32) < 0x20 => write!(ret, "\\u{:04x}", c as u32).unwrap(),
+            c => ret.push(c),
+        }
+    }
+    ret.push('"');
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bet_size::*;
+
+    #[test]
+    fn aggregate_report() {
+        let bet_sizes = BetSizeOptions::try_from(("50%", "")).unwrap();
+        let config = ReportConfig {
+            tree_config: TreeConfig {
+                initial_state: BoardState::Flop,
+                starting_pot: 100,
+                effective_stack: 100,
+                flop_bet_sizes: [bet_sizes.clone(), bet_sizes.clone()],
+                turn_bet_sizes: [bet_sizes.clone(), bet_sizes.clone()],
+                river_bet_sizes: [bet_sizes.clone(), bet_sizes],
+                ..Default::default()
+            },
+            ranges: ["AA,KK,QQ".parse().unwrap(), "JJ,TT,AK".parse().unwrap()],
+            deck: Deck::Standard,
+            max_num_iterations: 5,
+            target_exploitability: 0.0,
+            enable_compression: false,
+        };
+
+        let flops = [("2c3d4h", 3.0), ("AsKsQs", 1.0), ("Td9d6h", 2.0)].map(|(flop, weight)| {
+            WeightedFlop {
+                flop: flop_from_str(flop).unwrap(),
+                weight,
+            }
+        });
+
+        let mut visited = Vec::new();
+        let report = run_aggregate_report(&config, &flops, |index, _| {
+            visited.push(index);
+            index < 1
+        })
+        .unwrap();
+
+        assert_eq!(visited, vec![0, 1]);
+        assert_eq!(report.rows.len(), 2);
+        assert_eq!(report.actions, vec![Action::Check, Action::Bet(50)]);
+
+        for row in &report.rows {
+            assert_eq!(row.num_iterations, 5);
+            let sum = row.frequencies.iter().sum::<f32>();
+            assert!((sum - 1.0).abs() < 1e-4);
+            assert!((row.equity[0] + row.equity[1] - 1.0).abs() < 1e-4);
+            assert!((row.ev[0] + row.ev[1] - 100.0).abs() < 1e-2);
+        }
+
+        let csv = report.to_csv().unwrap();
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next(),
+            Some("Flop,Weight,Iterations,Exploitability,Check,Bet 50,OOP Equity,IP Equity,OOP EV,IP EV")
+        );
+        assert!(lines.next().unwrap().starts_with("4h3d2c,3,5,"));
+        assert_eq!(lines.count(), 1);
+
+        let json = report.to_json().unwrap();
+        assert!(json.starts_with(
+            "{\"actions\":[\"Check\",\"Bet 50\"],\"rows\":[{\"flop\":\"4h3d2c\",\"weight\":3,"
+        ));
+
+        let average = report.average().unwrap();
+        let expected = (report.rows[0].equity[0] * 3.0 + report.rows[1].equity[0]) / 4.0;
+        assert_eq!(average.weight, 4.0);
+        assert!((average.equity[0] - expected).abs() < 1e-6);
+
+        // a flop that fails to solve is recorded and the run continues
+        let config = ReportConfig {
+            ranges: ["AA".parse().unwrap(), "JJ".parse().unwrap()],
+            ..config
+        };
+        let flops = [
+            flops[2],
+            WeightedFlop {
+                flop: flop_from_str("JcJdJh").unwrap(),
+                weight: 1.0,
+            },
+        ];
+        let mut num_errors = 0;
+        let report = run_aggregate_report(&config, &flops, |_, result| {
+            num_errors += result.is_err() as usize;
+            true
+        })
+        .unwrap();
+
+        assert_eq!(num_errors, 1);
+        assert_eq!(report.rows.len(), 1);
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!(report.errors[0].0, flops[1].flop);
+        assert!(report
+            .to_json()
+            .unwrap()
+            .contains("\"errors\":[{\"flop\":\"JhJdJc\",\"error\":\""));
+
+        // flops are solved with the given deck
+        let config = ReportConfig {
+            deck: Deck::ShortDeck,
+            ..config
+        };
+        assert!(solve_flop_report(&config, flops[0].flop).is_ok());
+        assert!(solve_flop_report(&config, flop_from_str("2c3d4h").unwrap()).is_err());
+    }
+}
@@ -4,34 +4,283 @@ use crate::sliceop::*;
 use crate::utility::*;
 use std::io::{self, Write};
 use std::mem::MaybeUninit;
+use std::time::{Duration, Instant};
 
 #[cfg(feature = "custom-alloc")]
 use crate::alloc::*;
 
+/// A variant of the counterfactual regret minimization (CFR) algorithm.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum CfrAlgorithm {
+    /// Discounted CFR with (α, β, γ) = (1.5, 0, 3), where the cumulative strategy is reset at
+    /// iterations of powers of 4.
+    #[default]
+    Discounted,
+
+    /// Discounted CFR with the given parameters and without strategy resets.
+    ///
+    /// Positive and negative cumulative regrets are multiplied by `t^alpha / (t^alpha + 1)` and
+    /// `t^beta / (t^beta + 1)`, and the cumulative strategy by `(t / (t + 1))^gamma`.
+    /// `gamma` must be non-negative.
+    DiscountedWith { alpha: f32, beta: f32, gamma: f32 },
+
+    /// Linear CFR, which is equivalent to Discounted CFR with (α, β, γ) = (1, 1, 1).
+    Linear,
+
+    /// CFR+ with linear averaging of the strategy.
+    CfrPlus,
+
+    /// Predictive CFR+ with quadratic averaging of the strategy.
+    ///
+    /// The last instantaneous regrets are used as the prediction of the next ones, which requires
+    /// additional memory of [`PostFlopGame::memory_usage_predictions`] bytes.
+    ///
+    /// [`PostFlopGame::memory_usage_predictions`]: crate::PostFlopGame::memory_usage_predictions
+    PredictiveCfrPlus,
+}
+
+impl CfrAlgorithm {
+    /// Returns whether the algorithm requires the buffer of previous instantaneous regrets.
+    #[inline]
+    pub fn is_predictive(&self) -> bool {
+        *self == CfrAlgorithm::PredictiveCfrPlus
+    }
+}
+
+/// Stop conditions and options of [`continue_solve_with_options`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SolveOptions {
+    /// The CFR variant.
+    pub algorithm: CfrAlgorithm,
+
+    /// The total number of iterations including the ones already performed.
+    pub max_num_iterations: u32,
+
+    /// The solver stops once the exploitability is at most this value.
+    pub target_exploitability: f32,
+
+    /// The solver stops once this wall-clock time has elapsed; checked after every iteration.
+    pub time_limit: Option<Duration>,
+
+    /// The number of iterations between exploitability checks.
+    pub check_interval: u32,
+
+    /// Whether to compute the expected values of both players at each check.
+    pub compute_ev: bool,
+}
+
+impl Default for SolveOptions {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            algorithm: CfrAlgorithm::default(),
+            max_num_iterations: 1000,
+            target_exploitability: 0.0,
+            time_limit: None,
+            check_interval: 10,
+            compute_ev: true,
+        }
+    }
+}
+
+/// A point of the convergence history.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConvergencePoint {
+    /// The number of completed iterations.
+    pub iteration: u32,
+
+    /// The wall-clock time since the solver was started.
+    pub elapsed: Duration,
+
+    /// The exploitability of the current average strategy.
+    pub exploitability: f32,
+
+    /// The expected values of OOP and IP as returned by [`compute_current_ev`], if computed.
+    pub ev: Option<[f32; 2]>,
+}
+
+/// The reason why the solver stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopReason {
+    MaxIterations,
+    TargetExploitability,
+    TimeLimit,
+    /// The progress callback requested to stop.
+    Interrupted,
+}
+
+/// The result of [`continue_solve_with_options`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SolveSummary {
+    /// The total number of completed iterations.
+    pub num_iterations: u32,
+
+    /// The exploitability of the obtained strategy.
+    pub exploitability: f32,
+
+    /// The reason why the solver stopped.
+    pub stop_reason: StopReason,
+
+    /// The convergence history, starting with the state before solving.
+    pub history: Vec<ConvergencePoint>,
+}
+
 struct DiscountParams {
     alpha_t: f32,
     beta_t: f32,
     gamma_t: f32,
+    clamp_regrets: bool,
+    predictive: bool,
 }
 
 impl DiscountParams {
-    pub fn new(current_iteration: u32) -> Self {
-        // 0, 1, 4, 16, 64, 256, ...
-        let nearest_lower_power_of_4 = match current_iteration {
-            0 => 0,
-            x => 1 << ((x.leading_zeros() ^ 31) & !1),
+    pub fn new(algorithm: CfrAlgorithm, current_iteration: u32) -> Self {
+        let t = current_iteration as f64;
+
+        match algorithm {
+            CfrAlgorithm::Discounted => {
+                // 0, 1, 4, 16, 64, 256, ...
+                let nearest_lower_power_of_4 = match current_iteration {
+                    0 => 0,
+                    x => 1 << ((x.leading_zeros() ^ 31) & !1),
+                };
+
+                let t_alpha = (current_iteration as i32 - 1).max(0) as f64;
+                let t_gamma = (current_iteration - nearest_lower_power_of_4) as f64;
+
+                let pow_alpha = t_alpha * t_alpha.sqrt();
+                let pow_gamma = (t_gamma / (t_gamma + 1.0)).powi(3);
+
+                Self {
+                    alpha_t: (pow_alpha / (pow_alpha + 1.0)) as f32,
+                    beta_t: 0.5,
+                    gamma_t: pow_gamma as f32,
+                    clamp_regrets: false,
+                    predictive: false,
+                }
+            }
+
+            CfrAlgorithm::DiscountedWith { alpha, beta, gamma } => {
+                Self::discounted(current_iteration, alpha, beta, gamma)
+            }
+
+            CfrAlgorithm::Linear => Self::discounted(current_iteration, 1.0, 1.0, 1.0),
+
+            CfrAlgorithm::CfrPlus => Self {
+                alpha_t: 1.0,
+                beta_t: 0.0,
+                gamma_t: (t / (t + 1.0)) as f32,
+                clamp_regrets: true,
+                predictive: false,
+            },
+
+            CfrAlgorithm::PredictiveCfrPlus => Self {
+                alpha_t: 1.0,
+                beta_t: 0.0,
+                gamma_t: (t / (t + 1.0)).powi(2) as f32,
+                clamp_regrets: true,
+                predictive: true,
+            },
+        }
+    }
+
+    fn discounted(current_iteration: u32, alpha: f32, beta: f32, gamma: f32) -> Self {
+        let t_regret = (current_iteration as i32 - 1).max(0) as f64;
+        let t_strategy = current_iteration as f64;
+
+        // `t^x / (t^x + 1)`, which is 1 when `t^x` overflows (e.g., `t = 0` and `x < 0`)
+        let discount = |x: f32| {
+            let pow = t_regret.powf(x as f64);
+            if pow.is_finite() {
+                (pow / (pow + 1.0)) as f32
+            } else {
+                1.0
+            }
         };
 
-        let t_alpha = (current_iteration as i32 - 1).max(0) as f64;
-        let t_gamma = (current_iteration - nearest_lower_power_of_4) as f64;
+        Self {
+            alpha_t: discount(alpha),
+            beta_t: discount(beta),
+            gamma_t: (t_strategy / (t_strategy + 1.0)).powf(gamma as f64) as f32,
+            clamp_regrets: false,
+            predictive: false,
+        }
+    }
+}
 
-        let pow_alpha = t_alpha * t_alpha.sqrt();
-        let pow_gamma = (t_gamma / (t_gamma + 1.0)).powi(3);
+/// A re-solving gadget for safe subgame re-solving (Burch et al., 2014).
+///
+/// At the root of the subgame, each private hand of the gadget player chooses between
+/// terminating with its blueprint counterfactual value and following into the subgame. The choice
+/// is updated by CFR+ alongside the subgame, so the re-solved strategy of the opponent cannot be
+/// exploited by the gadget player more than the blueprint strategy.
+#[derive(Debug)]
+pub struct ResolveGadget {
+    player: usize,
+    values: Vec<f32>,
+    regrets: MutexLike<Vec<f32>>,
+}
 
+impl ResolveGadget {
+    /// Creates a new gadget for `player` with the given terminate values.
+    ///
+    /// `values` must be in the same scale as the counterfactual values of the subgame root.
+    #[inline]
+    pub fn new(player: usize, values: Vec<f32>) -> Self {
+        let regrets = MutexLike::new(vec![0.0; 2 * values.len()]);
         Self {
-            alpha_t: (pow_alpha / (pow_alpha + 1.0)) as f32,
-            beta_t: 0.5,
-            gamma_t: pow_gamma as f32,
+            player,
+            values,
+            regrets,
+        }
+    }
+
+    /// Returns the player who can terminate the subgame.
+    #[inline]
+    pub fn player(&self) -> usize {
+        self.player
+    }
+
+    /// Returns the terminate values of each private hand.
+    #[inline]
+    pub fn values(&self) -> &[f32] {
+        &self.values
+    }
+
+    /// Returns the current probability of following into the subgame of each private hand.
+    pub fn follow_probability(&self) -> Vec<f32> {
+        let regrets = self.regrets.lock();
+        let (terminate, follow) = regrets.split_at(self.values.len());
+        terminate
+            .iter()
+            .zip(follow)
+            .map(|(&t, &f)| if t + f > 0.0 { f / (t + f) } else { 0.5 })
+            .collect()
+    }
+
+    /// Clears the cumulative regrets.
+    #[inline]
+    pub fn reset(&self) {
+        self.regrets.lock().fill(0.0);
+    }
+
+    /// Returns the reach probabilities of the gadget player at the subgame root.
+    fn follow_reach(&self, weights: &[f32]) -> Vec<f32> {
+        let mut ret = self.follow_probability();
+        mul_slice(&mut ret, weights);
+        ret
+    }
+
+    /// Updates the cumulative regrets with the counterfactual values of following.
+    fn update(&self, cfvalues: &[f32]) {
+        let follow = self.follow_probability();
+        let mut regrets = self.regrets.lock();
+        let (terminate_regrets, follow_regrets) = regrets.split_at_mut(self.values.len());
+        for i in 0..self.values.len() {
+            let (terminate, follow_value) = (self.values[i], cfvalues[i]);
+            let value = terminate + (follow_value - terminate) * follow[i];
+            terminate_regrets[i] = (terminate_regrets[i] + terminate - value).max(0.0);
+            follow_regrets[i] = (follow_regrets[i] + follow_value - value).max(0.0);
         }
     }
 }
@@ -39,6 +288,10 @@ impl DiscountParams {
 /// Performs Discounted CFR algorithm until the given number of iterations or exploitability is
 /// satisfied.
 ///
+/// The iteration count starts from [`Game::num_iterations`], so a game loaded from an unfinalized
+/// checkpoint is resumed rather than restarted. `max_num_iterations` is the total number of
+/// iterations including the ones already performed.
+///
 /// This method returns the exploitability of the obtained strategy.
 pub fn solve<T: Game>(
     game: &mut T,
@@ -46,6 +299,134 @@ pub fn solve<T: Game>(
     target_exploitability: f32,
     print_progress: bool,
 ) -> f32 {
+    solve_with(
+        game,
+        CfrAlgorithm::Discounted,
+        max_num_iterations,
+        target_exploitability,
+        print_progress,
+    )
+}
+
+/// Performs the given CFR variant until the given number of iterations or exploitability is
+/// satisfied.
+///
+/// See [`solve`] for details. This method returns the exploitability of the obtained strategy.
+pub fn solve_with<T: Game>(
+    game: &mut T,
+    algorithm: CfrAlgorithm,
+    max_num_iterations: u32,
+    target_exploitability: f32,
+    print_progress: bool,
+) -> f32 {
+    let exploitability = continue_solve_with(
+        game,
+        algorithm,
+        max_num_iterations,
+        target_exploitability,
+        print_progress,
+    );
+
+    finalize(game);
+
+    exploitability
+}
+
+/// Resumes Discounted CFR algorithm from [`Game::num_iterations`] until the total number of
+/// iterations reaches `max_num_iterations` or exploitability is satisfied.
+///
+/// Unlike [`solve`], this method does not finalize the game. The game can therefore be saved and
+/// solved further later; call [`finalize`] once solving is complete.
+///
+/// This method returns the exploitability of the obtained strategy.
+pub fn continue_solve<T: Game>(
+    game: &mut T,
+    max_num_iterations: u32,
+    target_exploitability: f32,
+    print_progress: bool,
+) -> f32 {
+    continue_solve_with(
+        game,
+        CfrAlgorithm::Discounted,
+        max_num_iterations,
+        target_exploitability,
+        print_progress,
+    )
+}
+
+/// Resumes the given CFR variant from [`Game::num_iterations`] until the total number of
+/// iterations reaches `max_num_iterations` or exploitability is satisfied.
+///
+/// See [`continue_solve`] for details. Switching variants between calls is allowed, but the
+/// discount schedule of each variant assumes that it has been used from the first iteration.
+///
+/// This method returns the exploitability of the obtained strategy.
+pub fn continue_solve_with<T: Game>(
+    game: &mut T,
+    algorithm: CfrAlgorithm,
+    max_num_iterations: u32,
+    target_exploitability: f32,
+    print_progress: bool,
+) -> f32 {
+    let options = SolveOptions {
+        algorithm,
+        max_num_iterations,
+        target_exploitability,
+        compute_ev: false,
+        ..Default::default()
+    };
+
+    let print_iteration = |iteration: u32, exploitability: f32| {
+        if print_progress {
+            print!("\riteration: {iteration} / {max_num_iterations} ");
+            print!("(exploitability = {exploitability:.4e})");
+            io::stdout().flush().unwrap();
+        }
+    };
+
+    let summary = solve_internal(game, &options, |_| true, print_iteration);
+
+    if print_progress {
+        println!();
+        io::stdout().flush().unwrap();
+    }
+
+    summary.exploitability
+}
+
+/// Resumes solving from [`Game::num_iterations`] until one of the stop conditions of `options` is
+/// met, recording the convergence history.
+///
+/// The exploitability is computed at the start, every `check_interval` iterations, and when the
+/// solver stops; each result is appended to the history and passed to `on_point`. Returning
+/// `false` from `on_point` stops the solver. Like [`continue_solve`], the game is not finalized.
+///
+/// # Examples
+/// ```ignore
+/// let options = SolveOptions {
+///     max_num_iterations: 1000,
+///     time_limit: Some(Duration::from_secs(60)),
+///     ..Default::default()
+/// };
+/// let summary = continue_solve_with_options(&mut game, &options, |_| true);
+/// finalize(&mut game);
+/// ```
+pub fn continue_solve_with_options<T: Game>(
+    game: &mut T,
+    options: &SolveOptions,
+    on_point: impl FnMut(&ConvergencePoint) -> bool,
+) -> SolveSummary {
+    solve_internal(game, options, on_point, |_, _| {})
+}
+
+/// Implements [`continue_solve_with_options`]. `on_iteration` is called at the start and after
+/// every iteration with the iteration count and the latest computed exploitability.
+fn solve_internal<T: Game>(
+    game: &mut T,
+    options: &SolveOptions,
+    mut on_point: impl FnMut(&ConvergencePoint) -> bool,
+    mut on_iteration: impl FnMut(u32, f32),
+) -> SolveSummary {
     if game.is_solved() {
         panic!("Game is already solved");
     }
@@ -54,59 +435,124 @@ pub fn solve<T: Game>(
         panic!("Game is not ready");
     }
 
+    if options.algorithm.is_predictive() {
+        game.allocate_predictions();
+    }
+
+    let start_time = Instant::now();
+    let start_iteration = game.num_iterations();
+    let max_num_iterations = options.max_num_iterations;
+    let check_interval = options.check_interval.max(1);
+
+    let mut num_iterations = start_iteration;
+    let mut history = Vec::new();
+    let mut stop_reason = None;
+
     let mut root = game.root();
-    let mut exploitability = compute_exploitability(game);
 
-    if print_progress {
-        print!("iteration: 0 / {max_num_iterations} ");
-        print!("(exploitability = {exploitability:.4e})");
-        io::stdout().flush().unwrap();
+    let point = convergence_point(game, start_iteration, Duration::ZERO, options.compute_ev);
+    let mut exploitability = point.exploitability;
+    history.push(point);
+    on_iteration(start_iteration, exploitability);
+    if !on_point(&point) {
+        stop_reason = Some(StopReason::Interrupted);
     }
 
-    for t in 0..max_num_iterations {
-        if exploitability <= target_exploitability {
+    for t in start_iteration..max_num_iterations {
+        if stop_reason.is_some() {
             break;
         }
 
-        let params = DiscountParams::new(t);
-
-        // alternating updates
-        for player in 0..2 {
-            let mut result = Vec::with_capacity(game.num_private_hands(player));
-            solve_recursive(
-                result.spare_capacity_mut(),
-                game,
-                &mut root,
-                player,
-                game.initial_weights(player ^ 1),
-                &params,
-            );
+        if exploitability <= options.target_exploitability {
+            stop_reason = Some(StopReason::TargetExploitability);
+            break;
         }
 
-        if (t + 1) % 10 == 0 || t + 1 == max_num_iterations {
-            exploitability = compute_exploitability(game);
+        let params = DiscountParams::new(options.algorithm, t);
+        solve_iteration(game, &mut root, &params);
+
+        num_iterations = t + 1;
+
+        let is_timed_out = options
+            .time_limit
+            .is_some_and(|limit| start_time.elapsed() >= limit);
+
+        if (t + 1) % check_interval == 0 || t + 1 == max_num_iterations || is_timed_out {
+            let elapsed = start_time.elapsed();
+            let point = convergence_point(game, t + 1, elapsed, options.compute_ev);
+            exploitability = point.exploitability;
+            history.push(point);
+            if !on_point(&point) {
+                stop_reason = Some(StopReason::Interrupted);
+            }
         }
 
-        if print_progress {
-            print!("\riteration: {} / {} ", t + 1, max_num_iterations);
-            print!("(exploitability = {exploitability:.4e})");
-            io::stdout().flush().unwrap();
+        on_iteration(t + 1, exploitability);
+
+        if is_timed_out && stop_reason.is_none() {
+            stop_reason = Some(StopReason::TimeLimit);
         }
     }
 
-    if print_progress {
-        println!();
-        io::stdout().flush().unwrap();
-    }
+    let stop_reason = stop_reason.unwrap_or(if exploitability <= options.target_exploitability {
+        StopReason::TargetExploitability
+    } else {
+        StopReason::MaxIterations
+    });
 
-    finalize(game);
+    game.set_num_iterations(num_iterations);
 
-    exploitability
+    SolveSummary {
+        num_iterations,
+        exploitability,
+        stop_reason,
+        history,
+    }
+}
+
+/// Computes a point of the convergence history of the current strategy.
+///
+/// `iteration` and `elapsed` are recorded as they are.
+pub fn convergence_point<T: Game>(
+    game: &T,
+    iteration: u32,
+    elapsed: Duration,
+    compute_ev: bool,
+) -> ConvergencePoint {
+    ConvergencePoint {
+        iteration,
+        elapsed,
+        exploitability: compute_exploitability(game),
+        ev: compute_ev.then(|| compute_current_ev(game)),
+    }
 }
 
 /// Proceeds Discounted CFR algorithm for one iteration.
 #[inline]
 pub fn solve_step<T: Game>(game: &T, current_iteration: u32) {
+    solve_step_internal(game, CfrAlgorithm::Discounted, current_iteration);
+}
+
+/// Proceeds the given CFR variant for one iteration.
+#[inline]
+pub fn solve_step_with<T: Game>(game: &mut T, algorithm: CfrAlgorithm, current_iteration: u32) {
+    if game.is_solved() {
+        panic!("Game is already solved");
+    }
+
+    if !game.is_ready() {
+        panic!("Game is not ready");
+    }
+
+    // predictive variants read the previous regrets, so they must be allocated first
+    if algorithm.is_predictive() {
+        game.allocate_predictions();
+    }
+
+    solve_step_internal(game, algorithm, current_iteration);
+}
+
+fn solve_step_internal<T: Game>(game: &T, algorithm: CfrAlgorithm, current_iteration: u32) {
     if game.is_solved() {
         panic!("Game is already solved");
     }
@@ -116,19 +562,40 @@ pub fn solve_step<T: Game>(game: &T, current_iteration: u32) {
     }
 
     let mut root = game.root();
-    let params = DiscountParams::new(current_iteration);
+    let params = DiscountParams::new(algorithm, current_iteration);
+    solve_iteration(game, &mut root, &params);
+}
+
+/// Performs alternating updates of all players, including the re-solving gadget if any.
+fn solve_iteration<T: Game>(game: &T, root: &mut T::Node, params: &DiscountParams) {
+    let gadget = game.resolve_gadget();
+
+    for player in 0..game.num_players() {
+        let num_hands = game.num_private_hands(player);
+        let mut result = Vec::with_capacity(num_hands);
+
+        let follow_reach;
+        let cfreach = match gadget {
+            Some(gadget) if gadget.player() != player => {
+                follow_reach = gadget.follow_reach(game.initial_weights(player ^ 1));
+                follow_reach.as_slice()
+            }
+            _ => game.initial_opponent_weights(player),
+        };
 
-    // alternating updates
-    for player in 0..2 {
-        let mut result = Vec::with_capacity(game.num_private_hands(player));
         solve_recursive(
             result.spare_capacity_mut(),
             game,
-            &mut root,
+            root,
             player,
-            game.initial_weights(player ^ 1),
-            &params,
+            cfreach,
+            params,
         );
+
+        if let Some(gadget) = gadget.filter(|gadget| gadget.player() == player) {
+            unsafe { result.set_len(num_hands) };
+            gadget.update(&result);
+        }
     }
 }
 
@@ -252,6 +719,24 @@ fn solve_recursive<T: Game>(
         unsafe { cfv_actions.set_len(num_actions * num_hands) };
         let result = fma_slices_uninit(result, &strategy, &cfv_actions);
 
+        // predictive CFR: replace the previous instantaneous regrets with the current ones
+        #[cfg(feature = "custom-alloc")]
+        let mut prev_regrets = Vec::new_in(StackAlloc);
+        #[cfg(not(feature = "custom-alloc"))]
+        let mut prev_regrets = Vec::new();
+        if params.predictive {
+            let predictions = node.predictions_mut();
+            prev_regrets.extend_from_slice(predictions);
+            predictions
+                .chunks_exact_mut(num_hands)
+                .zip(cfv_actions.chunks_exact(num_hands))
+                .for_each(|(dst, src)| {
+                    dst.iter_mut()
+                        .zip(src.iter().zip(&*result))
+                        .for_each(|(d, (v, r))| *d = *v - *r);
+                });
+        }
+
         if game.is_compression_enabled() {
             // update the cumulative strategy
             let scale = node.strategy_scale();
@@ -279,13 +764,34 @@ fn solve_recursive<T: Game>(
             let beta_decoder = params.beta_t * scale / i16::MAX as f32;
             let cum_regret = node.regrets_compressed_mut();
 
-            cfv_actions.iter_mut().zip(&*cum_regret).for_each(|(x, y)| {
-                *x += *y as f32 * if *y >= 0 { alpha_decoder } else { beta_decoder };
-            });
-
-            cfv_actions.chunks_exact_mut(num_hands).for_each(|row| {
-                sub_slice(row, result);
-            });
+            if params.predictive {
+                let decoder = scale / i16::MAX as f32;
+                cfv_actions
+                    .chunks_exact_mut(num_hands)
+                    .zip(cum_regret.chunks_exact(num_hands))
+                    .zip(prev_regrets.chunks_exact(num_hands))
+                    .for_each(|((row, cum_row), prev_row)| {
+                        row.iter_mut()
+                            .zip(cum_row.iter().zip(prev_row))
+                            .zip(&*result)
+                            .for_each(|((x, (y, p)), r)| {
+                                let regret = *y as f32 * decoder;
+                                *x = predictive_regret(regret, *p, *x - *r, params.alpha_t);
+                            });
+                    });
+            } else {
+                cfv_actions.iter_mut().zip(&*cum_regret).for_each(|(x, y)| {
+                    *x += *y as f32 * if *y >= 0 { alpha_decoder } else { beta_decoder };
+                });
+
+                cfv_actions.chunks_exact_mut(num_hands).for_each(|row| {
+                    sub_slice(row, result);
+                });
+
+                if params.clamp_regrets {
+                    cfv_actions.iter_mut().for_each(|x| *x = max(*x, 0.0));
+                }
+            }
 
             if !locking.is_empty() {
                 cfv_actions.iter_mut().zip(locking).for_each(|(d, s)| {
@@ -308,13 +814,31 @@ fn solve_recursive<T: Game>(
             // update the cumulative regret
             let (alpha, beta) = (params.alpha_t, params.beta_t);
             let cum_regret = node.regrets_mut();
-            cum_regret.iter_mut().zip(&*cfv_actions).for_each(|(x, y)| {
-                let coef = if x.is_sign_positive() { alpha } else { beta };
-                *x = *x * coef + *y;
-            });
-            cum_regret.chunks_exact_mut(num_hands).for_each(|row| {
-                sub_slice(row, result);
-            });
+            if params.predictive {
+                cum_regret
+                    .chunks_exact_mut(num_hands)
+                    .zip(cfv_actions.chunks_exact(num_hands))
+                    .zip(prev_regrets.chunks_exact(num_hands))
+                    .for_each(|((row, cfv_row), prev_row)| {
+                        row.iter_mut()
+                            .zip(cfv_row.iter().zip(prev_row))
+                            .zip(&*result)
+                            .for_each(|((x, (y, p)), r)| {
+                                *x = predictive_regret(*x, *p, *y - *r, alpha);
+                            });
+                    });
+            } else {
+                cum_regret.iter_mut().zip(&*cfv_actions).for_each(|(x, y)| {
+                    let coef = if x.is_sign_positive() { alpha } else { beta };
+                    *x = *x * coef + *y;
+                });
+                cum_regret.chunks_exact_mut(num_hands).for_each(|row| {
+                    sub_slice(row, result);
+                });
+                if params.clamp_regrets {
+                    cum_regret.iter_mut().for_each(|x| *x = max(*x, 0.0));
+                }
+            }
         }
     }
     // if the current player is not `player`
@@ -332,9 +856,9 @@ fn solve_recursive<T: Game>(
 
         // update the reach probabilities
         let row_size = cfreach.len();
-        cfreach_actions.chunks_exact_mut(row_size).for_each(|row| {
-            mul_slice(row, cfreach);
-        });
+        let cfreach_multiway =
+            update_opponent_reach(game, player, node.player(), &mut cfreach_actions, cfreach);
+        let cfreach_actions = cfreach_multiway.as_deref().unwrap_or(&cfreach_actions);
 
         // compute the counterfactual values of each action
         for_each_child(node, |action| {
@@ -343,7 +867,7 @@ fn solve_recursive<T: Game>(
                 game,
                 &mut node.play(action),
                 player,
-                row(&cfreach_actions, action, row_size),
+                row(cfreach_actions, action, row_size),
                 params,
             );
         });
@@ -355,6 +879,16 @@ fn solve_recursive<T: Game>(
     }
 }
 
+/// Updates a cumulative regret of predictive CFR+.
+///
+/// The stored regret is the sum of the clamped cumulative regret and the previous prediction, so
+/// that regret matching on it yields the predictive strategy.
+#[inline]
+fn predictive_regret(stored: f32, prev_prediction: f32, instant: f32, alpha: f32) -> f32 {
+    let cum_regret = max((stored - prev_prediction) * alpha + instant, 0.0);
+    cum_regret + instant
+}
+
 /// Computes the strategy by regret-matching algorithm.
 #[cfg(feature = "custom-alloc")]
 #[inline]
@@ -0,0 +1,266 @@
+use crate::action_tree::*;
+use crate::game::*;
+use crate::interface::*;
+use crate::range::*;
+use crate::report::*;
+use crate::utility::*;
+use std::fmt::Write;
+
+/// Options for [`export_tree_json`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TreeExportOptions {
+    /// Maximum depth of the exported tree. Both player actions and dealt cards count as one level;
+    /// `0` exports the root node only.
+    pub max_depth: usize,
+
+    /// Whether to export every possible card at chance nodes. If `false`, chance nodes are
+    /// exported without children.
+    pub expand_chance: bool,
+
+    /// Whether to export the strategy and the expected value of each hand at player nodes.
+    pub include_hands: bool,
+}
+
+impl Default for TreeExportOptions {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            max_depth: 3,
+            expand_chance: false,
+            include_hands: true,
+        }
+    }
+}
+
+/// Exports the solved `game` from the root node as a JSON tree.
+///
+/// The result is an object with `board`, `startingPot`, `effectiveStack`, and `root` fields. Each
+/// node has a `type` (`"player"`, `"chance"`, or `"terminal"`), the `action` leading to it (absent
+/// at the root), the `pot` and the street `bets` of OOP and IP, and its `children` if the depth
+/// limit is not reached. Player nodes also have the acting `player` (`0` = OOP, `1` = IP), the
+/// `actions` and their overall `frequencies`, and optionally `hands` with the `weight`,
+/// `strategy`, and `ev` of each hand reaching the node.
+///
+/// The game is walked with [`PostFlopGame::play`], and the current node is restored afterwards.
+pub fn export_tree_json(
+    game: &mut PostFlopGame,
+    options: &TreeExportOptions,
+) -> Result<String, String> {
+    if !game.is_solved() {
+        return Err("Game is not solved".to_string());
+    }
+
+    let history = game.history().to_vec();
+    game.back_to_root();
+
+    let config = game.tree_config();
+    let mut ret = format!(
+        "{{\"board\":\"{}\",\"startingPot\":{},\"effectiveStack\":{},\"root\":",
+        board_to_string(&game.current_board())?,
+        config.starting_pot,
+        config.effective_stack
+    );
+
+    let result = write_node(game, options, None, 0, &mut ret);
+    game.apply_history(&history);
+    result?;
+
+    ret.push('}');
+    Ok(ret)
+}
+
+fn write_node(
+    game: &mut PostFlopGame,
+    options: &TreeExportOptions,
+    action: Option<Action>,
+    depth: usize,
+    out: &mut String,
+) -> Result<(), String> {
+    let bets = game.total_bet_amount();
+    let pot = game.tree_config().starting_pot + bets[0] + bets[1];
+
+    let node_type = if game.is_terminal_node() {
+        "terminal"
+    } else if game.is_chance_node() {
+        "chance"
+    } else {
+        "player"
+    };
+
+    write!(out, "{{\"type\":\"{node_type}\"").unwrap();
+    if let Some(action) = action {
+        write!(out, ",\"action\":\"{}\"", action_label(action)).unwrap();
+    }
+    write!(out, ",\"pot\":{pot},\"bets\":[{},{}]", bets[0], bets[1]).unwrap();
+
+    let expand = depth < options.max_depth;
+    let history = game.history().to_vec();
+
+    if game.is_terminal_node() {
+        // do nothing
+    } else if game.is_chance_node() {
+        if expand && options.expand_chance {
+            out.push_str(",\"children\":[");
+            let possible_cards = game.possible_cards();
+            let mut is_first = true;
+            for card in (0..52).filter(|&card| possible_cards & (1 << card) != 0) {
+                if !is_first {
+                    out.push(',');
+                }
+                is_first = false;
+                game.play(card as usize);
+                write_node(game, options, Some(Action::Chance(card)), depth + 1, out)?;
+                game.apply_history(&history);
+            }
+            out.push(']');
+        }
+    } else {
+        write_player_node(game, options, out)?;
+        if expand {
+            out.push_str(",\"children\":[");
+            for (index, action) in game.available_actions().into_iter().enumerate() {
+                if index > 0 {
+                    out.push(',');
+                }
+                game.play(index);
+                write_node(game, options, Some(action), depth + 1, out)?;
+                game.apply_history(&history);
+            }
+            out.push(']');
+        }
+    }
+
+    out.push('}');
+    Ok(())
+}
+
+fn write_player_node(
+    game: &mut PostFlopGame,
+    options: &TreeExportOptions,
+    out: &mut String,
+) -> Result<(), String> {
+    game.cache_normalized_weights();
+
+    let player = game.current_player();
+    let actions = game.available_actions();
+    let strategy = game.strategy();
+    let weights = game.normalized_weights(player);
+    let num_hands = weights.len();
+
+    let labels = actions
+        .iter()
+        .map(|&action| format!("\"{}\"", action_label(action)))
+        .collect::<Vec<_>>();
+    let frequencies = strategy
+        .chunks_exact(num_hands)
+        .map(|row| json_number(compute_average(row, weights)))
+        .collect::<Vec<_>>();
+
+    write!(
+        out,
+        ",\"player\":{player},\"actions\":[{}],\"frequencies\":[{}]",
+        labels.join(","),
+        frequencies.join(",")
+    )
+    .unwrap();
+
+    if options.include_hands {
+        let ev = game.expected_values(player);
+        let hands = holes_to_strings(game.private_cards(player))?;
+        out.push_str(",\"hands\":[");
+        let mut is_first = true;
+        for (index, hand) in hands.iter().enumerate() {
+            if weights[index] <= 0.0 {
+                continue;
+            }
+            if !is_first {
+                out.push(',');
+            }
+            is_first = false;
+            let strategy = (0..actions.len())
+                .map(|action| json_number(strategy[action * num_hands + index]))
+                .collect::<Vec<_>>();
+            write!(
+                out,
+                "{{\"hand\":\"{hand}\",\"weight\":{},\"strategy\":[{}],\"ev\":{}}}",
+                json_number(weights[index]),
+                strategy.join(","),
+                json_number(ev[index])
+            )
+            .unwrap();
+        }
+        out.push(']');
+    }
+
+    Ok(())
+}
+
+fn board_to_string(board: &[u8]) -> Result<String, String> {
+    board.iter().map(|&card| card_to_string(card)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::*;
+    use crate::solver::*;
+
+    #[test]
+    fn export_tree() {
+        let card_config = CardConfig {
+            range: ["AA,QQ".parse().unwrap(), "KK".parse().unwrap()],
+            flop: flop_from_str("2c6dTh").unwrap(),
+            turn: card_from_str("Qc").unwrap(),
+            river: NOT_DEALT,
+            deck: Deck::Standard,
+        };
+
+        let tree_config = TreeConfig {
+            initial_state: BoardState::Turn,
+            starting_pot: 60,
+            effective_stack: 970,
+            turn_bet_sizes: [("50%", "").try_into().unwrap(), Default::default()],
+            ..Default::default()
+        };
+
+        let action_tree = ActionTree::new(tree_config).unwrap();
+        let mut game = PostFlopGame::with_config(card_config, action_tree).unwrap();
+        game.allocate_memory(false);
+
+        let options = TreeExportOptions::default();
+        assert!(export_tree_json(&mut game, &options).is_err());
+
+        solve(&mut game, 100, 0.0, false);
+        game.play(1);
+
+        let json = export_tree_json(&mut game, &options).unwrap();
+        assert_eq!(game.history(), &[1]);
+        assert!(json.starts_with(
+            "{\"board\":\"2c6dThQc\",\"startingPot\":60,\"effectiveStack\":970,\"root\":\
+             {\"type\":\"player\",\"pot\":60,\"bets\":[0,0],\"player\":0,\
+             \"actions\":[\"Check\",\"Bet 30\"],\"frequencies\":["
+        ));
+        assert!(json.contains("{\"hand\":\"AdAc\",\"weight\":6,\"strategy\":["));
+        assert!(json.contains("{\"type\":\"terminal\",\"action\":\"Fold\",\"pot\":90,"));
+        assert!(json.contains("{\"type\":\"chance\",\"action\":\"Call\",\"pot\":120,"));
+        assert!(!json.contains("\"action\":\"2s\""));
+        assert!(json.ends_with("}"));
+
+        let options = TreeExportOptions {
+            max_depth: 0,
+            include_hands: false,
+            ..options
+        };
+        let json = export_tree_json(&mut game, &options).unwrap();
+        assert!(!json.contains("children") && !json.contains("hands"));
+
+        let options = TreeExportOptions {
+            max_depth: 3,
+            expand_chance: true,
+            ..options
+        };
+        let json = export_tree_json(&mut game, &options).unwrap();
+        assert_eq!(json.matches("\"action\":\"2s\"").count(), 2);
+        assert_eq!(json.matches('{').count(), json.matches('}').count());
+    }
+}
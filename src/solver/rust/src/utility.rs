@@ -252,14 +252,14 @@ pub fn finalize<T: Game>(game: &mut T) {
     }
 
     // compute the expected values and save them
-    for player in 0..2 {
+    for player in 0..game.num_players() {
         let mut cfvalues = Vec::with_capacity(game.num_private_hands(player));
         compute_cfvalue_recursive(
             cfvalues.spare_capacity_mut(),
             game,
             &mut game.root(),
             player,
-            game.initial_weights(player ^ 1),
+            game.initial_opponent_weights(player),
             true,
         );
     }
@@ -275,12 +275,21 @@ pub fn finalize<T: Game>(game: &mut T) {
 }
 
 /// Computes the exploitability of the current strategy.
+///
+/// With more than two players, this is the average gain of each player by switching to the MES.
 #[inline]
 pub fn compute_exploitability<T: Game>(game: &T) -> f32 {
     if !game.is_ready() && !game.is_solved() {
         panic!("Game is not ready");
     }
 
+    if game.num_players() > 2 {
+        let mes_ev = compute_mes_ev_multiway(game);
+        let current_ev = compute_current_ev_multiway(game);
+        let gain = mes_ev.iter().zip(&current_ev).map(|(m, c)| m - c);
+        return gain.sum::<f32>() / game.num_players() as f32;
+    }
+
     let mes_ev = compute_mes_ev(game);
     if !game.is_raked() {
         (mes_ev[0] + mes_ev[1]) * 0.5
@@ -296,31 +305,8 @@ pub fn compute_exploitability<T: Game>(game: &T) -> f32 {
 /// This treatment makes the return value zero-sum when not raked.
 #[inline]
 pub fn compute_current_ev<T: Game>(game: &T) -> [f32; 2] {
-    if !game.is_ready() && !game.is_solved() {
-        panic!("Game is not ready");
-    }
-
-    let mut cfvalues = [
-        Vec::with_capacity(game.num_private_hands(0)),
-        Vec::with_capacity(game.num_private_hands(1)),
-    ];
-
-    let reach = [game.initial_weights(0), game.initial_weights(1)];
-
-    for player in 0..2 {
-        compute_cfvalue_recursive(
-            cfvalues[player].spare_capacity_mut(),
-            game,
-            &mut game.root(),
-            player,
-            reach[player ^ 1],
-            false,
-        );
-        unsafe { cfvalues[player].set_len(game.num_private_hands(player)) };
-    }
-
-    let get_sum = |player: usize| weighted_sum(&cfvalues[player], reach[player]);
-    [get_sum(0), get_sum(1)]
+    let ev = compute_current_ev_multiway(game);
+    [ev[0], ev[1]]
 }
 
 /// Computes the expected values of the MES (Maximally Exploitative Strategy) of each player.
@@ -329,30 +315,50 @@ pub fn compute_current_ev<T: Game>(game: &T) -> [f32; 2] {
 /// Therefore, the average of the return value corresponds to the exploitability value if not raked.
 #[inline]
 pub fn compute_mes_ev<T: Game>(game: &T) -> [f32; 2] {
+    let ev = compute_mes_ev_multiway(game);
+    [ev[0], ev[1]]
+}
+
+/// Computes the expected values of the current strategy of all players.
+///
+/// The same as [`compute_current_ev`], but it supports games with more than two players, where
+/// the bias is (starting pot) / (number of players).
+#[inline]
+pub fn compute_current_ev_multiway<T: Game>(game: &T) -> Vec<f32> {
+    compute_ev_internal(game, false)
+}
+
+/// Computes the expected values of the MES of all players.
+///
+/// The same as [`compute_mes_ev`], but it supports games with more than two players.
+#[inline]
+pub fn compute_mes_ev_multiway<T: Game>(game: &T) -> Vec<f32> {
+    compute_ev_internal(game, true)
+}
+
+fn compute_ev_internal<T: Game>(game: &T, best_response: bool) -> Vec<f32> {
     if !game.is_ready() && !game.is_solved() {
         panic!("Game is not ready");
     }
 
-    let mut cfvalues = [
-        Vec::with_capacity(game.num_private_hands(0)),
-        Vec::with_capacity(game.num_private_hands(1)),
-    ];
-
-    let reach = [game.initial_weights(0), game.initial_weights(1)];
+    (0..game.num_players())
+        .map(|player| {
+            let num_hands = game.num_private_hands(player);
+            let cfreach = game.initial_opponent_weights(player);
+            let mut cfvalues = Vec::with_capacity(num_hands);
 
-    for player in 0..2 {
-        compute_best_cfv_recursive(
-            cfvalues[player].spare_capacity_mut(),
-            game,
-            &game.root(),
-            player,
-            reach[player ^ 1],
-        );
-        unsafe { cfvalues[player].set_len(game.num_private_hands(player)) };
-    }
+            if best_response {
+                let result = cfvalues.spare_capacity_mut();
+                compute_best_cfv_recursive(result, game, &game.root(), player, cfreach);
+            } else {
+                let result = cfvalues.spare_capacity_mut();
+                compute_cfvalue_recursive(result, game, &mut game.root(), player, cfreach, false);
+            }
 
-    let get_sum = |player: usize| weighted_sum(&cfvalues[player], reach[player]);
-    [get_sum(0), get_sum(1)]
+            unsafe { cfvalues.set_len(num_hands) };
+            weighted_sum(&cfvalues, game.initial_weights(player))
+        })
+        .collect()
 }
 
 /// The recursive helper function for computing the counterfactual values of the given strategy.
@@ -528,9 +534,9 @@ fn compute_cfvalue_recursive<T: Game>(
 
         // update the reach probabilities
         let row_size = cfreach.len();
-        cfreach_actions.chunks_exact_mut(row_size).for_each(|row| {
-            mul_slice(row, cfreach);
-        });
+        let cfreach_multiway =
+            update_opponent_reach(game, player, node.player(), &mut cfreach_actions, cfreach);
+        let cfreach_actions = cfreach_multiway.as_deref().unwrap_or(&cfreach_actions);
 
         // compute the counterfactual values of each action
         for_each_child(node, |action| {
@@ -539,7 +545,7 @@ fn compute_cfvalue_recursive<T: Game>(
                 game,
                 &mut node.play(action),
                 player,
-                row(&cfreach_actions, action, row_size),
+                row(cfreach_actions, action, row_size),
                 save_cfvalues,
             );
         });
@@ -697,9 +703,9 @@ fn compute_best_cfv_recursive<T: Game>(
 
         // update the reach probabilities
         let row_size = cfreach.len();
-        cfreach_actions.chunks_exact_mut(row_size).for_each(|row| {
-            mul_slice(row, cfreach);
-        });
+        let cfreach_multiway =
+            update_opponent_reach(game, player, node.player(), &mut cfreach_actions, cfreach);
+        let cfreach_actions = cfreach_multiway.as_deref().unwrap_or(&cfreach_actions);
 
         // compute the counterfactual values of each action
         for_each_child(node, |action| {
@@ -708,7 +714,7 @@ fn compute_best_cfv_recursive<T: Game>(
                 game,
                 &node.play(action),
                 player,
-                row(&cfreach_actions, action, row_size),
+                row(cfreach_actions, action, row_size),
             );
         });
 
@@ -827,3 +833,38 @@ pub(crate) fn apply_locking_strategy(dst: &mut [f32], locking: &[f32]) {
         });
     }
 }
+
+/// Multiplies each row of `cfreach_actions`, the strategy of `opponent`, by `opponent`'s reach
+/// probabilities within `cfreach`.
+///
+/// With more than two players, the rows only cover `opponent`'s part of `cfreach`, so the reach
+/// probabilities after each action are returned with the other opponents' parts copied in.
+#[inline]
+pub(crate) fn update_opponent_reach<T: Game>(
+    game: &T,
+    player: usize,
+    opponent: usize,
+    cfreach_actions: &mut [f32],
+    cfreach: &[f32],
+) -> Option<Vec<f32>> {
+    let range = game.opponent_reach_range(player, opponent);
+    let reach = &cfreach[range.clone()];
+    cfreach_actions
+        .chunks_exact_mut(reach.len())
+        .for_each(|row| {
+            mul_slice(row, reach);
+        });
+
+    if reach.len() == cfreach.len() {
+        return None;
+    }
+
+    let num_actions = cfreach_actions.len() / reach.len();
+    let mut ret = Vec::with_capacity(num_actions * cfreach.len());
+    for row in cfreach_actions.chunks_exact(reach.len()) {
+        ret.extend_from_slice(&cfreach[..range.start]);
+        ret.extend_from_slice(row);
+        ret.extend_from_slice(&cfreach[range.end..]);
+    }
+    Some(ret)
+}